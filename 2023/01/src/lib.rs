@@ -0,0 +1,134 @@
+use common::Answer;
+
+pub use scanner::{Match, Scanner, Vocabulary};
+
+mod scanner;
+
+#[tracing::instrument(skip_all)]
+pub fn part1(contents: &str) -> Result<Answer, String> {
+    calibrate(contents, &Scanner::new(Vocabulary::digits())).map(Answer::from)
+}
+
+#[tracing::instrument(skip_all)]
+pub fn part2(contents: &str) -> Result<Answer, String> {
+    part2_with(contents, Vocabulary::english())
+}
+
+/// Part 2 with `words` spelling out the digits instead.
+#[tracing::instrument(skip_all)]
+pub fn part2_with(contents: &str, words: Vocabulary) -> Result<Answer, String> {
+    calibrate(contents, &Scanner::new(words)).map(Answer::from)
+}
+
+/// Sum of the calibration values of every line.
+pub fn calibrate(contents: &str, scanner: &Scanner) -> Result<u64, String> {
+    contents
+        .lines()
+        .enumerate()
+        .try_fold(0u64, |sum, (i, l)| {
+            let value = calibration_value(l, scanner)
+                .map_err(|e| format!("line {}: {}", i + 1, e))?;
+
+            sum.checked_add(value).ok_or(format!(
+                "line {}: the calibration values add up to more than fit in a u64",
+                i + 1
+            ))
+        })
+}
+
+/// The first token's value followed by the last token's value. Multi digit
+/// values are concatenated, so `twenty` then `ten` reads as 2010.
+pub fn calibration_value(line: &str, scanner: &Scanner) -> Result<u64, String> {
+    let (first, last) = scanner
+        .first_and_last(line)
+        .ok_or(format!("no digit found in '{}'", line))?;
+
+    let too_big = || format!("'{}' calibrates to more than fits in a u64", line);
+    let place = 10u64
+        .checked_pow(last.value.checked_ilog10().unwrap_or(0) + 1)
+        .ok_or_else(too_big)?;
+
+    first.value
+        .checked_mul(place)
+        .and_then(|v| v.checked_add(last.value))
+        .ok_or_else(too_big)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn calibrates_digits() {
+        let s =
+r"1abc2
+pqr3stu8vwx
+a1b2c3d4e5f
+treb7uchet";
+        let scanner = Scanner::new(Vocabulary::digits());
+
+        assert_eq!(Ok(142), calibrate(s, &scanner));
+    }
+
+    #[test]
+    fn calibrates_words() {
+        let s =
+r"two1nine
+eightwothree
+abcone2threexyz
+xtwone3four
+4nineeightseven2
+zoneight234
+7pqrstsixteen";
+        let scanner = Scanner::new(Vocabulary::english());
+
+        assert_eq!(Ok(281), calibrate(s, &scanner));
+        assert_eq!(Ok(Answer::Int(281)), part2(s));
+    }
+
+    #[test]
+    fn calibrates_overlaps() {
+        let scanner = Scanner::new(Vocabulary::english());
+
+        assert_eq!(Ok(18), calibration_value("oneight", &scanner));
+        assert_eq!(Ok(21), calibration_value("twone", &scanner));
+        assert_eq!(Ok(88), calibration_value("eight", &scanner));
+    }
+
+    #[test]
+    fn calibrates_custom_vocabulary() {
+        let german: Vocabulary = "eins 1\nzwei 2\ndrei 3\nfünf 5".parse().unwrap();
+        let scanner = Scanner::new(Vocabulary::digits().with(german));
+
+        assert_eq!(Ok(25), calibration_value("xzweinsfünfx", &scanner));
+        assert_eq!(Ok(31), calibration_value("dreins", &scanner));
+
+        let tens: Vocabulary = "ten 10\ntwenty 20".parse().unwrap();
+        let scanner = Scanner::new(Vocabulary::digits().with(tens));
+
+        assert_eq!(Ok(2010), calibration_value("twentyten", &scanner));
+        assert_eq!(Ok(710), calibration_value("7ten", &scanner));
+    }
+
+    #[test]
+    fn errors_without_digits() {
+        let scanner = Scanner::new(Vocabulary::digits());
+
+        assert!(calibration_value("abc", &scanner).is_err());
+        assert_eq!(
+            Err("line 2: no digit found in 'abc'".to_string()),
+            calibrate("1abc2\nabc", &scanner)
+        );
+    }
+
+    #[test]
+    fn errors_on_overflow() {
+        let big: Vocabulary = "big 18446744073709551615\nhalf 9223372036854775807\nzero 0".parse().unwrap();
+        let scanner = Scanner::new(Vocabulary::digits().with(big));
+
+        assert!(calibration_value("1big", &scanner).is_err());
+        assert!(calibration_value("big1", &scanner).is_err());
+        assert_eq!(Ok(9223372036854775807), calibration_value("zerohalf", &scanner));
+        assert!(calibrate("zerohalf\nzerohalf\nzerohalf", &scanner).unwrap_err().starts_with("line 3: "));
+    }
+}
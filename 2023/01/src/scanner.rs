@@ -0,0 +1,279 @@
+use std::{collections::{HashMap, VecDeque}, fmt::Display, str::FromStr};
+
+const DIGIT_STR: [&str; 9] = [
+    "one",
+    "two",
+    "three",
+    "four",
+    "five",
+    "six",
+    "seven",
+    "eight",
+    "nine",
+];
+
+/// The tokens a scanner looks for, each with the value it stands for.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Vocabulary {
+    tokens: Vec<(String, u64)>,
+}
+
+impl Vocabulary {
+    pub fn new(tokens: Vec<(String, u64)>) -> Self {
+        Self { tokens }
+    }
+
+    /// Just the digits `1` to `9`.
+    pub fn digits() -> Self {
+        Self::new((1..=9).map(|d| (d.to_string(), d)).collect())
+    }
+
+    /// The digits plus `one` to `nine` spelled out.
+    pub fn english() -> Self {
+        let words = DIGIT_STR
+            .iter()
+            .zip(1..)
+            .map(|(w, d)| (w.to_string(), d))
+            .collect();
+
+        Self::digits().with(Self::new(words))
+    }
+
+    pub fn with(mut self, other: Self) -> Self {
+        self.tokens.extend(other.tokens);
+        self
+    }
+}
+
+/// One token per line: the token, whitespace, then its value, e.g. `eins 1`.
+impl FromStr for Vocabulary {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let tokens = s
+            .lines()
+            .filter(|l| !l.trim().is_empty())
+            .map(|l| {
+                let (token, value) = l
+                    .trim()
+                    .split_once(char::is_whitespace)
+                    .ok_or(format!("Expected '<token> <value>', got '{}'", l))?;
+                let value = value
+                    .trim()
+                    .parse()
+                    .map_err(|_| format!("Could not parse value of '{}'", token))?;
+
+                Ok((token.to_string(), value))
+            })
+            .collect::<Result<Vec<_>, String>>()?;
+
+        Ok(Self::new(tokens))
+    }
+}
+
+/// The same `<token> <value>` lines `from_str` reads.
+impl Display for Vocabulary {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        for (token, value) in self.tokens.iter() {
+            writeln!(f, "{} {}", token, value)?;
+        }
+
+        Ok(())
+    }
+}
+
+/// A token found in a line. `start` is a char index.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Match {
+    pub start: usize,
+    pub len: usize,
+    pub value: u64,
+}
+
+#[derive(Debug, Default)]
+struct Node {
+    next: HashMap<char, usize>,
+    fail: usize,
+    // indexes into the vocabulary of every token ending at this node
+    outputs: Vec<usize>,
+}
+
+/// An Aho-Corasick automaton over a vocabulary. It finds every occurrence of
+/// every token in one pass, overlapping ones included, so `oneight` yields
+/// both `one` and `eight`.
+#[derive(Debug)]
+pub struct Scanner {
+    nodes: Vec<Node>,
+    vocabulary: Vocabulary,
+}
+
+impl Scanner {
+    pub fn new(vocabulary: Vocabulary) -> Self {
+        let mut nodes = vec![Node::default()];
+
+        for (i, (token, _)) in vocabulary.tokens.iter().enumerate() {
+            let mut current = 0;
+            for c in token.chars() {
+                current = match nodes[current].next.get(&c) {
+                    Some(n) => *n,
+                    None => {
+                        nodes.push(Node::default());
+                        let n = nodes.len() - 1;
+                        nodes[current].next.insert(c, n);
+                        n
+                    }
+                };
+            }
+
+            nodes[current].outputs.push(i);
+        }
+
+        // breadth first, so every fail link points at a node that is already done
+        let mut queue: VecDeque<usize> = nodes[0].next.values().copied().collect();
+        while let Some(current) = queue.pop_front() {
+            let edges: Vec<_> = nodes[current].next.iter().map(|(c, n)| (*c, *n)).collect();
+
+            for (c, child) in edges {
+                let mut fail = nodes[current].fail;
+                let child_fail = loop {
+                    if let Some(n) = nodes[fail].next.get(&c) {
+                        break *n;
+                    }
+
+                    if fail == 0 {
+                        break 0;
+                    }
+
+                    fail = nodes[fail].fail;
+                };
+
+                nodes[child].fail = child_fail;
+                let inherited = nodes[child_fail].outputs.clone();
+                nodes[child].outputs.extend(inherited);
+
+                queue.push_back(child);
+            }
+        }
+
+        Self { nodes, vocabulary }
+    }
+
+    pub fn matches(&self, line: &str) -> Vec<Match> {
+        let mut matches = vec![];
+        let mut current = 0;
+
+        for (i, c) in line.chars().enumerate() {
+            current = loop {
+                if let Some(n) = self.nodes[current].next.get(&c) {
+                    break *n;
+                }
+
+                if current == 0 {
+                    break 0;
+                }
+
+                current = self.nodes[current].fail;
+            };
+
+            for output in self.nodes[current].outputs.iter() {
+                let (token, value) = &self.vocabulary.tokens[*output];
+                let len = token.chars().count();
+
+                matches.push(Match {
+                    start: i + 1 - len,
+                    len,
+                    value: *value,
+                });
+            }
+        }
+
+        matches
+    }
+
+    /// The first and last tokens of a line. When two tokens start at the same
+    /// place the longer one wins.
+    pub fn first_and_last(&self, line: &str) -> Option<(Match, Match)> {
+        let matches = self.matches(line);
+
+        let first = matches
+            .iter()
+            .min_by(|a, b| a.start.cmp(&b.start).then(b.len.cmp(&a.len)))?;
+        let last = matches
+            .iter()
+            .max_by(|a, b| a.start.cmp(&b.start).then(a.len.cmp(&b.len)))?;
+
+        Some((*first, *last))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn values(scanner: &Scanner, line: &str) -> Vec<u64> {
+        scanner.matches(line).iter().map(|m| m.value).collect()
+    }
+
+    #[test]
+    fn scanner_finds_digits() {
+        let scanner = Scanner::new(Vocabulary::digits());
+
+        assert_eq!(vec![1, 2], values(&scanner, "1abc2"));
+        assert_eq!(vec![7], values(&scanner, "treb7uchet"));
+        assert!(values(&scanner, "one").is_empty());
+    }
+
+    #[test]
+    fn scanner_finds_overlapping_words() {
+        let scanner = Scanner::new(Vocabulary::english());
+
+        assert_eq!(vec![1, 8], values(&scanner, "oneight"));
+        assert_eq!(vec![2, 1], values(&scanner, "twone"));
+        assert_eq!(vec![8, 2, 3], values(&scanner, "eightwothree"));
+        assert_eq!(vec![5, 8, 6, 2, 1], values(&scanner, "fivezg8jmf6hrxnhgxxttwoneg"));
+    }
+
+    #[test]
+    fn scanner_reports_positions() {
+        let scanner = Scanner::new(Vocabulary::english());
+        let exp = vec![
+            Match { start: 0, len: 3, value: 1 },
+            Match { start: 2, len: 5, value: 8 },
+        ];
+
+        assert_eq!(exp, scanner.matches("oneight"));
+    }
+
+    #[test]
+    fn scanner_prefers_longer_tokens() {
+        let vocabulary = Vocabulary::new(vec![
+            ("seven".into(), 7),
+            ("seventeen".into(), 17),
+        ]);
+        let scanner = Scanner::new(vocabulary);
+
+        let (first, last) = scanner.first_and_last("xseventeenx").unwrap();
+
+        assert_eq!(17, first.value);
+        assert_eq!(17, last.value);
+    }
+
+    #[test]
+    fn vocabulary_parses() {
+        let exp = Vocabulary::new(vec![("eins".into(), 1), ("zwei".into(), 2)]);
+
+        let vocabulary: Vocabulary = "eins 1\nzwei  2\n".parse().unwrap();
+
+        assert_eq!(exp, vocabulary);
+        assert!("eins".parse::<Vocabulary>().is_err());
+        assert!("eins one".parse::<Vocabulary>().is_err());
+    }
+
+    #[test]
+    fn vocabulary_prints_what_it_parses() {
+        let vocabulary = Vocabulary::new(vec![("eins".into(), 1), ("zwei".into(), 2)]);
+
+        assert_eq!("eins 1\nzwei 2\n", vocabulary.to_string());
+        assert_eq!(vocabulary, vocabulary.to_string().parse().unwrap());
+    }
+}
@@ -1,28 +1,37 @@
-use std::str::FromStr;
+use std::{fmt::Display, str::FromStr};
 
-#[derive(Debug, PartialEq)]
-pub enum Color {
-    Red(u32),
-    Green(u32),
-    Blue(u32),
+/// A number of cubes of one colour, e.g. `4 red`. Any colour name goes.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Color {
+    pub name: String,
+    pub value: u32,
+}
+
+impl Color {
+    pub fn new(name: &str, value: u32) -> Self {
+        Self { name: name.to_string(), value }
+    }
+}
+
+impl Display for Color {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{} {}", self.value, self.name)
+    }
 }
 
 impl FromStr for Color {
     type Err = String;
 
     fn from_str(s: &str) -> Result<Self, Self::Err> {
-        let (value, color) = s.split_once(' ').ok_or("Color: No space in string")?;
+        let (value, name) = s.split_once(' ').ok_or("Color: No space in string")?;
 
-        let value_fromstr = value.parse::<u32>().map_err(|_| "Could not parse value")?;
+        let value = value.parse::<u32>().map_err(|_| "Could not parse value")?;
 
-        let color = match color {
-            "red" => Ok(Color::Red(value_fromstr)),
-            "green" => Ok(Color::Green(value_fromstr)),
-            "blue" => Ok(Color::Blue(value_fromstr)),
-            _ => Err("Invalid color")
-        }?;
+        if name.is_empty() || name.contains(char::is_whitespace) {
+            return Err("Invalid color".into());
+        }
 
-        Ok(color)
+        Ok(Color::new(name, value))
     }
 }
 
@@ -33,13 +42,22 @@ mod tests {
     #[test]
     fn color_parses() {
         let expectations = vec![
-            ("4 red", Color::Red(4)),
-            ("12 green", Color::Green(12)),
-            ("3 blue", Color::Blue(3)),
+            ("4 red", Color::new("red", 4)),
+            ("12 green", Color::new("green", 12)),
+            ("3 blue", Color::new("blue", 3)),
+            ("7 mauve", Color::new("mauve", 7)),
         ];
 
         for (str, ex) in expectations {
             assert_eq!(str.parse::<Color>().unwrap(), ex);
         }
     }
+
+    #[test]
+    fn color_rejects_garbage() {
+        assert!("red".parse::<Color>().is_err());
+        assert!("x red".parse::<Color>().is_err());
+        assert!("4 ".parse::<Color>().is_err());
+        assert!("4 dark red".parse::<Color>().is_err());
+    }
 }
@@ -1,11 +1,11 @@
-use std::str::FromStr;
+use std::{fmt::Display, str::FromStr};
 
-use crate::{set::Set, color::Color};
+use crate::set::Set;
 
-#[derive(Debug)]
+#[derive(Debug, PartialEq, Eq)]
 pub struct Game {
-    id: u32,
-    sets: Vec<Set>,
+    pub id: u32,
+    pub sets: Vec<Set>,
 }
 
 impl Game {
@@ -19,40 +19,25 @@ impl Game {
         Some(self.id)
     }
 
+    /// The smallest bag this game could have been played with.
     pub fn get_min_set(&self) -> Set {
-        let mut red = Color::Red(0);
-        let mut green = Color::Green(0);
-        let mut blue = Color::Blue(0);
-
-        for set in self.sets.iter() {
-            for c in set.colors.iter() {
-                match c {
-                    Color::Red(v) => {
-                        if let Color::Red(c) = red {
-                            if c > *v { continue; }
-                        }
-
-                        red = Color::Red(*v);
-                    },
-                    Color::Green(v) => {
-                        if let Color::Green(c) = green {
-                            if c > *v { continue; }
-                        }
-
-                        green = Color::Green(*v);
-                    },
-                    Color::Blue(v) => {
-                        if let Color::Blue(c) = blue {
-                            if c > *v { continue; }
-                        }
-
-                        blue = Color::Blue(*v);
-                    },
-                }
+        self.sets.iter().fold(Set::default(), |acc, s| acc.max(s))
+    }
+}
+
+impl Display for Game {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "Game {}: ", self.id)?;
+
+        for (i, s) in self.sets.iter().enumerate() {
+            if i > 0 {
+                write!(f, "; ")?;
             }
+
+            write!(f, "{}", s)?;
         }
 
-        Set { colors: vec![red, green, blue] }
+        Ok(())
     }
 }
 
@@ -83,9 +68,9 @@ mod tests {
     fn game_parses() {
         let line = "Game 1: 3 blue, 4 red; 1 red, 2 green, 6 blue; 2 green";
         let sets = vec![
-            Set::new(vec![Color::Blue(3), Color::Red(4)]),
-            Set::new(vec![Color::Red(1), Color::Green(2), Color::Blue(6)]),
-            Set::new(vec![Color::Green(2)]),
+            Set::new(vec![Color::new("blue", 3), Color::new("red", 4)]),
+            Set::new(vec![Color::new("red", 1), Color::new("green", 2), Color::new("blue", 6)]),
+            Set::new(vec![Color::new("green", 2)]),
         ];
 
         let game: Game = line.parse().unwrap();
@@ -96,10 +81,17 @@ mod tests {
         }
     }
 
+    #[test]
+    fn game_displays_as_parsed() {
+        let line = "Game 1: 3 blue, 4 red; 1 red, 2 green, 6 blue; 2 green";
+
+        assert_eq!(line.parse::<Game>().unwrap().to_string(), line);
+    }
+
     #[test]
     fn game_1_is_possible() {
         let game: Game = "Game 1: 3 blue, 4 red; 1 red, 2 green, 6 blue; 2 green".parse().unwrap();
-        let set = Set::new(vec![Color::Red(12), Color::Green(13), Color::Blue(14)]);
+        let set = Set::new(vec![Color::new("red", 12), Color::new("green", 13), Color::new("blue", 14)]);
 
         assert_eq!(game.is_possible(&set), Some(1), "game 1 should've been possible");
     }
@@ -107,7 +99,7 @@ mod tests {
     #[test]
     fn game_2_is_possible() {
         let game: Game = "Game 2: 1 blue, 2 green; 3 green, 4 blue, 1 red; 1 green, 1 blue".parse().unwrap();
-        let set = Set::new(vec![Color::Red(12), Color::Green(13), Color::Blue(14)]);
+        let set = Set::new(vec![Color::new("red", 12), Color::new("green", 13), Color::new("blue", 14)]);
 
         assert_eq!(game.is_possible(&set), Some(2), "game 2 should've been possible");
     }
@@ -115,7 +107,7 @@ mod tests {
     #[test]
     fn game_3_is_not_possible() {
         let game: Game = "Game 3: 8 green, 6 blue, 20 red; 5 blue, 4 red, 13 green; 5 green, 1 red".parse().unwrap();
-        let set = Set::new(vec![Color::Red(12), Color::Green(13), Color::Blue(14)]);
+        let set = Set::new(vec![Color::new("red", 12), Color::new("green", 13), Color::new("blue", 14)]);
 
         assert_eq!(game.is_possible(&set), None, "game 3 shouldnt've been possible");
     }
@@ -123,7 +115,7 @@ mod tests {
     #[test]
     fn game_4_is_not_possible() {
         let game: Game = "Game 4: 1 green, 3 red, 6 blue; 3 green, 6 red; 3 green, 15 blue, 14 red".parse().unwrap();
-        let set = Set::new(vec![Color::Red(12), Color::Green(13), Color::Blue(14)]);
+        let set = Set::new(vec![Color::new("red", 12), Color::new("green", 13), Color::new("blue", 14)]);
 
         assert_eq!(game.is_possible(&set), None, "game 4 shouldnt've been possible");
     }
@@ -131,7 +123,7 @@ mod tests {
     #[test]
     fn game_5_is_possible() {
         let game: Game = "Game 5: 6 red, 1 blue, 3 green; 2 blue, 1 red, 2 green".parse().unwrap();
-        let set = Set::new(vec![Color::Red(12), Color::Green(13), Color::Blue(14)]);
+        let set = Set::new(vec![Color::new("red", 12), Color::new("green", 13), Color::new("blue", 14)]);
 
         assert_eq!(game.is_possible(&set), Some(5), "game 5 should've been possible");
     }
@@ -139,11 +131,11 @@ mod tests {
     #[test]
     fn game_gets_minimum_set() {
         let exps = vec![
-            ("Game 1: 3 blue, 4 red; 1 red, 2 green, 6 blue; 2 green", Set::new(vec![Color::Red(4), Color::Green(2), Color::Blue(6)])),
-            ("Game 2: 1 blue, 2 green; 3 green, 4 blue, 1 red; 1 green, 1 blue", Set::new(vec![Color::Red(1), Color::Green(3), Color::Blue(4)])),
-            ("Game 3: 8 green, 6 blue, 20 red; 5 blue, 4 red, 13 green; 5 green, 1 red", Set::new(vec![Color::Red(20), Color::Green(13), Color::Blue(6)])),
-            ("Game 4: 1 green, 3 red, 6 blue; 3 green, 6 red; 3 green, 15 blue, 14 red", Set::new(vec![Color::Red(14), Color::Green(3), Color::Blue(15)])),
-            ("Game 5: 6 red, 1 blue, 3 green; 2 blue, 1 red, 2 green", Set::new(vec![Color::Red(6), Color::Green(3), Color::Blue(2)])),
+            ("Game 1: 3 blue, 4 red; 1 red, 2 green, 6 blue; 2 green", Set::new(vec![Color::new("red", 4), Color::new("green", 2), Color::new("blue", 6)])),
+            ("Game 2: 1 blue, 2 green; 3 green, 4 blue, 1 red; 1 green, 1 blue", Set::new(vec![Color::new("red", 1), Color::new("green", 3), Color::new("blue", 4)])),
+            ("Game 3: 8 green, 6 blue, 20 red; 5 blue, 4 red, 13 green; 5 green, 1 red", Set::new(vec![Color::new("red", 20), Color::new("green", 13), Color::new("blue", 6)])),
+            ("Game 4: 1 green, 3 red, 6 blue; 3 green, 6 red; 3 green, 15 blue, 14 red", Set::new(vec![Color::new("red", 14), Color::new("green", 3), Color::new("blue", 15)])),
+            ("Game 5: 6 red, 1 blue, 3 green; 2 blue, 1 red, 2 green", Set::new(vec![Color::new("red", 6), Color::new("green", 3), Color::new("blue", 2)])),
         ];
 
         for (input, exp) in exps {
@@ -1,23 +1,183 @@
-use set::Set;
+use std::collections::BTreeSet;
 
-use crate::{game::Game, color::Color};
+use common::Answer;
+
+pub use crate::{color::Color, game::Game, set::Set};
 
 mod color;
 mod game;
 mod set;
 
-pub fn run(contents: &str) -> Result<(), String> {
-    let input: Vec<Game> = contents
+/// The bag from the puzzle, used when none is given.
+pub const DEFAULT_BAG: &str = "12 red, 13 green, 14 blue";
+
+common::params! {
+    #[derive(Debug, Clone)]
+    pub struct Rules {
+        /// the cubes in the bag, e.g. "12 red, 13 green, 14 blue"
+        bag: Set = DEFAULT_BAG.parse().expect("the default bag should parse"),
+    }
+}
+
+#[tracing::instrument(skip_all)]
+pub fn part1(contents: &str) -> Result<Answer, String> {
+    part1_with(contents, &DEFAULT_BAG.parse()?)
+}
+
+/// Part 1 with another bag.
+#[tracing::instrument(skip_all)]
+pub fn part1_with(contents: &str, bag: &Set) -> Result<Answer, String> {
+    let possible: u32 = possible_ids(&parse(contents)?, bag).iter().sum();
+
+    Ok(possible.into())
+}
+
+#[tracing::instrument(skip_all)]
+pub fn part2(contents: &str) -> Result<Answer, String> {
+    Ok(power_sum(&parse(contents)?)?.into())
+}
+
+pub fn parse(contents: &str) -> Result<Vec<Game>, String> {
+    contents
         .lines()
         .map(|l| l.parse::<Game>())
-        .collect::<Result<Vec<Game>, String>>()?;
+        .collect()
+}
+
+/// Every colour named anywhere in the games.
+pub fn palette(games: &[Game]) -> BTreeSet<&str> {
+    games
+        .iter()
+        .flat_map(|g| g.sets.iter())
+        .flat_map(|s| s.colors.iter())
+        .map(|c| c.name.as_str())
+        .collect()
+}
+
+pub fn possible_ids(games: &[Game], bag: &Set) -> Vec<u32> {
+    games.iter().filter_map(|g| g.is_possible(bag)).collect()
+}
+
+/// Share of the games the bag could have been used for, from 0 to 1.
+pub fn possibility(games: &[Game], bag: &Set) -> f64 {
+    if games.is_empty() {
+        return 0.0;
+    }
+
+    possible_ids(games, bag).len() as f64 / games.len() as f64
+}
+
+/// The candidate bags that make at least `n` of the games possible.
+pub fn bags_making_possible<'a>(games: &[Game], bags: &'a [Set], n: usize) -> Vec<&'a Set> {
+    bags.iter()
+        .filter(|b| possible_ids(games, b).len() >= n)
+        .collect()
+}
+
+/// The minimal bag of each game, without repeats, in game order.
+pub fn minimal_bags(games: &[Game]) -> Vec<Set> {
+    let mut bags: Vec<Set> = vec![];
+
+    for g in games {
+        let bag = g.get_min_set();
+        if !bags.contains(&bag) {
+            bags.push(bag);
+        }
+    }
+
+    bags
+}
+
+/// The most cubes of each colour seen in any one game, which is also the
+/// smallest bag that makes every game possible.
+pub fn color_maxima(games: &[Game]) -> Set {
+    games.iter().fold(Set::default(), |acc, g| acc.max(&g.get_min_set()))
+}
+
+/// Sum of the powers of each game's minimal bag. A colour from the palette
+/// that a game never shows counts as zero cubes.
+pub fn power_sum(games: &[Game]) -> Result<u64, String> {
+    let none = Set::zeroed(palette(games));
+
+    games.iter().try_fold(0u64, |sum, g| {
+        sum.checked_add(none.max(&g.get_min_set()).power()?)
+            .ok_or("the powers add up to more than fit in a u64".to_string())
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const SAMPLE: &str =
+r"Game 1: 3 blue, 4 red; 1 red, 2 green, 6 blue; 2 green
+Game 2: 1 blue, 2 green; 3 green, 4 blue, 1 red; 1 green, 1 blue
+Game 3: 8 green, 6 blue, 20 red; 5 blue, 4 red, 13 green; 5 green, 1 red
+Game 4: 1 green, 3 red, 6 blue; 3 green, 6 red; 3 green, 15 blue, 14 red
+Game 5: 6 red, 1 blue, 3 green; 2 blue, 1 red, 2 green";
+
+    fn bag(s: &str) -> Set {
+        s.parse().unwrap()
+    }
+
+    #[test]
+    fn solves_sample() {
+        let games = parse(SAMPLE).unwrap();
+
+        assert_eq!(vec![1, 2, 5], possible_ids(&games, &bag(DEFAULT_BAG)));
+        assert_eq!(Ok(2286), power_sum(&games));
+        assert_eq!(Ok(Answer::Int(8)), part1(SAMPLE));
+        assert_eq!(Ok(Answer::Int(2286)), part2(SAMPLE));
+    }
+
+    #[test]
+    fn finds_palette() {
+        let games = parse("Game 1: 1 mauve; 2 teal\nGame 2: 3 teal").unwrap();
+
+        assert_eq!(BTreeSet::from(["mauve", "teal"]), palette(&games));
+    }
+
+    #[test]
+    fn missing_colors_have_no_power() {
+        let games = parse("Game 1: 1 mauve, 2 teal\nGame 2: 3 teal").unwrap();
+
+        assert_eq!(Ok(2), power_sum(&games));
+
+        let huge = parse("Game 1: 4294967295 red, 4294967295 blue\nGame 2: 4294967295 red, 4294967295 blue").unwrap();
+        assert!(power_sum(&huge).is_err());
+    }
+
+    #[test]
+    fn computes_possibility() {
+        let games = parse(SAMPLE).unwrap();
+
+        assert_eq!(0.6, possibility(&games, &bag(DEFAULT_BAG)));
+        assert_eq!(0.0, possibility(&[], &bag(DEFAULT_BAG)));
+    }
+
+    #[test]
+    fn filters_bags_by_possible_games() {
+        let games = parse(SAMPLE).unwrap();
+        let bags = vec![
+            bag(DEFAULT_BAG),
+            bag("20 red, 13 green, 15 blue"),
+            bag("1 red, 1 green, 1 blue"),
+        ];
+
+        let found = bags_making_possible(&games, &bags, 3);
+
+        assert_eq!(vec![&bags[0], &bags[1]], found);
+        assert_eq!(vec![&bags[1]], bags_making_possible(&games, &bags, 5));
+    }
 
-    let set = Set::new(vec![Color::Red(12), Color::Green(13), Color::Blue(14)]);
-    let possible: u32 = input.iter().filter_map(|g| g.is_possible(&set)).sum();
-    let power: u32 = input.iter().map(|g| g.get_min_set().power()).sum();
+    #[test]
+    fn finds_minimal_bags_and_maxima() {
+        let games = parse("Game 1: 1 red; 2 blue\nGame 2: 2 blue, 1 red\nGame 3: 5 red").unwrap();
 
-    println!("solution 1: {}", possible);
-    println!("solution 2: {}", power);
+        assert_eq!(vec![bag("1 red, 2 blue"), bag("5 red")], minimal_bags(&games));
+        assert_eq!(bag("5 red, 2 blue"), color_maxima(&games));
 
-    Ok(())
+        let games = parse(SAMPLE).unwrap();
+        assert_eq!(bag("20 red, 13 green, 15 blue"), color_maxima(&games));
+    }
 }
@@ -1,12 +1,37 @@
-use std::{path::PathBuf, fs, env};
+use std::{env, error::Error, fs, path::Path};
 
-fn main() -> Result<(), std::io::Error> {
-    let args: Vec<_> = env::args().collect();
+use cube::{Rules, Set};
 
-    let filename = PathBuf::from(&args[1]);
+fn main() -> Result<(), Box<dyn Error>> {
+    let _day = common::trace::init("cube");
+
+    let args: Vec<String> = env::args().collect();
+    let (rules, args) = common::params::load::<Rules>(Path::new(env!("CARGO_MANIFEST_DIR")), &args)?;
+
+    let filename = args.get(1).expect("Filename");
     let contents = fs::read_to_string(filename)?;
 
-    cube::run(&contents);
+    // the bag can be given inline, e.g. "12 red, 13 green", or as a file
+    let bag: Set = match args.get(2) {
+        Some(bag) if Path::new(bag).is_file() => fs::read_to_string(bag)?.trim().parse()?,
+        Some(bag) => bag.parse()?,
+        None => rules.bag,
+    };
+
+    let games = cube::parse(&contents)?;
+
+    if common::answer::for_tools() {
+        common::answer::print(1, &cube::part1_with(&contents, &bag)?);
+        common::answer::print(2, &cube::part2(&contents)?);
+        return Ok(());
+    }
+
+    println!("solution 1: {}", cube::part1_with(&contents, &bag)?);
+    println!("solution 2: {}", cube::part2(&contents)?);
+
+    println!("possible with {}: {:.1}% of games", bag, cube::possibility(&games, &bag) * 100.0);
+    println!("per colour maxima: {}", cube::color_maxima(&games));
+    println!("distinct minimal bags: {}", cube::minimal_bags(&games).len());
 
     Ok(())
 }
@@ -1,8 +1,10 @@
-use std::str::FromStr;
+use std::{collections::BTreeMap, fmt::Display, str::FromStr};
 
 use crate::color::Color;
 
-#[derive(Debug, PartialEq)]
+/// A handful of cubes, or a bag to draw them from. Colours are whatever the
+/// input names; a colour that isn't listed counts as none at all.
+#[derive(Debug, Clone, Default)]
 pub struct Set {
     pub colors: Vec<Color>
 }
@@ -12,48 +14,71 @@ impl Set {
         Self { colors }
     }
 
+    /// A set holding none of each of the given colours.
+    pub fn zeroed<'a>(names: impl IntoIterator<Item = &'a str>) -> Self {
+        Self::new(names.into_iter().map(|n| Color::new(n, 0)).collect())
+    }
+
+    pub fn get(&self, name: &str) -> Option<u32> {
+        self.colors.iter().find(|c| c.name == name).map(|c| c.value)
+    }
+
+    pub fn counts(&self) -> BTreeMap<&str, u32> {
+        self.colors.iter().map(|c| (c.name.as_str(), c.value)).collect()
+    }
+
+    fn nonzero_counts(&self) -> BTreeMap<&str, u32> {
+        self.counts().into_iter().filter(|(_, v)| *v > 0).collect()
+    }
+
     pub fn is_possible_within(&self, other: &Self) -> bool {
-        let available_colors: Vec<_> = other.colors
-            .iter()
-            .map(std::mem::discriminant)
-            .collect();
-
-        for c in self.colors.iter() {
-            if !available_colors.contains(&std::mem::discriminant(c)) {
-                println!("My color: {:?} not found within {:?}", c, available_colors);
-                return false;
-            }
+        self.colors.iter().all(|c| {
+            other.get(&c.name).is_some_and(|available| c.value <= available)
+        })
+    }
 
-            for oc in other.colors.iter() {
-                if let (Color::Red(v), Color::Red(ov)) = (c, oc) {
-                    // println!("Comparing my red: {} to avail red: {}", v, ov);
-                    if v > ov { return false };
-                }
-                if let (Color::Green(v), Color::Green(ov)) = (c, oc) {
-                    // println!("Comparing my green: {} to avail green: {}", v, ov);
-                    if v > ov { return false };
-                }
-                if let (Color::Blue(v), Color::Blue(ov)) = (c, oc) {
-                    // println!("Comparing my blue: {} to avail blue: {}", v, ov);
-                    if v > ov { return false };
-                }
+    /// The larger count of every colour in either set, keeping the order the
+    /// colours were first seen in.
+    pub fn max(&self, other: &Self) -> Self {
+        let mut colors: Vec<Color> = vec![];
+
+        for c in self.colors.iter().chain(other.colors.iter()) {
+            match colors.iter_mut().find(|m| m.name == c.name) {
+                Some(m) => m.value = m.value.max(c.value),
+                None => colors.push(c.clone()),
             }
         }
 
-        true
+        Self { colors }
+    }
+
+    pub fn power(&self) -> Result<u64, String> {
+        self.colors.iter().try_fold(1u64, |power, c| {
+            power.checked_mul(c.value as u64).ok_or(format!("Set: power of '{}' is too big", self))
+        })
+    }
+}
+
+// none of a colour is the same as not listing it
+impl PartialEq for Set {
+    fn eq(&self, other: &Self) -> bool {
+        self.nonzero_counts() == other.nonzero_counts()
     }
+}
 
-    pub fn power(&self) -> u32 {
-        let mut power = 1;
-        for color in self.colors.iter() {
-            match color {
-                Color::Red(v) => power *= v,
-                Color::Green(v) => power *= v,
-                Color::Blue(v) => power *=v,
+impl Eq for Set {}
+
+impl Display for Set {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        for (i, c) in self.colors.iter().enumerate() {
+            if i > 0 {
+                write!(f, ", ")?;
             }
+
+            write!(f, "{}", c)?;
         }
 
-        power
+        Ok(())
     }
 }
 
@@ -66,7 +91,12 @@ impl FromStr for Set {
             .map(|c| c.parse::<Color>())
             .collect::<Result<Vec<Color>, Self::Err>>()?;
 
-        Ok(Self { colors })
+        let set = Self { colors };
+        if set.counts().len() != set.colors.len() {
+            return Err(format!("Set: duplicate color in '{}'", s));
+        }
+
+        Ok(set)
     }
 }
 
@@ -77,9 +107,9 @@ mod tests {
     #[test]
     fn set_parses() {
         let expectations = vec![
-            ("3 blue, 4 red", Set { colors: vec![Color::Blue(3), Color::Red(4)] }),
-            ("1 red, 2 green, 6 blue", Set { colors: vec![Color::Red(1), Color::Green(2), Color::Blue(6)]}),
-            ("2 green", Set { colors: vec![Color::Green(2)] }),
+            ("3 blue, 4 red", Set { colors: vec![Color::new("blue", 3), Color::new("red", 4)] }),
+            ("1 red, 2 green, 6 blue", Set { colors: vec![Color::new("red", 1), Color::new("green", 2), Color::new("blue", 6)]}),
+            ("2 green", Set { colors: vec![Color::new("green", 2)] }),
         ];
 
         for (str, ex) in expectations {
@@ -89,48 +119,48 @@ mod tests {
 
     #[test]
     fn set_impossible_value_single() {
-        let me = Set::new(vec![Color::Red(2)]);
-        let other = Set::new(vec![Color::Red(1)]);
+        let me = Set::new(vec![Color::new("red", 2)]);
+        let other = Set::new(vec![Color::new("red", 1)]);
 
         assert!(!me.is_possible_within(&other), "2 red should not be possible within 1 red");
     }
 
     #[test]
     fn set_impossible_variant_single() {
-        let me = Set::new(vec![Color::Green(1)]);
-        let other = Set::new(vec![Color::Red(2)]);
+        let me = Set::new(vec![Color::new("green", 1)]);
+        let other = Set::new(vec![Color::new("red", 2)]);
 
         assert!(!me.is_possible_within(&other), "1 green should not be possible within 2 red");
     }
 
     #[test]
     fn set_impossible_game_3() {
-        let me = Set::new(vec![Color::Green(8), Color::Blue(6), Color::Red(20)]);
-        let other = Set::new(vec![Color::Red(12), Color::Green(13), Color::Blue(14)]);
+        let me = Set::new(vec![Color::new("green", 8), Color::new("blue", 6), Color::new("red", 20)]);
+        let other = Set::new(vec![Color::new("red", 12), Color::new("green", 13), Color::new("blue", 14)]);
 
         assert!(!me.is_possible_within(&other), "game 3 should be impossible");
     }
 
     #[test]
     fn set_possible_value_single() {
-        let me = Set::new(vec![Color::Red(1)]);
-        let other = Set::new(vec![Color::Red(2)]);
+        let me = Set::new(vec![Color::new("red", 1)]);
+        let other = Set::new(vec![Color::new("red", 2)]);
 
         assert!(me.is_possible_within(&other), "1 red should be possible within 2 red");
     }
 
     #[test]
     fn set_possible_value_multiple() {
-        let me = Set::new(vec![Color::Red(2)]);
-        let other = Set::new(vec![Color::Red(2), Color::Green(1)]);
+        let me = Set::new(vec![Color::new("red", 2)]);
+        let other = Set::new(vec![Color::new("red", 2), Color::new("green", 1)]);
 
         assert!(me.is_possible_within(&other), "2 red should be possible within 2 red, 1 green");
     }
 
     #[test]
     fn set_possible_misordered() {
-        let me = Set::new(vec![Color::Red(2)]);
-        let other = Set::new(vec![Color::Green(1), Color::Red(2), Color::Blue(3)]);
+        let me = Set::new(vec![Color::new("red", 2)]);
+        let other = Set::new(vec![Color::new("green", 1), Color::new("red", 2), Color::new("blue", 3)]);
 
         assert!(me.is_possible_within(&other), "2 red should be possible within 1 green, 2 red, 3 blue");
     }
@@ -138,13 +168,13 @@ mod tests {
     #[test]
     fn set_is_possible() {
         let failures = vec![
-            (Set::new(vec![Color::Red(2)]), Set::new(vec![Color::Red(1)]), "Value comparison failed"),
-            (Set::new(vec![Color::Green(0)]), Set::new(vec![Color::Red(1)]), "Variant comparison failed"),
+            (Set::new(vec![Color::new("red", 2)]), Set::new(vec![Color::new("red", 1)]), "Value comparison failed"),
+            (Set::new(vec![Color::new("green", 0)]), Set::new(vec![Color::new("red", 1)]), "Variant comparison failed"),
         ];
 
         let successes = vec![
-            (Set::new(vec![Color::Red(1)]), Set::new(vec![Color::Red(2)]), "Value comparison failed"),
-            (Set::new(vec![Color::Green(3)]), Set::new(vec![Color::Red(1), Color::Green(3)]), "Variant list comparison failed"),
+            (Set::new(vec![Color::new("red", 1)]), Set::new(vec![Color::new("red", 2)]), "Value comparison failed"),
+            (Set::new(vec![Color::new("green", 3)]), Set::new(vec![Color::new("red", 1), Color::new("green", 3)]), "Variant list comparison failed"),
         ];
 
         for (set, other, m) in failures {
@@ -156,18 +186,56 @@ mod tests {
         }
     }
 
+    #[test]
+    fn set_rejects_duplicate_colors() {
+        assert!("1 red, 2 red".parse::<Set>().is_err());
+    }
+
+    #[test]
+    fn set_equality_ignores_order() {
+        let me: Set = "1 red, 2 green".parse().unwrap();
+        let other: Set = "2 green, 1 red".parse().unwrap();
+
+        assert_eq!(me, other);
+        assert_eq!(me, "1 red, 0 blue, 2 green".parse().unwrap());
+        assert_ne!(me, "1 red".parse().unwrap());
+    }
+
+    #[test]
+    fn set_takes_max() {
+        let me: Set = "3 blue, 4 red".parse().unwrap();
+        let other: Set = "1 red, 2 green, 6 blue".parse().unwrap();
+
+        let max = me.max(&other);
+
+        assert_eq!(max.to_string(), "6 blue, 4 red, 2 green");
+    }
+
+    #[test]
+    fn set_handles_any_color() {
+        let me: Set = "2 mauve, 1 teal".parse().unwrap();
+        let bag: Set = "3 mauve, 1 teal, 9 red".parse().unwrap();
+
+        assert!(me.is_possible_within(&bag));
+        assert!(!bag.is_possible_within(&me));
+        assert_eq!(Ok(2), me.power());
+    }
+
     #[test]
     fn set_power() {
         let exps = vec![
-            (48, Set::new(vec![Color::Red(4), Color::Green(2), Color::Blue(6)])),
-            (12, Set::new(vec![Color::Red(1), Color::Green(3), Color::Blue(4)])),
-            (1560, Set::new(vec![Color::Red(20), Color::Green(13), Color::Blue(6)])),
-            (630, Set::new(vec![Color::Red(14), Color::Green(3), Color::Blue(15)])),
-            (36, Set::new(vec![Color::Red(6), Color::Green(3), Color::Blue(2)])),
+            (48, Set::new(vec![Color::new("red", 4), Color::new("green", 2), Color::new("blue", 6)])),
+            (12, Set::new(vec![Color::new("red", 1), Color::new("green", 3), Color::new("blue", 4)])),
+            (1560, Set::new(vec![Color::new("red", 20), Color::new("green", 13), Color::new("blue", 6)])),
+            (630, Set::new(vec![Color::new("red", 14), Color::new("green", 3), Color::new("blue", 15)])),
+            (36, Set::new(vec![Color::new("red", 6), Color::new("green", 3), Color::new("blue", 2)])),
         ];
 
         for (ex, s) in exps {
-            assert_eq!(ex, s.power());
+            assert_eq!(Ok(ex), s.power());
         }
+
+        let huge: Set = "4294967295 red, 4294967295 green, 2 blue".parse().unwrap();
+        assert!(huge.power().is_err());
     }
 }
@@ -1,3 +1,5 @@
+use std::fmt::Display;
+
 #[derive(Clone, Copy, Debug, PartialEq, Eq)]
 pub enum Element {
     None,
@@ -7,7 +9,7 @@ pub enum Element {
 
 impl From<char> for Element {
     fn from(value: char) -> Self {
-        if value == '.' {
+        if value == '.' || value.is_whitespace() {
             return Self::None;
         }
 
@@ -19,6 +21,16 @@ impl From<char> for Element {
     }
 }
 
+impl Display for Element {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::None => write!(f, "."),
+            Self::Number(d) => write!(f, "{}", d),
+            Self::Symbol(c) => write!(f, "{}", c),
+        }
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::Element;
@@ -46,4 +58,13 @@ mod tests {
 
         assert_eq!(exp, c.into());
     }
+
+    #[test]
+    fn element_displays_as_parsed() {
+        for c in ".7$".chars() {
+            assert_eq!(c.to_string(), Element::from(c).to_string());
+        }
+
+        assert_eq!(".", Element::from(' ').to_string());
+    }
 }
@@ -1,12 +1,12 @@
-use std::{str::FromStr, collections::VecDeque, ops::RangeInclusive};
+use std::{fmt::Display, str::FromStr, ops::RangeInclusive};
 
-use crate::{element::Element, point::Point};
+use crate::{element::Element, point::Point, schematic::Number};
 
 #[derive(Debug, PartialEq)]
 pub struct Grid {
     array: Vec<Vec<Element>>,
-    width: usize,
-    height: usize,
+    pub width: usize,
+    pub height: usize,
 }
 
 impl Grid {
@@ -24,27 +24,20 @@ impl Grid {
         pos
     }
 
-    pub fn find_part_numbers(&self, p: Point) -> Vec<u32> {
-        let mut part_numbers = vec![];
+    pub fn find_numbers(&self) -> Vec<Number> {
+        let mut numbers = vec![];
 
-        // there is an assumption that the neighbor positions are ordered
-        let neighbors = p.get_neighbor_positions(self.width, self.height);
-        let mut visited: Vec<&Point> = vec![];
+        for y in 0..self.height {
+            let mut x = 0;
+            while x < self.width {
+                let p = Point::new(x, y);
+                let Some(Element::Number(_)) = self.get(&p) else {
+                    x += 1;
+                    continue;
+                };
 
-        for (i, n) in neighbors.iter().enumerate() {
-            if visited.contains(&n) { 
-                continue;
-            }
-
-            let e = self.get(n);
-            if let Some(Element::Number(_)) = e {
-                // find beginning and end cursor of the number
-                let cursors = self.find_number_cursors(n);
-                let row = &self.array[n.y];
-
-                let digit_elements = &row[cursors.clone()];
-
-                let digits: Vec<_> = digit_elements
+                let span = self.find_number_cursors(&p);
+                let digits: Vec<_> = self.array[y][span.clone()]
                     .iter()
                     .filter_map(|e| {
                         if let Element::Number(n) = e { return Some(*n) }
@@ -52,21 +45,16 @@ impl Grid {
                     })
                     .collect();
 
-                // need to update visited as needed
-                for o in neighbors.iter() {
-                    let on_same_row = o.y == n.y;
-                    let within_cursor_range = cursors.contains(&o.x);
-                    if on_same_row && within_cursor_range {
-                        visited.push(o);
-                    }
-                }
-
-                let part_number = digits_to_number(&digits);
-                part_numbers.push(part_number);
+                x = span.end() + 1;
+                numbers.push(Number {
+                    value: digits_to_number(&digits),
+                    row: y,
+                    span,
+                });
             }
         }
 
-        part_numbers
+        numbers
     }
 
     fn get(&self, p: &Point) -> Option<Element> {
@@ -124,15 +112,33 @@ fn digits_to_number(ds: &[u32]) -> u32 {
     value
 }
 
+impl Display for Grid {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        for row in self.array.iter() {
+            for el in row.iter() {
+                write!(f, "{}", el)?;
+            }
+
+            writeln!(f)?;
+        }
+
+        Ok(())
+    }
+}
+
 impl FromStr for Grid {
     type Err = String;
 
     fn from_str(s: &str) -> Result<Self, Self::Err> {
-        let width = s.find('\n').unwrap_or(s.len());
+        let width = s.lines().next().map_or(0, |l| l.chars().count());
         let height = s.lines().count();
         let mut array = vec![vec![Element::None; width]; height];
 
         for (y, line) in s.lines().enumerate() {
+            if line.chars().count() != width {
+                return Err(format!("row {} is not {} wide", y, width));
+            }
+
             for (x, el) in line.chars().enumerate() {
                 array[y][x] = el.into();
             }
@@ -181,6 +187,20 @@ mod tests {
         assert_eq!(exp, grid, "Grids should be equal");
     }
 
+    #[test]
+    fn grid_displays_as_parsed() {
+        let s = "467..114..\n...*......\n";
+        let grid: Grid = s.parse().unwrap();
+
+        assert_eq!(s, grid.to_string());
+    }
+
+    #[test]
+    fn grid_rejects_ragged_rows() {
+        assert!("..\n...".parse::<Grid>().is_err());
+        assert!("..\n.".parse::<Grid>().is_err());
+    }
+
     #[test]
     fn grid_gets_symbols() {
         let input = "_";
@@ -223,32 +243,15 @@ mod tests {
     }
 
     #[test]
-    fn grid_finds_part_numbers_simple() {
-        let input = 
-r"1.2
-3$4
-5.6";
-        let point = Point::new(1, 1);
-        let grid: Grid = input.parse().unwrap();
-        let exp = vec![1, 2, 3, 4, 5, 6];
-
-        let part_numbers = grid.find_part_numbers(point);
-
-        assert_eq!(exp, part_numbers);
-    }
-
-    #[test]
-    fn o_grid_finds_part_numbers_complex() {
-        let input = 
-r"11.
-3$4
-556";
-        let point = Point::new(1, 1);
-        let grid: Grid = input.parse().unwrap();
-        let exp = vec![11, 3, 4, 556];
-
-        let part_numbers = grid.find_part_numbers(point);
-
-        assert_eq!(exp, part_numbers);
+    fn grid_finds_numbers() {
+        let grid: Grid = "11.\n3$4\n556".parse().unwrap();
+        let exp = vec![
+            Number { value: 11, row: 0, span: 0..=1 },
+            Number { value: 3, row: 1, span: 0..=0 },
+            Number { value: 4, row: 1, span: 2..=2 },
+            Number { value: 556, row: 2, span: 0..=2 },
+        ];
+
+        assert_eq!(exp, grid.find_numbers());
     }
 }
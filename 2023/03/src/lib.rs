@@ -1,29 +1,72 @@
-use grid::Grid;
+use common::Answer;
+
+pub use crate::{element::Element, grid::Grid, point::Point, schematic::{Number, Schematic, Symbol}};
 
 mod element;
 mod grid;
 mod point;
+mod schematic;
+
+/// The sum of the part numbers.
+#[tracing::instrument(skip_all)]
+pub fn part1(contents: &str) -> Result<Answer, String> {
+    let part_number_sum: u32 = parse(contents)?
+        .part_numbers()
+        .iter()
+        .map(|n| n.value)
+        .sum();
+
+    Ok(part_number_sum.into())
+}
+
+/// The sum of the gear ratios.
+#[tracing::instrument(skip_all)]
+pub fn part2(contents: &str) -> Result<Answer, String> {
+    let gear_ratio_sum = parse(contents)?
+        .ratio_sums(2)
+        .get(&'*')
+        .copied()
+        .unwrap_or(0);
+
+    Ok(gear_ratio_sum.into())
+}
 
-pub fn run(contents: &str) {
-    let grid: Grid = contents.parse().unwrap();
+pub fn parse(contents: &str) -> Result<Schematic, String> {
+    let grid: Grid = contents.parse()?;
 
-    let symbol_positions = grid.find_symbol_positions();
+    Ok(Schematic::from(&grid))
+}
 
-    let mut part_number_sum = 0;
-    let mut gear_ratio_sum = 0;
-    for (c, sp) in symbol_positions {
-        let part_numbers = grid.find_part_numbers(sp);
-        part_number_sum += part_numbers.iter().sum::<u32>();
+/// The schematic graph as `dot` or `json`.
+pub fn export(contents: &str, format: &str) -> Result<String, String> {
+    let schematic = parse(contents)?;
 
-        let is_gear = c == '*';
-        let has_two_numbers = part_numbers.len() == 2;
-        if is_gear && has_two_numbers {
-            let first = part_numbers[0];
-            let second = part_numbers[1];
-            gear_ratio_sum += first * second;
-        }
+    match format {
+        "dot" => Ok(schematic.to_dot()),
+        "json" => Ok(schematic.to_json()),
+        _ => Err(format!("Unknown export format '{}', expected dot or json", format)),
     }
+}
 
-    println!("solution 1 (sum of part numbers): {}", part_number_sum);
-    println!("solution 2 (sum of gear ratios): {}", gear_ratio_sum);
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const SAMPLE: &str =
+r"467..114..
+...*......
+..35..633.
+......#...
+617*......
+.....+.58.
+..592.....
+......755.
+...$.*....
+.664.598..";
+
+    #[test]
+    fn solves_sample() {
+        assert_eq!(Ok(Answer::Int(4361)), part1(SAMPLE));
+        assert_eq!(Ok(Answer::Int(467835)), part2(SAMPLE));
+    }
 }
@@ -1,12 +1,28 @@
 use std::{env, fs};
 
 
-fn main() {
+fn main() -> Result<(), String> {
+    let _day = common::trace::init("gear");
+
     let args: Vec<_> = env::args().collect();
-    let filename = args.get(1).expect("Should give me a filename as only arg");
+    let filename = args.get(1).expect("Should give me a filename as first arg");
 
     let contents = fs::read_to_string(filename).expect("filename should exist");
 
-    gear::run(&contents);
-}
+    if common::answer::for_tools() {
+        common::answer::print(1, &gear::part1(&contents)?);
+        common::answer::print(2, &gear::part2(&contents)?);
+        return Ok(());
+    }
 
+    // an optional format dumps the schematic graph instead of solving
+    match args.get(2) {
+        Some(format) => print!("{}", gear::export(&contents, format)?),
+        None => {
+            println!("solution 1 (sum of part numbers): {}", gear::part1(&contents)?);
+            println!("solution 2 (sum of gear ratios): {}", gear::part2(&contents)?);
+        }
+    }
+
+    Ok(())
+}
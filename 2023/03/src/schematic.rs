@@ -0,0 +1,275 @@
+use std::{collections::{BTreeMap, HashMap}, fmt::Write, ops::RangeInclusive};
+
+use common::json::escape;
+
+use crate::{grid::Grid, point::Point};
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Number {
+    pub value: u32,
+    pub row: usize,
+    pub span: RangeInclusive<usize>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Symbol {
+    pub c: char,
+    pub position: Point,
+}
+
+/// The schematic as a bipartite graph: numbers on one side, symbols on the
+/// other, and an edge wherever a symbol touches any digit of a number.
+#[derive(Debug)]
+pub struct Schematic {
+    pub numbers: Vec<Number>,
+    pub symbols: Vec<Symbol>,
+    // indexes into `numbers` for each symbol, in neighbour order
+    symbol_edges: Vec<Vec<usize>>,
+    // indexes into `symbols` for each number
+    number_edges: Vec<Vec<usize>>,
+}
+
+impl From<&Grid> for Schematic {
+    fn from(grid: &Grid) -> Self {
+        let numbers = grid.find_numbers();
+        let symbols: Vec<_> = grid
+            .find_symbol_positions()
+            .into_iter()
+            .map(|(c, position)| Symbol { c, position })
+            .collect();
+
+        let mut owners = HashMap::new();
+        for (i, n) in numbers.iter().enumerate() {
+            for x in n.span.clone() {
+                owners.insert(Point::new(x, n.row), i);
+            }
+        }
+
+        let mut symbol_edges = vec![vec![]; symbols.len()];
+        let mut number_edges = vec![vec![]; numbers.len()];
+
+        for (s, symbol) in symbols.iter().enumerate() {
+            for p in symbol.position.get_neighbor_positions(grid.width, grid.height) {
+                let Some(&n) = owners.get(&p) else {
+                    continue;
+                };
+
+                // several neighbours can belong to the same number
+                if symbol_edges[s].contains(&n) {
+                    continue;
+                }
+
+                symbol_edges[s].push(n);
+                number_edges[n].push(s);
+            }
+        }
+
+        Self {
+            numbers,
+            symbols,
+            symbol_edges,
+            number_edges,
+        }
+    }
+}
+
+impl Schematic {
+    pub fn numbers_around(&self, symbol: usize) -> Vec<&Number> {
+        self.symbol_edges[symbol].iter().map(|n| &self.numbers[*n]).collect()
+    }
+
+    pub fn symbols_around(&self, number: usize) -> Vec<&Symbol> {
+        self.number_edges[number].iter().map(|s| &self.symbols[*s]).collect()
+    }
+
+    /// Numbers touching at least one symbol. Each is listed once, however
+    /// many symbols it touches.
+    pub fn part_numbers(&self) -> Vec<&Number> {
+        self.numbers
+            .iter()
+            .zip(self.number_edges.iter())
+            .filter(|(_, edges)| !edges.is_empty())
+            .map(|(n, _)| n)
+            .collect()
+    }
+
+    /// Indexes of the symbols touching exactly `count` numbers.
+    pub fn symbols_with(&self, count: usize) -> Vec<usize> {
+        (0..self.symbols.len())
+            .filter(|s| self.symbol_edges[*s].len() == count)
+            .collect()
+    }
+
+    /// For every kind of symbol, the sum of the products of the numbers around
+    /// each symbol of that kind touching exactly `count` numbers. Gear ratios
+    /// are `ratio_sums(2)[&'*']`.
+    pub fn ratio_sums(&self, count: usize) -> BTreeMap<char, u64> {
+        let mut sums = BTreeMap::new();
+
+        for s in self.symbols_with(count) {
+            let product: u64 = self
+                .numbers_around(s)
+                .iter()
+                .map(|n| n.value as u64)
+                .product();
+
+            *sums.entry(self.symbols[s].c).or_insert(0) += product;
+        }
+
+        sums
+    }
+
+    pub fn edges(&self) -> impl Iterator<Item = (usize, usize)> + '_ {
+        self.symbol_edges
+            .iter()
+            .enumerate()
+            .flat_map(|(s, ns)| ns.iter().map(move |n| (s, *n)))
+    }
+
+    pub fn to_dot(&self) -> String {
+        let mut s = String::from("graph schematic {\n");
+
+        for (i, n) in self.numbers.iter().enumerate() {
+            let _ = writeln!(s, "    n{} [label=\"{}\" shape=box];", i, n.value);
+        }
+
+        for (i, sym) in self.symbols.iter().enumerate() {
+            let _ = writeln!(s, "    s{} [label=\"{}\"];", i, escape(&sym.c.to_string()));
+        }
+
+        for (sym, n) in self.edges() {
+            let _ = writeln!(s, "    s{} -- n{};", sym, n);
+        }
+
+        s.push_str("}\n");
+        s
+    }
+
+    pub fn to_json(&self) -> String {
+        let numbers: Vec<_> = self
+            .numbers
+            .iter()
+            .map(|n| {
+                format!(
+                    "{{\"value\":{},\"row\":{},\"start\":{},\"end\":{}}}",
+                    n.value,
+                    n.row,
+                    n.span.start(),
+                    n.span.end()
+                )
+            })
+            .collect();
+
+        let symbols: Vec<_> = self
+            .symbols
+            .iter()
+            .map(|s| {
+                format!(
+                    "{{\"symbol\":\"{}\",\"x\":{},\"y\":{}}}",
+                    escape(&s.c.to_string()),
+                    s.position.x,
+                    s.position.y
+                )
+            })
+            .collect();
+
+        let edges: Vec<_> = self
+            .edges()
+            .map(|(s, n)| format!("{{\"symbol\":{},\"number\":{}}}", s, n))
+            .collect();
+
+        format!(
+            "{{\"numbers\":[{}],\"symbols\":[{}],\"edges\":[{}]}}",
+            numbers.join(","),
+            symbols.join(","),
+            edges.join(",")
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn schematic(s: &str) -> Schematic {
+        let grid: Grid = s.parse().unwrap();
+        Schematic::from(&grid)
+    }
+
+    fn values(numbers: Vec<&Number>) -> Vec<u32> {
+        numbers.iter().map(|n| n.value).collect()
+    }
+
+    const SAMPLE: &str =
+r"467..114..
+...*......
+..35..633.
+......#...
+617*......
+.....+.58.
+..592.....
+......755.
+...$.*....
+.664.598..";
+
+    #[test]
+    fn schematic_finds_numbers_around_simple() {
+        let schematic = schematic(
+r"1.2
+3$4
+5.6");
+
+        assert_eq!(vec![1, 2, 3, 4, 5, 6], values(schematic.numbers_around(0)));
+    }
+
+    #[test]
+    fn schematic_finds_numbers_around_complex() {
+        let schematic = schematic(
+r"11.
+3$4
+556");
+
+        assert_eq!(vec![11, 3, 4, 556], values(schematic.numbers_around(0)));
+        assert_eq!(0..=2, schematic.numbers[3].span);
+    }
+
+    #[test]
+    fn schematic_counts_shared_numbers_once() {
+        let schematic = schematic("*12*");
+
+        assert_eq!(vec![12], values(schematic.part_numbers()));
+        assert_eq!(2, schematic.symbols_around(0).len());
+    }
+
+    #[test]
+    fn schematic_answers_sample() {
+        let schematic = schematic(SAMPLE);
+
+        let sum: u32 = schematic.part_numbers().iter().map(|n| n.value).sum();
+        assert_eq!(4361, sum);
+
+        assert_eq!(Some(&467835), schematic.ratio_sums(2).get(&'*'));
+        assert_eq!(vec![0, 5], schematic.symbols_with(2));
+    }
+
+    #[test]
+    fn schematic_exports_dot() {
+        let schematic = schematic("1\"");
+        let exp = "graph schematic {
+    n0 [label=\"1\" shape=box];
+    s0 [label=\"\\\"\"];
+    s0 -- n0;
+}
+";
+
+        assert_eq!(exp, schematic.to_dot());
+    }
+
+    #[test]
+    fn schematic_exports_json() {
+        let schematic = schematic("12#");
+        let exp = r##"{"numbers":[{"value":12,"row":0,"start":0,"end":1}],"symbols":[{"symbol":"#","x":2,"y":0}],"edges":[{"symbol":0,"number":0}]}"##;
+
+        assert_eq!(exp, schematic.to_json());
+    }
+}
@@ -1,23 +1,26 @@
-use std::str::FromStr;
+use std::{fmt::Display, str::FromStr};
+
+use common::parse::{numbers, number, parse, ParseError, Res};
+use nom::{
+    bytes::complete::tag,
+    character::complete::{char, space0, space1},
+    combinator::{map, opt},
+    error::context,
+    sequence::{delimited, pair, tuple},
+};
+
+use crate::cascade::Points;
 
 #[derive(Debug, PartialEq)]
 pub struct Card {
+    pub id: u32,
     winning: Vec<u32>,
     mine: Vec<u32>,
 }
 
 impl Card {
-    pub fn points(&self) -> u32 {
-        let count = self.mine
-            .iter()
-            .filter(|m| self.winning.contains(m))
-            .count();
-
-        if count == 0 {
-            return 0;
-        }
-
-        2u32.pow(count as u32 - 1)
+    pub fn points(&self) -> Result<u32, String> {
+        Points::Doubling.score(self.wins())
     }
 
     pub fn wins(&self) -> usize {
@@ -29,30 +32,40 @@ impl Card {
 }
 
 impl FromStr for Card {
-    type Err = String;
+    type Err = ParseError;
 
     fn from_str(s: &str) -> Result<Self, Self::Err> {
-        let (_, rest) = s.split_once(':').expect("Should have :");
-        let (winning, mine) = rest.split_once('|').expect("Should be separated by |");
-
-        Ok(Card { 
-            winning: parse_number_list(winning),
-            mine: parse_number_list(mine)
-        })
+        parse(s, parse_card)
     }
 }
 
-fn parse_number_list(s: &str) -> Vec<u32> {
-    s
-        .split(' ')
-        .filter_map(|w| {
-            if w.is_empty() {
-                return None;
-            }
-
-            w.parse::<u32>().ok()
-        })
-        .collect::<Vec<u32>>()
+/// `Card 1: 41 48 | 83 86  6`, either side of the `|` possibly empty.
+pub(crate) fn parse_card(input: &str) -> Res<'_, Card> {
+    let id = delimited(pair(tag("Card"), space1), number, char(':'));
+    let list = || map(opt(numbers), Option::unwrap_or_default);
+
+    context("a card", map(
+        tuple((id, space0, list(), space0, char('|'), space0, list())),
+        |(id, _, winning, _, _, _, mine)| Card { id, winning, mine },
+    ))(input)
+}
+
+impl Display for Card {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "Card {}:", self.id)?;
+
+        for n in self.winning.iter() {
+            write!(f, " {:>2}", n)?;
+        }
+
+        write!(f, " |")?;
+
+        for n in self.mine.iter() {
+            write!(f, " {:>2}", n)?;
+        }
+
+        Ok(())
+    }
 }
 
 #[cfg(test)]
@@ -63,6 +76,7 @@ mod tests {
     fn card_parses_string() {
         let s = "Card 1: 41 48 83 86 17 | 83 86  6 31 17  9 48 53";
         let exp = Card {
+            id: 1,
             winning: vec![41, 48, 83, 86, 17],
             mine: vec![83, 86, 6, 31, 17, 9, 48, 53],
         };
@@ -71,4 +85,23 @@ mod tests {
 
         assert_eq!(exp, card);
     }
+
+    #[test]
+    fn card_rejects_garbage() {
+        assert!("Card 1 41 48 | 83 86".parse::<Card>().is_err());
+        assert!("Card 1: 41 48 83 86".parse::<Card>().is_err());
+        assert!("Card: 41 48 | 83 86".parse::<Card>().is_err());
+        assert_eq!(
+            Err("line 1, column 15: expected a number in a card".to_string()),
+            "Card 1: 41 48 x | 83 86".parse::<Card>().map_err(String::from)
+        );
+    }
+
+    #[test]
+    fn card_displays_as_parsed() {
+        let s = "Card 1: 41 48 83 86 17 | 83 86  6 31 17  9 48 53";
+
+        assert_eq!(s, s.parse::<Card>().unwrap().to_string());
+        assert_eq!("Card 2: |", "Card 2: |".parse::<Card>().unwrap().to_string());
+    }
 }
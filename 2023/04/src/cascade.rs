@@ -0,0 +1,264 @@
+use crate::card::Card;
+
+/// How a card's matches turn into points.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Points {
+    /// 1 for the first match, doubled for every match after it
+    Doubling,
+    /// 1 for every match
+    PerMatch,
+}
+
+impl Points {
+    /// Errors when the points are too many for a u32, which doubling gets to
+    /// at 33 matches.
+    pub fn score(&self, wins: usize) -> Result<u32, String> {
+        let points = match self {
+            Points::Doubling if wins == 0 => Some(0),
+            Points::Doubling => u32::try_from(wins - 1)
+                .ok()
+                .and_then(|w| 2u32.checked_pow(w)),
+            Points::PerMatch => u32::try_from(wins).ok(),
+        };
+
+        points.ok_or(format!("{} matches are worth more points than fit in a u32", wins))
+    }
+}
+
+/// What to do when a card wins copies of cards past the end of the table.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Overflow {
+    /// Win whatever cards exist and note the rest in `Cascade::overflows`
+    Clamp,
+    /// Stop with an error
+    Error,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Rules {
+    pub points: Points,
+    /// cards skipped after the winning card before copies start
+    pub offset: usize,
+    /// cards copied per match
+    pub width: usize,
+    pub overflow: Overflow,
+}
+
+impl Default for Rules {
+    /// The puzzle's rules: doubling points, and each match copies one of the
+    /// cards right after the winner.
+    fn default() -> Self {
+        Self {
+            points: Points::Doubling,
+            offset: 0,
+            width: 1,
+            overflow: Overflow::Clamp,
+        }
+    }
+}
+
+impl Rules {
+    /// Indexes of the cards copied when card `i` has `wins` matches, which may
+    /// run past the end of the table. Errors when they run past `usize::MAX`.
+    pub fn window(&self, i: usize, wins: usize) -> Result<std::ops::Range<usize>, String> {
+        let start = i.checked_add(1).and_then(|s| s.checked_add(self.offset));
+        let end = start
+            .zip(wins.checked_mul(self.width))
+            .and_then(|(s, w)| s.checked_add(w));
+
+        match (start, end) {
+            (Some(start), Some(end)) => Ok(start..end),
+            _ => Err(format!("Card {} wins cards numbered past {}", i + 1, usize::MAX)),
+        }
+    }
+}
+
+/// Cards are numbered from 1, like in the input.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Event {
+    Wins { card: usize, wins: usize },
+    Copies { from: usize, to: usize, copies: usize },
+    Overflow { card: usize, missing: usize },
+}
+
+#[derive(Debug, PartialEq, Eq)]
+pub struct Cascade {
+    /// how many of each card you end up with, originals included
+    pub instances: Vec<usize>,
+    /// cards (numbered from 1) whose copies ran past the end, and by how many
+    pub overflows: Vec<(usize, usize)>,
+}
+
+impl Cascade {
+    pub fn total(&self) -> Result<usize, String> {
+        self.instances
+            .iter()
+            .try_fold(0usize, |total, n| total.checked_add(*n))
+            .ok_or(format!("More than {} cards in total", usize::MAX))
+    }
+}
+
+/// Plays out the copies. Pass a trace to get every step as an `Event`.
+pub fn simulate(
+    cards: &[Card],
+    rules: &Rules,
+    mut trace: Option<&mut Vec<Event>>,
+) -> Result<Cascade, String> {
+    let mut instances = vec![1usize; cards.len()];
+    let mut overflows = vec![];
+
+    for (i, c) in cards.iter().enumerate() {
+        let wins = c.wins();
+        tracing::trace!(card = i + 1, wins, "wins");
+        if let Some(trace) = trace.as_mut() {
+            trace.push(Event::Wins { card: i + 1, wins });
+        }
+
+        let window = rules.window(i, wins)?;
+        if window.end > cards.len() {
+            let missing = window.end - window.start.max(cards.len());
+
+            if rules.overflow == Overflow::Error {
+                return Err(format!(
+                    "Card {} wins {} cards past the end of the table",
+                    i + 1,
+                    missing
+                ));
+            }
+
+            overflows.push((i + 1, missing));
+            tracing::debug!(card = i + 1, missing, "overflow");
+            if let Some(trace) = trace.as_mut() {
+                trace.push(Event::Overflow { card: i + 1, missing });
+            }
+        }
+
+        for copy_i in window.start.min(cards.len())..window.end.min(cards.len()) {
+            instances[copy_i] = instances[copy_i]
+                .checked_add(instances[i])
+                .ok_or(format!(
+                    "Card {} ends up with more than {} copies",
+                    copy_i + 1,
+                    usize::MAX
+                ))?;
+            tracing::trace!(from = i + 1, to = copy_i + 1, copies = instances[i], "copies");
+
+            if let Some(trace) = trace.as_mut() {
+                trace.push(Event::Copies { from: i + 1, to: copy_i + 1, copies: instances[i] });
+            }
+        }
+    }
+
+    Ok(Cascade { instances, overflows })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const SAMPLE: &str =
+r"Card 1: 41 48 83 86 17 | 83 86  6 31 17  9 48 53
+Card 2: 13 32 20 16 61 | 61 30 68 82 17 32 24 19
+Card 3:  1 21 53 59 44 | 69 82 63 72 16 21 14  1
+Card 4: 41 92 73 84 69 | 59 84 76 51 58  5 54 83
+Card 5: 87 83 26 28 32 | 88 30 70 12 93 22 82 36
+Card 6: 31 18 13 56 72 | 74 77 10 23 35 67 36 11";
+
+    fn cards(s: &str) -> Vec<Card> {
+        s.lines().map(|l| l.parse().unwrap()).collect()
+    }
+
+    #[test]
+    fn points_score() {
+        assert_eq!(Ok(0), Points::Doubling.score(0));
+        assert_eq!(Ok(8), Points::Doubling.score(4));
+        assert_eq!(Ok(4), Points::PerMatch.score(4));
+
+        assert_eq!(Ok(1 << 31), Points::Doubling.score(32));
+        assert!(Points::Doubling.score(33).is_err());
+    }
+
+    #[test]
+    fn cascade_counts_sample() {
+        let cascade = simulate(&cards(SAMPLE), &Rules::default(), None).unwrap();
+
+        assert_eq!(vec![1, 2, 4, 8, 14, 1], cascade.instances);
+        assert_eq!(Ok(30), cascade.total());
+        assert!(cascade.overflows.is_empty());
+    }
+
+    #[test]
+    fn cascade_follows_window() {
+        let rules = Rules { offset: 1, ..Rules::default() };
+
+        let cascade = simulate(&cards(SAMPLE), &rules, None).unwrap();
+
+        // card 1 copies 3 to 6, card 2 copies 4 and 5, card 3 copies 5 and 6, card 4 copies 6
+        assert_eq!(vec![1, 1, 2, 3, 5, 7], cascade.instances);
+    }
+
+    #[test]
+    fn cascade_clamps_overflow() {
+        let cards = cards("Card 1: 1 2 | 1 2\nCard 2: 3 | 3");
+
+        let cascade = simulate(&cards, &Rules::default(), None).unwrap();
+
+        assert_eq!(vec![1, 2], cascade.instances);
+        assert_eq!(vec![(1, 1), (2, 1)], cascade.overflows);
+    }
+
+    #[test]
+    fn cascade_reports_overflow() {
+        let cards = cards("Card 1: 1 2 | 1 2\nCard 2: 3 | 3");
+        let rules = Rules { overflow: Overflow::Error, ..Rules::default() };
+
+        let err = simulate(&cards, &rules, None).unwrap_err();
+
+        assert_eq!("Card 1 wins 1 cards past the end of the table", err);
+    }
+
+    #[test]
+    fn cascade_reports_too_many_cards() {
+        let cards = cards("Card 1: 1 | 1\nCard 2: 2 | 2");
+
+        let rules = Rules { offset: usize::MAX, ..Rules::default() };
+        assert!(simulate(&cards, &rules, None).is_err());
+
+        let rules = Rules { width: usize::MAX, ..Rules::default() };
+        assert!(simulate(&cards, &rules, None).is_err());
+
+        let cascade = Cascade { instances: vec![usize::MAX, 1], overflows: vec![] };
+        assert!(cascade.total().is_err());
+    }
+
+    #[test]
+    fn cascade_counts_too_many_copies() {
+        // every card copies every card after it, so the last has 2^(n-2) copies
+        let n = usize::BITS as usize + 2;
+        let cards: Vec<Card> = (0..n)
+            .map(|i| format!("Card {}: {} | {}", i + 1, 1, 1).parse().unwrap())
+            .collect();
+        let rules = Rules { width: n, ..Rules::default() };
+
+        let err = simulate(&cards, &rules, None).unwrap_err();
+
+        assert!(err.contains("more than"), "{}", err);
+    }
+
+    #[test]
+    fn cascade_traces() {
+        let cards = cards("Card 1: 1 | 1\nCard 2: 2 | 3");
+        let mut trace = vec![];
+
+        simulate(&cards, &Rules::default(), Some(&mut trace)).unwrap();
+
+        assert_eq!(
+            vec![
+                Event::Wins { card: 1, wins: 1 },
+                Event::Copies { from: 1, to: 2, copies: 1 },
+                Event::Wins { card: 2, wins: 0 },
+            ],
+            trace
+        );
+    }
+}
@@ -1,40 +1,73 @@
-use std::collections::HashMap;
+use std::error::Error;
 
-use card::Card;
+use common::{
+    parse::{self, lines, ParseError},
+    Answer,
+};
+
+pub use crate::{
+    card::Card,
+    cascade::{simulate, Cascade, Event, Overflow, Points, Rules},
+};
+
+use card::parse_card;
 
 mod card;
+mod cascade;
+
+/// The points the cards are worth.
+#[tracing::instrument(skip_all)]
+pub fn part1(contents: &str) -> Result<Answer, Box<dyn Error>> {
+    let rules = Rules::default();
+
+    let mut sum: u32 = 0;
+    for c in parse(contents)? {
+        sum = sum
+            .checked_add(rules.points.score(c.wins())?)
+            .ok_or("the points add up to more than fit in a u32")?;
+    }
+
+    Ok(sum.into())
+}
+
+/// How many cards there are once every copy has been won.
+#[tracing::instrument(skip_all)]
+pub fn part2(contents: &str) -> Result<Answer, Box<dyn Error>> {
+    let cascade = simulate(&parse(contents)?, &Rules::default(), None)?;
+
+    Ok(cascade.total()?.into())
+}
+
+pub fn parse(contents: &str) -> Result<Vec<Card>, ParseError> {
+    parse::parse(contents, lines(parse_card))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const SAMPLE: &str = include_str!("../sample_1.txt");
 
-pub fn run(contents: &str) {
-    let cards: Vec<_> = contents
-        .lines()
-        .filter_map(|l| l.parse::<Card>().ok())
-        .collect();
-
-    let sum: u32 = cards
-        .iter()
-        .map(|c| c.points())
-        .sum();
-
-    let mut win_cache: HashMap<usize, usize> = HashMap::new();
-    let mut instances = vec![1; cards.len()];
-
-    for (i, c) in cards.iter().enumerate() {
-        let wins = match win_cache.get(&i) {
-            Some(w) => *w,
-            None => c.wins(),
-        };
-        
-        let _ = win_cache.insert(i, wins);
-
-        println!("Card {} wins {}", i + 1, wins);
-        for copy_i in (i + 1)..=(i + wins) {
-            instances[copy_i] += instances[i];
-            println!("updating card {} to have {} copies", i + 1, instances[i]);
-        }
+    #[test]
+    fn solves_sample() {
+        assert_eq!(Answer::Int(13), part1(SAMPLE).unwrap());
+        assert_eq!(Answer::Int(30), part2(SAMPLE).unwrap());
     }
 
-    let card_count: usize = instances.iter().sum();
+    #[test]
+    fn parse_reports_bad_cards() {
+        let error = parse("Card 1: 1 | 2\nCard 2 3 | 4").unwrap_err();
 
-    println!("solution 1 (sum): {}", sum);
-    println!("solution 2 (card count): {}", card_count);
+        assert_eq!((2, 7), (error.line, error.column));
+        assert_eq!("expected ':' in a card", error.message);
+    }
+
+    #[test]
+    fn part1_reports_too_many_points() {
+        let numbers: Vec<_> = (1..=33).map(|n| n.to_string()).collect();
+        let numbers = numbers.join(" ");
+        let card = format!("Card 1: {} | {}", numbers, numbers);
+
+        assert!(part1(&card).is_err());
+    }
 }
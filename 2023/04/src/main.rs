@@ -1,9 +1,20 @@
-use std::fs;
+use std::{error::Error, fs};
+
+fn main() -> Result<(), Box<dyn Error>> {
+    let _day = common::trace::init("scratch");
 
-fn main() {
     let args: Vec<_> = std::env::args().collect();
     let filename = args.get(1).expect("Should provide input filename");
     let contents = fs::read_to_string(filename).expect("File should exist");
 
-    scratch::run(&contents);
+    if common::answer::for_tools() {
+        common::answer::print(1, &scratch::part1(&contents)?);
+        common::answer::print(2, &scratch::part2(&contents)?);
+        return Ok(());
+    }
+
+    println!("solution 1 (sum): {}", scratch::part1(&contents)?);
+    println!("solution 2 (card count): {}", scratch::part2(&contents)?);
+
+    Ok(())
 }
@@ -1,94 +1,174 @@
-use std::{ops::Range, sync::Mutex};
+use std::{error::Error, ops::Range};
 
-use linya::{Progress, Bar};
-use map::Map;
-use rayon::iter::{IntoParallelIterator, ParallelIterator};
+use common::{
+    parse::{blank_line, labelled, parse, sections, ParseError},
+    Answer,
+};
+use nom::{
+    combinator::{cut, opt},
+    sequence::{pair, preceded},
+};
+
+pub use map::Map;
+use map::parse_map;
 
 mod map;
 
-pub fn run(contents: &str) {
-    let seed_line = contents.lines().next().expect("seed line");
-    let seeds = seeds(seed_line);
-
-    let map_strs = contents
-        .split("\n\n")
-        .skip(1);
-
-    let maps: Vec<Map> = map_strs.filter_map(|s| s.parse().ok()).collect();
-
-    let min = seeds
-        .into_iter()
-        .map(|s| maps.iter().fold(s, |s, m| m.map(&s)))
-        .min().expect("Should be a min");
-
-    println!("solution 1 (lowest location): {}", min);
-
-    let seeds = seed_ranges(seed_line);
-    
-    let progress = Mutex::new(Progress::new());
-    let min = seeds
-        .into_par_iter()
-        .map(|r| {
-            let total = r.end as usize - r.start as usize;
-            let bar: Bar = progress.lock().unwrap().bar(total, format!("Processing seeds {} to {}", r.start, r.end));
-            r.enumerate().map(|(i, s)| {
-                let l = maps.iter().fold(s, |s, m| m.map(&s));
-                if i % 1000 == 0 {
-                    progress.lock().unwrap().inc_and_draw(&bar, 1000);
-                }
-                l
-            }).min().unwrap()
-        })
-        .min().expect("should be a min");
+/// The lowest location of any seed.
+#[tracing::instrument(skip_all)]
+pub fn part1(contents: &str) -> Result<Answer, Box<dyn Error>> {
+    let (seeds, maps) = almanac(contents)?;
+
+    lowest_location(&seeds, &maps)
+        .map(Answer::from)
+        .ok_or("Expected seeds".into())
+}
+
+/// The lowest location of any seed, reading the seeds as ranges.
+#[tracing::instrument(skip_all)]
+pub fn part2(contents: &str) -> Result<Answer, Box<dyn Error>> {
+    let (seeds, maps) = almanac(contents)?;
+
+    lowest_location_of_ranges(&seed_ranges(&seeds)?, &maps)
+        .map(Answer::from)
+        .ok_or("Expected seed ranges".into())
+}
 
-    println!("solution 2 (lowest location): {}", min);
+/// The seeds, then every map in order.
+fn almanac(contents: &str) -> Result<(Vec<u64>, Vec<Map>), ParseError> {
+    let maps = opt(preceded(blank_line, cut(sections(parse_map))));
+
+    parse(contents, pair(labelled("seeds"), maps))
+        .map(|(seeds, maps)| (seeds, maps.unwrap_or_default()))
 }
 
-fn seeds(seed_line: &str) -> Vec<u64> {
-    let (_, seeds) = seed_line.split_once(':').expect("Seed: should have a ':'");
+fn lowest_location(seeds: &[u64], maps: &[Map]) -> Option<u64> {
     seeds
-        .split_whitespace()
-        .filter_map(|s| s.parse().ok())
-        .collect()
+        .iter()
+        .map(|s| maps.iter().fold(*s, |s, m| m.map(&s)))
+        .min()
+}
+
+fn lowest_location_of_ranges(seeds: &[Range<u64>], maps: &[Map]) -> Option<u64> {
+    maps.iter()
+        .fold(seeds.to_vec(), |ranges, m| m.map_ranges(&ranges))
+        .iter()
+        .filter(|r| !r.is_empty())
+        .map(|r| r.start)
+        .min()
 }
 
-fn seed_ranges(s: &str) -> Vec<Range<u64>> {
-    let (_, seeds) = s.split_once(':').expect("Seed: should have a ':'");
-    let seeds: Vec<_> = seeds
-        .split_whitespace()
-        .map(|s| s.parse().expect("Should parse to number"))
-        .collect();
-        
+fn seed_ranges(seeds: &[u64]) -> Result<Vec<Range<u64>>, String> {
+    if !seeds.len().is_multiple_of(2) {
+        return Err("Seeds should come in pairs of start and length".into());
+    }
+
     seeds
         .chunks_exact(2)
-        .map(|c| c[0]..(c[0] + c[1]))
-        .collect()
+        .map(|c| c[0].checked_add(c[1]).map(|end| c[0]..end))
+        .collect::<Option<_>>()
+        .ok_or("Seed ranges should fit in a u64".into())
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use proptest::prelude::*;
+
+    const SAMPLE: &str = include_str!("../sample_1.txt");
 
     #[test]
-    fn seeds_parses() {
+    fn almanac_parses() {
         let s = "seeds: 1 2 3 999 20";
         let exp = vec![1, 2, 3, 999, 20];
 
-        let seeds = seeds(s);
+        let (seeds, maps) = almanac(s).unwrap();
 
         assert_eq!(exp, seeds);
+        assert!(maps.is_empty());
+    }
+
+    #[test]
+    fn almanac_errors_say_where() {
+        let s = "seeds: 1 2\n\na-to-b map:\n1 2 3\n4 x 6\n\nb-to-c map:\n1 2 3";
+        let error = almanac(s).unwrap_err();
+
+        assert_eq!((5, 3), (error.line, error.column));
+        assert_eq!("expected a number in a mapping", error.message);
+
+        assert!(almanac("seeds 1 2").is_err());
+        assert!(almanac("seeds: 1 2\n\n\n1 2 3").is_err());
     }
 
     #[test]
     fn seed_ranges_parses() {
-        let s = "seeds: 0 10 10 100";
+        let s = [0, 10, 10, 100];
         let exp = vec![
             0..10,
             10..110
         ];
 
-        let seeds = seed_ranges(s);
+        let seeds = seed_ranges(&s).unwrap();
 
         assert_eq!(exp, seeds);
+        assert!(seed_ranges(&[0, 10, 10]).is_err());
+        assert!(seed_ranges(&[u64::MAX, 1]).is_err());
+    }
+
+    #[test]
+    fn almanac_finds_lowest_locations() {
+        let (seeds, maps) = almanac(SAMPLE).unwrap();
+
+        assert_eq!(Some(35), lowest_location(&seeds, &maps));
+        assert_eq!(Some(46), lowest_location_of_ranges(&seed_ranges(&seeds).unwrap(), &maps));
+        assert_eq!(Answer::Int(35), part1(SAMPLE).unwrap());
+        assert_eq!(Answer::Int(46), part2(SAMPLE).unwrap());
+    }
+
+    // a seed line of small ranges, then a few maps of `dest src length` lines
+    fn almanacs() -> impl Strategy<Value = String> {
+        let seeds = prop::collection::vec((0..100u64, 0..20u64), 1..4);
+        let mapping = (0..120u64, 0..120u64, 1..30u64);
+        let maps = prop::collection::vec(prop::collection::vec(mapping, 0..5), 1..4);
+
+        (seeds, maps).prop_map(|(seeds, maps)| {
+            let seeds: Vec<_> = seeds.iter().map(|(s, l)| format!("{} {}", s, l)).collect();
+            let mut almanac = format!("seeds: {}", seeds.join(" "));
+
+            for (i, map) in maps.iter().enumerate() {
+                almanac.push_str(&format!("\n\nmap-{}-to-{} map:", i, i + 1));
+                for (dest, src, length) in map {
+                    almanac.push_str(&format!("\n{} {} {}", dest, src, length));
+                }
+            }
+
+            almanac
+        })
+    }
+
+    proptest! {
+        #[test]
+        fn range_mapping_equals_seed_mapping(input in almanacs()) {
+            let (seeds, maps) = almanac(&input).unwrap();
+            let ranges = seed_ranges(&seeds).unwrap();
+
+            let mut per_seed: Vec<_> = ranges
+                .iter()
+                .flat_map(|r| r.clone())
+                .map(|s| maps.iter().fold(s, |s, m| m.map(&s)))
+                .collect();
+            per_seed.sort();
+
+            let mut by_range: Vec<_> = maps
+                .iter()
+                .fold(ranges.clone(), |rs, m| m.map_ranges(&rs))
+                .into_iter()
+                .flatten()
+                .collect();
+            by_range.sort();
+
+            prop_assert_eq!(per_seed.first().copied(), lowest_location_of_ranges(&ranges, &maps));
+            prop_assert_eq!(per_seed, by_range);
+        }
     }
 }
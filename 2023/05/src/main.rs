@@ -1,9 +1,20 @@
-use std::{env, fs};
+use std::{env, error::Error, fs};
+
+fn main() -> Result<(), Box<dyn Error>> {
+    let _day = common::trace::init("seed");
 
-fn main() {
     let args: Vec<_> = env::args().collect();
     let filename = args.get(1).expect("Filename. Now.");
     let contents = fs::read_to_string(filename).expect("Contents. Now.");
 
-    seed::run(&contents);
+    if common::answer::for_tools() {
+        common::answer::print(1, &seed::part1(&contents)?);
+        common::answer::print(2, &seed::part2(&contents)?);
+        return Ok(());
+    }
+
+    println!("solution 1 (lowest location): {}", seed::part1(&contents)?);
+    println!("solution 2 (lowest location): {}", seed::part2(&contents)?);
+
+    Ok(())
 }
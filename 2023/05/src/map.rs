@@ -1,4 +1,14 @@
-use std::{str::FromStr, ops::Range};
+use std::{fmt::Display, str::FromStr, ops::Range};
+
+use common::parse::{lines, number, parse, ParseError, Res};
+use nom::{
+    branch::alt,
+    bytes::complete::take_till,
+    character::complete::{char, line_ending, space0, space1},
+    combinator::{cut, eof, map, map_opt, not, opt, verify},
+    error::context,
+    sequence::{pair, preceded, tuple},
+};
 
 #[derive(Debug, PartialEq)]
 pub struct Map {
@@ -16,40 +26,90 @@ impl Map {
 
         *seed
     }
+
+    /// Maps whole ranges at once, splitting them wherever they cross the edge
+    /// of a mapping. Like `map`, the first mapping containing a seed wins.
+    pub fn map_ranges(&self, seeds: &[Range<u64>]) -> Vec<Range<u64>> {
+        let mut pending = seeds.to_vec();
+        let mut mapped = vec![];
+
+        for (src, dest) in self.ranges.iter() {
+            let mut unmapped = vec![];
+
+            for r in pending {
+                let start = r.start.max(src.start);
+                let end = r.end.min(src.end);
+
+                if start >= end {
+                    unmapped.push(r);
+                    continue;
+                }
+
+                mapped.push((start - src.start + dest.start)..(end - src.start + dest.start));
+
+                if r.start < start {
+                    unmapped.push(r.start..start);
+                }
+                if end < r.end {
+                    unmapped.push(end..r.end);
+                }
+            }
+
+            pending = unmapped;
+        }
+
+        mapped.extend(pending);
+        mapped
+    }
+}
+
+/// A titled block of `dest src length` lines, like those in an almanac.
+pub(crate) fn parse_map(input: &str) -> Res<'_, Map> {
+    let title = context(
+        "a title",
+        verify(take_till(|c| c == '\n'), |t: &str| !t.trim().is_empty()),
+    );
+
+    // a title on its own is a map that changes nothing
+    let mappings = preceded(
+        tuple((char('\n'), not(pair(space0, alt((line_ending, eof)))))),
+        cut(lines(mapping)),
+    );
+
+    map(pair(title, opt(mappings)), |(title, ranges)| Map {
+        title: title.trim_end().to_owned(),
+        ranges: ranges.unwrap_or_default(),
+    })(input)
 }
 
-fn parse_mapping(s: &str) -> Result<(Range<u64>, Range<u64>), String> {
-    let parts: Vec<u64> = s
-        .splitn(3, ' ')
-        .filter_map(|s| s.parse().ok())
-        .collect();
+fn mapping(input: &str) -> Res<'_, (Range<u64>, Range<u64>)> {
+    let numbers = tuple((number::<u64>, space1, number::<u64>, space1, number::<u64>));
 
-    let dest = parts.first().ok_or("expected dest")?;
-    let src = parts.get(1).ok_or("expected src")?;
-    let length = parts.get(2).ok_or("expected length")?;
+    context("a mapping", map_opt(numbers, |(dest, _, src, _, length)| {
+        let src_end = src.checked_add(length)?;
+        let dest_end = dest.checked_add(length)?;
 
+        Some((src..src_end, dest..dest_end))
+    }))(input)
+}
+
+impl Display for Map {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.title)?;
+
+        for (src, dest) in self.ranges.iter() {
+            write!(f, "\n{} {} {}", dest.start, src.start, src.end - src.start)?;
+        }
 
-    Ok((
-        *src..(src + length),
-        *dest..(dest + length),
-    ))
+        Ok(())
+    }
 }
 
 impl FromStr for Map {
-    type Err = String;
+    type Err = ParseError;
 
     fn from_str(s: &str) -> Result<Self, Self::Err> {
-        let mut lines = s.lines();
-        let title = lines.next().ok_or("Should have a title line")?.to_owned();
-
-        let maps = lines
-            .filter_map(|l| parse_mapping(l).ok())
-            .collect();
-        
-        Ok(Map { 
-            title,
-            ranges: maps,
-        })
+        parse(s, parse_map)
     }
 }
 
@@ -69,9 +129,9 @@ r"test to test map:
         let exp = Map {
             title: "test to test map:".into(),
             ranges: vec![
-                parse_mapping("1 2 3").unwrap(),
-                parse_mapping("4 5 6").unwrap(),
-                parse_mapping("10 11 1").unwrap(),
+                parse("1 2 3", mapping).unwrap(),
+                parse("4 5 6", mapping).unwrap(),
+                parse("10 11 1", mapping).unwrap(),
             ]
         };
 
@@ -91,4 +151,29 @@ r"test to test map:
         assert_eq!(0, map.map(&0));
         assert_eq!(6, map.map(&7));
     }
+
+    #[test]
+    fn map_displays_as_parsed() {
+        let s = "test to test map:\n1 2 3\n10 11 1";
+        let map: Map = s.parse().unwrap();
+
+        assert_eq!(s, map.to_string());
+        assert!(parse("1 18446744073709551615 1", mapping).is_err());
+        assert!("\n1 2 3".parse::<Map>().is_err());
+        assert_eq!("map:", "map:\r\r\n1 2 3".parse::<Map>().unwrap().title);
+    }
+
+    #[test]
+    fn map_maps_ranges() {
+        let map: Map = 
+r"test to test map:
+1 2 3
+4 5 6
+10 11 1".parse().unwrap();
+
+        let mut ranges = map.map_ranges(&[0..8, 8..13]);
+        ranges.sort_by_key(|r| r.start);
+
+        assert_eq!(vec![0..2, 1..4, 4..7, 7..10, 10..11, 12..13], ranges);
+    }
 }
@@ -1,5 +1,25 @@
-use std::mem::Discriminant;
+use std::{error::Error, str::FromStr};
 
+use common::{
+    math::isqrt,
+    parse::{self, labelled, ParseError},
+    Answer,
+};
+use nom::{character::complete::line_ending, sequence::separated_pair};
+
+/// The product of the ways to win each race.
+#[tracing::instrument(skip_all)]
+pub fn part1(contents: &str) -> Result<Answer, Box<dyn Error>> {
+    let error: usize = parse(contents)?.into_iter().map(|r| r.wins()).product();
+
+    Ok(error.into())
+}
+
+/// The ways to win the one long race.
+#[tracing::instrument(skip_all)]
+pub fn part2(contents: &str) -> Result<Answer, Box<dyn Error>> {
+    Ok(parse_as_single(contents)?.wins().into())
+}
 
 #[derive(Debug, PartialEq, Eq)]
 pub struct Race {
@@ -12,52 +32,57 @@ impl Race {
         Self { duration, distance }
     }
 
+    /// Holding for `t` goes `(duration - t) * t`, so the winning holds lie
+    /// between the roots of `t² - duration * t + distance`.
     pub fn wins(&self) -> usize {
-        let first_win = (0..=self.duration)
-            .find(|t| {
-                let traveled = (self.duration - t) * t;
-                traveled > self.distance
-            });
-
-        match first_win {
-            Some(i) => {
-                let end_index = self.duration - i;
-                end_index - i + 1
-            },
-            None => 0,
+        let (duration, distance) = (self.duration as u128, self.distance as u128);
+        let travels = |t: u128| (duration - t) * t;
+
+        // holding for half the race goes furthest
+        if travels(duration / 2) <= distance {
+            return 0;
+        }
+
+        // the square root is rounded, so nudge onto the first win
+        let discriminant = duration * duration - 4 * distance;
+        let mut first_win = (duration - isqrt(discriminant)) / 2;
+        while travels(first_win) <= distance {
+            first_win += 1;
         }
+        while first_win > 0 && travels(first_win - 1) > distance {
+            first_win -= 1;
+        }
+
+        (duration - 2 * first_win + 1) as usize
     }
 }
 
-pub fn parse(s: &str) -> Vec<Race> {
-    let (time, distance) = s.split_once('\n').expect("to be a newline");
-    let time = parse_line(time);
-    let distance = parse_line(distance);
+pub fn parse(s: &str) -> Result<Vec<Race>, Box<dyn Error>> {
+    let (time, distance) = parse_lines::<usize>(s)?;
 
-    time
+    if time.len() != distance.len() {
+        return Err(format!("{} times but {} distances", time.len(), distance.len()).into());
+    }
+
+    Ok(time
         .into_iter()
         .zip(distance)
         .map(|(t, d)| Race::new(t, d))
-        .collect()
+        .collect())
 }
 
-fn parse_line(s: &str) -> Vec<usize> {
-    let (_, num_s) = s.split_once(':').expect("should be ':'");
-    num_s
-        .split_whitespace()
-        .map(|n| n.parse().expect("should parse into number"))
-        .collect()
+fn parse_lines<T: FromStr>(s: &str) -> Result<(Vec<T>, Vec<T>), ParseError> {
+    parse::parse(s, separated_pair(labelled("Time"), line_ending, labelled("Distance")))
 }
 
-pub fn parse_as_single(s: &str) -> Race {
-    let (time, distance) = s.split_once('\n').expect("to be a newline");
-    let (_, time) = time.split_once(':').expect("should be a :");
-    let (_, distance) = distance.split_once(':').expect("should be a :");
+/// Reads the numbers on each line as the digits of one, ignoring the spaces.
+pub fn parse_as_single(s: &str) -> Result<Race, Box<dyn Error>> {
+    let (time, distance) = parse_lines::<String>(s)?;
 
-    let time: usize = time.split_whitespace().collect::<String>().parse().expect("Should parse to one number");
-    let distance: usize = distance.split_whitespace().collect::<String>().parse().expect("Should parse to one number");
+    let time: usize = time.concat().parse().map_err(|_| "Time should fit in a usize")?;
+    let distance: usize = distance.concat().parse().map_err(|_| "Distance should fit in a usize")?;
 
-    Race { duration: time, distance }
+    Ok(Race { duration: time, distance })
 }
 
 #[cfg(test)]
@@ -65,12 +90,14 @@ mod tests {
     use super::*;
 
     #[test]
-    fn parse_line_parses() {
-        let exp = vec![1, 2, 3];
-
-        let result = parse_line("line: 1  2    3");
-
-        assert_eq!(exp, result);
+    fn parse_reports_bad_lines() {
+        assert_eq!(vec![Race::new(1, 2)], parse("Time: 1\nDistance: 2\n").unwrap());
+        assert_eq!(
+            "line 2, column 13: expected a number in Distance",
+            parse("Time: 1 2\nDistance: 2 x").unwrap_err().to_string()
+        );
+        assert_eq!("2 times but 1 distances", parse("Time: 1 2\nDistance: 2").unwrap_err().to_string());
+        assert!(parse_as_single("Time: 1 2\nDistance: 99999999999 99999999999").is_err());
     }
 
     #[test]
@@ -85,7 +112,7 @@ mod tests {
 r"Time:      7  15   30
 Distance:  9  40  200");
 
-        assert_eq!(exp, result);
+        assert_eq!(exp, result.unwrap());
     }
 
     #[test]
@@ -99,6 +126,21 @@ Distance:  9  40  200");
         for (exp, wins) in exps {
             assert_eq!(exp, wins);
         }
+
+        assert_eq!(Answer::Int(288), part1("Time: 7 15 30\nDistance: 9 40 200").unwrap());
+    }
+
+    #[test]
+    fn race_wins_match_trying_every_hold() {
+        for duration in 0..40 {
+            for distance in 0..450 {
+                let tried = (0..=duration)
+                    .filter(|t| (duration - t) * t > distance)
+                    .count();
+
+                assert_eq!(tried, Race::new(duration, distance).wins());
+            }
+        }
     }
 
     #[test]
@@ -111,6 +153,7 @@ Distance:  9  40  200";
 
         let race = parse_as_single(s);
 
-        assert_eq!(exp, race);
+        assert_eq!(exp, race.unwrap());
+        assert_eq!(Answer::Int(71503), part2(s).unwrap());
     }
 }
@@ -1,19 +1,20 @@
-use wait::{parse, parse_as_single};
-use std::{env, fs};
+use std::{env, error::Error, fs};
+
+fn main() -> Result<(), Box<dyn Error>> {
+    let _day = common::trace::init("wait");
 
-fn main() {
     let args: Vec<_> = env::args().collect();
     let filename = args.get(1).expect("Give filename");
     let contents = fs::read_to_string(filename).expect("Not real file");
 
-    let races = crate::parse(&contents);
-
-    let error: usize = races.into_iter().map(|r| r.wins()).product();
-    println!("solution 1 (margin of error): {}", error);
+    if common::answer::for_tools() {
+        common::answer::print(1, &wait::part1(&contents)?);
+        common::answer::print(2, &wait::part2(&contents)?);
+        return Ok(());
+    }
 
-    let race = parse_as_single(&contents);
-    let error = race.wins();
+    println!("solution 1 (margin of error): {}", wait::part1(&contents)?);
+    println!("solution 2 (margin of error): {}", wait::part2(&contents)?);
 
-    println!("solution 2 (margin of error): {}", error);
+    Ok(())
 }
-
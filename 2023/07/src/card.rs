@@ -1,13 +1,66 @@
-pub fn card_value(c: char, joker: bool) -> u32 {
-    match c {
-        'A' => 14,
-        'K' => 13,
-        'Q' => 12,
-        'J' => if joker { 1 } else { 11 },
-        'T' => 10,
-        '0'..='9' => c.to_digit(10).unwrap(),
-        _ => panic!("Not valid Card character")
+use std::fmt::Display;
+
+use crate::rules::Rules;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Card {
+    pub face: char,
+    pub suit: Option<char>,
+    /// position of `face` in the rank order, weakest is 0
+    pub rank: u32,
+}
+
+impl Card {
+    pub fn new(face: char, suit: Option<char>, rules: &Rules) -> Result<Self, String> {
+        let rank = rules.card_value(face)?;
+
+        if let Some(suit) = suit {
+            if !rules.suits.contains(&suit) {
+                return Err(format!("Not valid suit: {}", suit));
+            }
+        }
+
+        Ok(Self { face, suit, rank })
+    }
+}
+
+impl Display for Card {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.face)?;
+
+        match self.suit {
+            Some(suit) => write!(f, "{}", suit),
+            None => Ok(()),
+        }
+    }
+}
+
+/// Reads a hand's cards: one character per card, or a face then a suit when
+/// the rules have suits, e.g. `AhKh`.
+pub fn parse_cards(s: &str, rules: &Rules) -> Result<Vec<Card>, String> {
+    let chars: Vec<_> = s.chars().collect();
+
+    if rules.suits.is_empty() {
+        return chars.into_iter().map(|c| Card::new(c, None, rules)).collect();
+    }
+
+    if chars.len() % 2 != 0 {
+        return Err(format!("Expected a face and a suit for every card: {}", s));
+    }
+
+    let cards: Vec<Card> = chars
+        .chunks_exact(2)
+        .map(|c| Card::new(c[0], Some(c[1]), rules))
+        .collect::<Result<_, _>>()?;
+
+    // there's only one of each card in a suited deck
+    for (i, card) in cards.iter().enumerate() {
+        if cards[..i].contains(card) {
+            return Err(format!("Dealt {} twice", card));
+        }
     }
+
+    Ok(cards)
 }
 
 #[cfg(test)]
@@ -16,12 +69,39 @@ mod tests {
 
     #[test]
     fn card_parses_from_char() {
-        assert_eq!(1, card_value('J', true));
-        assert_eq!(3, card_value('3', false));
-        assert_eq!(10, card_value('T', false));
-        assert_eq!(11, card_value('J', false));
-        assert_eq!(12, card_value('Q', false));
-        assert_eq!(13, card_value('K', false));
-        assert_eq!(14, card_value('A', false));
+        let rules = Rules::camel();
+        let jokers = Rules::camel_jokers();
+
+        assert_eq!(0, jokers.card_value('J').unwrap());
+        assert_eq!(1, rules.card_value('3').unwrap());
+        assert_eq!(8, rules.card_value('T').unwrap());
+        assert_eq!(9, rules.card_value('J').unwrap());
+        assert_eq!(10, rules.card_value('Q').unwrap());
+        assert_eq!(11, rules.card_value('K').unwrap());
+        assert_eq!(12, rules.card_value('A').unwrap());
+        assert!(rules.card_value('1').is_err());
+    }
+
+    #[test]
+    fn cards_parse_with_suits() {
+        let rules = Rules::poker();
+
+        let cards = parse_cards("AhTc", &rules).unwrap();
+
+        assert_eq!(Card { face: 'A', suit: Some('h'), rank: 12 }, cards[0]);
+        assert_eq!(Card { face: 'T', suit: Some('c'), rank: 8 }, cards[1]);
+        assert!(parse_cards("AhT", &rules).is_err());
+        assert!(parse_cards("Ax", &rules).is_err());
+        assert!(parse_cards("AhAh", &rules).is_err());
+        assert!(parse_cards("AhAs", &rules).is_ok());
+    }
+
+    #[test]
+    fn cards_display_as_parsed() {
+        let rules = Rules::poker();
+        let cards = parse_cards("AhTc", &rules).unwrap();
+
+        assert_eq!("Ah", cards[0].to_string());
+        assert_eq!("K", Card::new('K', None, &Rules::camel()).unwrap().to_string());
     }
 }
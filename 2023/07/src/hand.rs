@@ -1,32 +1,93 @@
-use crate::{card::card_value, hand_type::HandType};
+use std::{collections::HashMap, fmt::Display};
+
+use common::parse::{number, parse, Res};
+use nom::{
+    bytes::complete::take_till1,
+    character::complete::space1,
+    error::context,
+    sequence::separated_pair,
+};
+
+use crate::{
+    card::{parse_cards, Card},
+    rules::{Rules, TieBreak},
+};
 
 #[derive(Debug, Eq)]
 pub struct Hand {
+    pub cards: Vec<Card>,
     pub bid: u32,
-    cards: Vec<u32>,
-    t: HandType,
+    // index into the rules' types
+    t: usize,
+    // ranks to compare when the types are equal
+    key: Vec<i64>,
 }
 
 impl Hand {
-    pub fn new(s: &str, joker: bool) -> Result<Self, String> {
-        let (cards, bid) = s.split_once(' ').ok_or("Could not split on whitespace")?;
+    pub fn new(s: &str, rules: &Rules) -> Result<Self, String> {
+        let (cards, bid) = parse(s, parse_hand)?;
 
-        let bid = bid.parse().map_err(|_| "Could not parse bid")?;
-        let cards = cards.chars().map(|c| card_value(c, joker)).collect();
+        Self::with_rules(cards, bid, rules)
+    }
 
-        let hand_type = HandType::new(&cards, joker)?;
+    /// Checks `cards` against the rules, for when the line is already split.
+    pub(crate) fn with_rules(cards: &str, bid: u32, rules: &Rules) -> Result<Self, String> {
+        let cards = parse_cards(cards, rules)?;
+
+        let t = rules.classify(&cards)?;
+        let ace_low = rules.types[t].is_wheel(&cards, rules);
+        let key = tie_break_key(&cards, rules, ace_low);
 
         Ok(Self {
-            bid,
             cards,
-            t: hand_type,
+            bid,
+            t,
+            key,
         })
     }
 }
 
+/// `32T3K 765`, as the cards, still to be checked against the rules, and the bid.
+pub(crate) fn parse_hand(input: &str) -> Res<'_, (&str, u32)> {
+    let cards = take_till1(|c: char| c.is_whitespace());
+
+    context("a hand", separated_pair(cards, space1, number))(input)
+}
+
+impl Display for Hand {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        for card in self.cards.iter() {
+            write!(f, "{}", card)?;
+        }
+
+        write!(f, " {}", self.bid)
+    }
+}
+
+// In a wheel the top face counts as one below the weakest, so A2345 loses to
+// 23456.
+fn tie_break_key(cards: &[Card], rules: &Rules, ace_low: bool) -> Vec<i64> {
+    let top = rules.straight_sequence().last().copied();
+    let mut ranks: Vec<_> = cards
+        .iter()
+        .map(|c| if ace_low && Some(c.face) == top { -1 } else { c.rank as i64 })
+        .collect();
+
+    if rules.tie_break == TieBreak::Sorted {
+        let counts = ranks.iter().fold(HashMap::new(), |mut acc, r| {
+            *acc.entry(*r).or_insert(0) += 1;
+            acc
+        });
+
+        ranks.sort_unstable_by(|a, b| counts[b].cmp(&counts[a]).then(b.cmp(a)));
+    }
+
+    ranks
+}
+
 impl PartialEq for Hand {
     fn eq(&self, other: &Self) -> bool {
-        self.cards.eq(&other.cards)
+        self.t == other.t && self.key == other.key
     }
 }
 
@@ -38,19 +99,9 @@ impl PartialOrd for Hand {
 
 impl Ord for Hand {
     fn cmp(&self, other: &Self) -> std::cmp::Ordering {
-        if self.t != other.t {
-            return self.t.cmp(&other.t);
-        }
-
-        let first_unequal = self.cards
-            .iter()
-            .zip(other.cards.iter())
-            .find(|(m, o)| m != o);
-
-        match first_unequal {
-            Some((m, o)) => m.cmp(o),
-            None => std::cmp::Ordering::Equal,
-        }
+        self.t
+            .cmp(&other.t)
+            .then_with(|| self.key.cmp(&other.key))
     }
 }
 
@@ -60,34 +111,68 @@ mod tests {
 
     use super::*;
 
-
     #[test]
     fn hand_parses() {
-        let cards = vec![ 3, 2, 10, 3, 13, ];
-
-        let t = HandType::new(&cards, false).unwrap();
-
+        let rules = Rules::camel();
         let exp = Hand {
+            cards: parse_cards("32T3K", &rules).unwrap(),
             bid: 765,
-            cards,
-            t,
+            t: 1,
+            key: vec![1, 0, 8, 1, 11],
         };
 
-        let hand = Hand::new("32T3K 765", false).unwrap();
+        let hand = Hand::new("32T3K 765", &rules).unwrap();
 
         assert_eq!(exp, hand);
+        assert!(Hand::new("32T3X 765", &rules).is_err());
+        assert_eq!(
+            Err("line 1, column 7: expected a number in a hand".to_string()),
+            Hand::new("32T3K bid", &rules)
+        );
     }
 
     #[test]
     fn hand_compares() {
+        let rules = Rules::camel();
         let exps = vec![
-            (Ordering::Equal, Hand::new("32T3K 0", false), Hand::new("32T3K 0", false)),
-            (Ordering::Greater, Hand::new("KK677 0", false), Hand::new("KTJJT 0", false)),
-            (Ordering::Less, Hand::new("32T3K 0", false), Hand::new("T55J5 0", false)),
+            (Ordering::Equal, "32T3K 0", "32T3K 0"),
+            (Ordering::Greater, "KK677 0", "KTJJT 0"),
+            (Ordering::Less, "32T3K 0", "T55J5 0"),
         ];
 
         for (ord, one, two) in exps {
+            let one = Hand::new(one, &rules).unwrap();
+            let two = Hand::new(two, &rules).unwrap();
+
             assert_eq!(ord, one.cmp(&two));
         }
     }
+
+    #[test]
+    fn hand_displays_as_parsed() {
+        assert_eq!("32T3K 765", Hand::new("32T3K 765", &Rules::camel()).unwrap().to_string());
+        assert_eq!("AhKc3d3sQh 0", Hand::new("AhKc3d3sQh 0", &Rules::poker()).unwrap().to_string());
+    }
+
+    #[test]
+    fn hand_compares_sorted() {
+        let rules = Rules::poker();
+        let one = Hand::new("2h2cAdKsQh 0", &rules).unwrap();
+        let two = Hand::new("AhKc3d3sQh 0", &rules).unwrap();
+
+        // pair of threes beats pair of twos, whatever else was dealt
+        assert_eq!(Ordering::Less, one.cmp(&two));
+    }
+
+    #[test]
+    fn hand_compares_wheel_lowest() {
+        let rules = Rules::poker();
+        let wheel = Hand::new("Ah2c3d4s5h 0", &rules).unwrap();
+        let six_high = Hand::new("2h3c4d5s6h 0", &rules).unwrap();
+        let broadway = Hand::new("ThJcQdKsAh 0", &rules).unwrap();
+
+        assert_eq!(Ordering::Less, wheel.cmp(&six_high));
+        assert_eq!(Ordering::Less, six_high.cmp(&broadway));
+        assert_eq!(Ordering::Equal, wheel.cmp(&Hand::new("5c4d3h2sAc 0", &rules).unwrap()));
+    }
 }
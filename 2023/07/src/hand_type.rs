@@ -1,145 +1,213 @@
 use std::collections::HashMap;
 
-#[derive(Clone, Debug, PartialEq, Eq, PartialOrd, Ord)]
+use crate::{card::Card, rules::Rules};
+
+#[derive(Clone, Debug, PartialEq, Eq)]
 pub enum HandType {
-    High,
-    OnePair,
-    TwoPair,
-    Three,
-    Full,
-    Four,
-    Five,
+    /// Cards of the same face, as group sizes from largest down; `[3, 2]` is
+    /// a full house. Every card of the hand belongs to one of the groups.
+    Groups(Vec<usize>),
+    /// Consecutive faces in the rank order, leaving out wild faces
+    Straight,
+    /// Every card of the same suit
+    Flush,
+    StraightFlush,
 }
 
-impl TryFrom<usize> for HandType {
-    type Error = String;
-
-    fn try_from(value: usize) -> Result<Self, Self::Error> {
-        let types = vec![
-            HandType::High,
-            HandType::OnePair,
-            HandType::TwoPair,
-            HandType::Three,
-            HandType::Full,
-            HandType::Four,
-            HandType::Five,
-        ];
+impl HandType {
+    pub fn groups(sizes: &[usize]) -> Self {
+        Self::Groups(sizes.to_vec())
+    }
 
-        Ok(types.get(value).ok_or("Could not find HandType from given value")?.clone())
+    /// Whether the wild cards can be turned into something that makes the
+    /// hand this type.
+    pub fn is_achievable(&self, cards: &[Card], rules: &Rules) -> bool {
+        let (wild, natural): (Vec<&Card>, Vec<&Card>) = cards.iter().partition(|c| rules.is_wild(c));
+
+        match self {
+            HandType::Groups(sizes) => groups_achievable(sizes, &natural, wild.len()),
+            HandType::Straight => straight_achievable(&natural, rules),
+            HandType::Flush => flush_achievable(&natural),
+            HandType::StraightFlush => straight_achievable(&natural, rules) && flush_achievable(&natural),
+        }
     }
-}
 
-impl HandType {
-    pub fn new(cards: &Vec<u32>, joker: bool) -> Result<Self, String> {
-        if cards.len() != 5 {
-            return Err(format!("Could not construct type from hand: {:?}", cards));
+    /// Whether a hand of this type is only a straight with the top face
+    /// below the bottom one, like A2345, so that face ranks lowest in it.
+    pub fn is_wheel(&self, cards: &[Card], rules: &Rules) -> bool {
+        if !matches!(self, HandType::Straight | HandType::StraightFlush) {
+            return false;
         }
 
-        let map: HashMap<_, _> = cards.iter().fold(HashMap::new(), |mut acc, c| {
-            *acc.entry(c).or_insert(0) += 1;
-            acc
-        });
-
-        let mut unique_cards: Vec<_> = map.into_iter().collect();
-        unique_cards.sort_unstable_by(|a, b| b.1.cmp(&a.1));
-        let most_dups = unique_cards[0];
-        let most_dups_2 = unique_cards.get(1).map(|uc| uc.1);
-
-        let joker_count = cards.iter().filter(|c| **c == 1).count() as u32;
-        let joker_is_most = *most_dups.0 == 1;
-
-        let most_dups = if joker && !joker_is_most {
-            most_dups.1 + joker_count
-        } else if joker && joker_is_most && most_dups_2.is_some() {
-            most_dups.1 + most_dups_2.unwrap()
-        } else {
-            most_dups.1
-        };
+        let natural: Vec<&Card> = cards.iter().filter(|c| !rules.is_wild(c)).collect();
 
-        // This takes care of real Five's and joker induced Five's
-        if most_dups >= 5 {
-            return Ok(Self::Five);
-        }
+        straights(&natural, rules) == [true]
+    }
 
-        let most_dups_2 = most_dups_2.unwrap();
+    /// Every way of splitting `hand_size` cards into groups, weakest first.
+    /// For five cards that's high card, one pair, two pair, three of a kind,
+    /// full house, four and five of a kind.
+    pub fn all_groups(hand_size: usize) -> Vec<Self> {
+        let mut partitions = vec![];
+        partition(hand_size, hand_size, &mut vec![], &mut partitions);
 
-        // This takes care of real Four's and joker induced Four's
-        if most_dups == 4 {
-            return Ok(Self::Four);
-        }
+        partitions.sort();
+        partitions.into_iter().map(Self::Groups).collect()
+    }
+}
 
-        if most_dups == 3 {
-            return Ok(
-                if most_dups_2 == 2 {
-                    Self::Full
-                } else {
-                    Self::Three
-                }
-            );
-        }
+fn partition(remaining: usize, max: usize, current: &mut Vec<usize>, out: &mut Vec<Vec<usize>>) {
+    if remaining == 0 {
+        out.push(current.clone());
+        return;
+    }
+
+    for size in (1..=remaining.min(max)).rev() {
+        current.push(size);
+        partition(remaining - size, size, current, out);
+        current.pop();
+    }
+}
+
+// Wild cards can join any group or start a new one. The natural groups have
+// to fit inside the wanted groups, which is only possible if they fit when
+// both are sorted largest first.
+fn groups_achievable(sizes: &[usize], natural: &[&Card], wild: usize) -> bool {
+    let counts = natural.iter().fold(HashMap::new(), |mut acc, c| {
+        *acc.entry(c.face).or_insert(0) += 1;
+        acc
+    });
+
+    let mut natural_sizes: Vec<usize> = counts.into_values().collect();
+    natural_sizes.sort_unstable_by(|a, b| b.cmp(a));
+
+    let mut sizes = sizes.to_vec();
+    sizes.sort_unstable_by(|a, b| b.cmp(a));
+
+    let total: usize = sizes.iter().sum();
+    if total != natural.len() + wild || natural_sizes.len() > sizes.len() {
+        return false;
+    }
+
+    natural_sizes.iter().zip(sizes.iter()).all(|(n, s)| n <= s)
+}
+
+fn straight_achievable(natural: &[&Card], rules: &Rules) -> bool {
+    !straights(natural, rules).is_empty()
+}
+
+// The straights the natural cards fit in, each as whether it's the wheel.
+fn straights(natural: &[&Card], rules: &Rules) -> Vec<bool> {
+    let sequence = rules.straight_sequence();
+    let length = rules.hand_size;
 
-        if most_dups == 2 {
-            return Ok(
-                if most_dups_2 == 2 {
-                    Self::TwoPair
-                } else {
-                    Self::OnePair
-                }
-            );
+    if length == 0 || sequence.len() < length {
+        return vec![];
+    }
+
+    let mut positions = vec![];
+    for c in natural {
+        let Some(p) = sequence.iter().position(|f| *f == c.face) else {
+            return vec![];
+        };
+
+        if positions.contains(&p) {
+            return vec![];
         }
 
-        Ok(Self::High)
+        positions.push(p);
+    }
+
+    let mut windows: Vec<(Vec<usize>, bool)> = (0..=(sequence.len() - length))
+        .map(|start| ((start..(start + length)).collect(), false))
+        .collect();
+
+    // the top face can also sit below the bottom one, like an ace low straight
+    if rules.wheel {
+        let mut wheel = vec![sequence.len() - 1];
+        wheel.extend(0..(length - 1));
+        windows.push((wheel, true));
     }
+
+    windows
+        .into_iter()
+        .filter(|(w, _)| positions.iter().all(|p| w.contains(p)))
+        .map(|(_, wheel)| wheel)
+        .collect()
+}
+
+fn flush_achievable(natural: &[&Card]) -> bool {
+    let Some(first) = natural.first() else {
+        return true;
+    };
+
+    first.suit.is_some() && natural.iter().all(|c| c.suit == first.suit)
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::card::parse_cards;
+
+    fn achievable(t: &HandType, hand: &str, rules: &Rules) -> bool {
+        t.is_achievable(&parse_cards(hand, rules).unwrap(), rules)
+    }
 
     #[test]
-    fn hand_type_news_correctly() {
-        let exps = vec![
-            (HandType::Five, vec![14, 14, 14, 14, 14]),
-            (HandType::Four, vec![14, 14, 14, 14, 13]),
-            (HandType::Full, vec![14, 14, 14, 13, 13]),
-            (HandType::Three, vec![14, 14, 14, 13, 12]),
-            (HandType::TwoPair, vec![14, 14, 13, 13, 12]),
-            (HandType::OnePair, vec![14, 14, 13, 12, 11]),
-            (HandType::High, vec![14, 13, 12, 11, 10]),
+    fn hand_type_lists_groups() {
+        let exp = vec![
+            HandType::groups(&[1, 1, 1, 1, 1]),
+            HandType::groups(&[2, 1, 1, 1]),
+            HandType::groups(&[2, 2, 1]),
+            HandType::groups(&[3, 1, 1]),
+            HandType::groups(&[3, 2]),
+            HandType::groups(&[4, 1]),
+            HandType::groups(&[5]),
         ];
 
-        for (exp, cards) in exps.into_iter() {
-            assert_eq!(exp, HandType::new(&cards, false).unwrap());
-        }
+        assert_eq!(exp, HandType::all_groups(5));
+        assert_eq!(11, HandType::all_groups(6).len());
+    }
 
-        // Now with jokers
-        let exps = vec![
-            // 1 joker
-            (HandType::Five, vec![14, 14, 14, 14, 1]),
-            (HandType::Four, vec![14, 14, 14, 1, 13]),
-            (HandType::Full, vec![14, 14, 1, 13, 13]),
-            (HandType::Three, vec![14, 14, 1, 13, 12]),
-            (HandType::OnePair, vec![14, 1, 13, 12, 11]),
-            (HandType::High, vec![14, 13, 12, 11, 10]),
-
-            // 2 jokers
-            (HandType::Five, vec![2, 2, 2, 1, 1]),
-            (HandType::Four, vec![3, 2, 2, 1, 1]),
-            (HandType::Three, vec![4, 3, 2, 1, 1]),
-
-            // 3 jokers
-            (HandType::Five, vec![2, 2, 1, 1, 1]),
-            (HandType::Four, vec![3, 2, 1, 1, 1]),
-
-            // 4 jokers
-            (HandType::Five, vec![2, 1, 1, 1, 1]),
-
-            // 5 jokers
-            (HandType::Five, vec![1, 1, 1, 1, 1]),
-        ];
+    #[test]
+    fn hand_type_achieves_groups() {
+        let rules = Rules::camel_jokers();
+        let full = HandType::groups(&[3, 2]);
+
+        assert!(achievable(&full, "22333", &rules));
+        assert!(achievable(&full, "2233J", &rules));
+        assert!(!achievable(&full, "22334", &rules));
+        assert!(!achievable(&full, "2222J", &rules));
+    }
 
-        for (exp, cards) in exps.into_iter() {
-            assert_eq!(exp, HandType::new(&cards, true).unwrap());
-        }
+    #[test]
+    fn hand_type_achieves_straights() {
+        let rules = Rules::poker();
+
+        assert!(achievable(&HandType::Straight, "9hTcJdQsKh", &rules));
+        assert!(achievable(&HandType::Straight, "Ah2c3d4s5h", &rules));
+        assert!(!achievable(&HandType::Straight, "QhKcAd2s3h", &rules));
+        assert!(!achievable(&HandType::Straight, "9h9cJdQsKh", &rules));
+    }
+
+    #[test]
+    fn hand_type_spots_wheels() {
+        let rules = Rules::poker();
+        let wheel = |hand: &str| HandType::Straight.is_wheel(&parse_cards(hand, &rules).unwrap(), &rules);
+
+        assert!(wheel("Ah2c3d4s5h"));
+        assert!(!wheel("2h3c4d5s6h"));
+        assert!(!wheel("ThJcQdKsAh"));
+        assert!(!HandType::Flush.is_wheel(&parse_cards("Ah2h3h4h5h", &rules).unwrap(), &rules));
+    }
+
+    #[test]
+    fn hand_type_achieves_flushes() {
+        let rules = Rules::poker();
+
+        assert!(achievable(&HandType::Flush, "2h5h9hQhAh", &rules));
+        assert!(!achievable(&HandType::Flush, "2h5h9hQhAs", &rules));
+        assert!(achievable(&HandType::StraightFlush, "9hThJhQhKh", &rules));
+        assert!(!achievable(&HandType::StraightFlush, "9hThJhQhKs", &rules));
     }
 }
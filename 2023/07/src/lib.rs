@@ -1,26 +1,85 @@
-use hand::Hand;
+use std::error::Error;
+
+use common::{
+    parse::{self, lines},
+    Answer,
+};
+
+pub use card::{parse_cards, Card};
+pub use hand::Hand;
+pub use hand_type::HandType;
+pub use rules::{Rules, TieBreak};
+
+use hand::parse_hand;
 
 mod card;
 mod hand;
 mod hand_type;
+mod rules;
+
+fn parse(s: &str, rules: &Rules) -> Result<Vec<Hand>, Box<dyn Error>> {
+    let hands = parse::parse(s, lines(parse_hand))?
+        .into_iter()
+        .enumerate()
+        .map(|(i, (cards, bid))| {
+            Hand::with_rules(cards, bid, rules).map_err(|e| format!("line {}: {}", i + 1, e))
+        })
+        .collect::<Result<_, _>>()?;
 
-fn parse(s: &str, joker: bool) -> Vec<Hand> {
-    s.lines()
-        .filter_map(|l| Hand::new(l, joker).ok())
-        .collect()
+    Ok(hands)
 }
 
-pub fn run(s: &str, joker: bool) -> usize {
-    let mut hands = parse(s, joker);
+#[tracing::instrument(skip_all)]
+pub fn part1(s: &str) -> Result<Answer, Box<dyn Error>> {
+    Ok(winnings(s, &Rules::camel())?.into())
+}
+
+/// Part 1 with `J` as a joker.
+#[tracing::instrument(skip_all)]
+pub fn part2(s: &str) -> Result<Answer, Box<dyn Error>> {
+    Ok(winnings(s, &Rules::camel_jokers())?.into())
+}
+
+/// Every hand's bid times its rank, summed.
+pub fn winnings(s: &str, rules: &Rules) -> Result<usize, Box<dyn Error>> {
+    let mut hands = parse(s, rules)?;
 
     hands.sort();
 
-    hands
+    Ok(hands
         .iter()
         .enumerate()
         .map(|(i, h)| {
             let rank = i + 1;
             rank * h.bid as usize
         })
-        .sum()
+        .sum())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const SAMPLE: &str =
+r"32T3K 765
+T55J5 684
+KK677 28
+KTJJT 220
+QQQJA 483";
+
+    #[test]
+    fn winnings_answer_sample() {
+        assert_eq!(6440, winnings(SAMPLE, &Rules::camel()).unwrap());
+        assert_eq!(5905, winnings(SAMPLE, &Rules::camel_jokers()).unwrap());
+        assert_eq!(Answer::Int(5905), part2(SAMPLE).unwrap());
+    }
+
+    #[test]
+    fn winnings_report_bad_hands() {
+        assert_eq!(
+            "line 2, column 6: expected space in a hand",
+            winnings("32T3K 765\nT55J5", &Rules::camel()).unwrap_err().to_string()
+        );
+        assert!(winnings("32T3K 765\nT55X5 1", &Rules::camel()).unwrap_err().to_string().starts_with("line 2: "));
+    }
 }
@@ -1,14 +1,21 @@
-use std::{env, fs};
+use std::{env, error::Error, fs};
+
+fn main() -> Result<(), Box<dyn Error>> {
+    let _day = common::trace::init("camel");
 
-fn main() {
     let args: Vec<_> = env::args().collect();
     let filename = args.get(1).expect("give filename");
 
     let contents = fs::read_to_string(filename).expect("file exist. now.");
 
-    let winnings = camel::run(&contents, false);
-    println!("solution 1 (winnings): {}", winnings);
+    if common::answer::for_tools() {
+        common::answer::print(1, &camel::part1(&contents)?);
+        common::answer::print(2, &camel::part2(&contents)?);
+        return Ok(());
+    }
+
+    println!("solution 1 (winnings): {}", camel::part1(&contents)?);
+    println!("solution 2 (winnings with jokers): {}", camel::part2(&contents)?);
 
-    let winnings = camel::run(&contents, true);
-    println!("solution 2 (winnings with jokers): {}", winnings);
+    Ok(())
 }
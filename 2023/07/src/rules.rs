@@ -0,0 +1,280 @@
+use crate::{card::Card, hand_type::HandType};
+
+const CAMEL_ORDER: &str = "23456789TJQKA";
+
+/// How two hands of the same type are ordered.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum TieBreak {
+    /// Card by card, in the order they were dealt
+    Lexicographic,
+    /// Biggest groups first, then highest rank first, like poker
+    Sorted,
+}
+
+/// Everything a game needs to know to rank hands.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Rules {
+    /// the card faces, weakest first
+    pub order: Vec<char>,
+    /// the suits, empty when cards don't have any
+    pub suits: Vec<char>,
+    /// faces that stand in for whatever makes the hand strongest
+    pub wild: Vec<char>,
+    pub hand_size: usize,
+    /// the hand types, weakest first
+    pub types: Vec<HandType>,
+    pub tie_break: TieBreak,
+    /// whether the strongest face can also go below the weakest in a straight
+    pub wheel: bool,
+}
+
+impl Rules {
+    /// Part 1: no wild cards, only groups count.
+    pub fn camel() -> Self {
+        Self {
+            order: CAMEL_ORDER.chars().collect(),
+            suits: vec![],
+            wild: vec![],
+            hand_size: 5,
+            types: HandType::all_groups(5),
+            tie_break: TieBreak::Lexicographic,
+            wheel: false,
+        }
+    }
+
+    /// Part 2: `J` is a joker, and the weakest card on its own.
+    pub fn camel_jokers() -> Self {
+        Self::camel().with_wild("J")
+    }
+
+    /// Standard five card poker. Cards are written face then suit, e.g. `Th`.
+    pub fn poker() -> Self {
+        Self {
+            order: CAMEL_ORDER.chars().collect(),
+            suits: "cdhs".chars().collect(),
+            wild: vec![],
+            hand_size: 5,
+            types: vec![
+                HandType::groups(&[1, 1, 1, 1, 1]),
+                HandType::groups(&[2, 1, 1, 1]),
+                HandType::groups(&[2, 2, 1]),
+                HandType::groups(&[3, 1, 1]),
+                HandType::Straight,
+                HandType::Flush,
+                HandType::groups(&[3, 2]),
+                HandType::groups(&[4, 1]),
+                HandType::StraightFlush,
+                HandType::groups(&[5]),
+            ],
+            tie_break: TieBreak::Sorted,
+            wheel: true,
+        }
+    }
+
+    /// Makes `faces` wild and moves them to the bottom of the order.
+    pub fn with_wild(mut self, faces: &str) -> Self {
+        let wild: Vec<_> = faces.chars().collect();
+
+        self.order.retain(|c| !wild.contains(c));
+        self.order.splice(0..0, wild.iter().copied());
+        self.wild = wild;
+        self
+    }
+
+    /// Deals `hand_size` cards instead. The types become every way of
+    /// grouping that many cards, so straights and flushes are dropped.
+    pub fn with_hand_size(mut self, hand_size: usize) -> Result<Self, String> {
+        if hand_size == 0 {
+            return Err("A hand needs at least one card".to_string());
+        }
+
+        self.hand_size = hand_size;
+        self.types = HandType::all_groups(hand_size);
+        Ok(self)
+    }
+
+    pub fn card_value(&self, c: char) -> Result<u32, String> {
+        self.order
+            .iter()
+            .position(|o| *o == c)
+            .map(|p| p as u32)
+            .ok_or(format!("Not valid card: {}", c))
+    }
+
+    pub fn is_wild(&self, card: &Card) -> bool {
+        self.wild.contains(&card.face)
+    }
+
+    /// The faces a straight runs through, weakest first. Wild faces can be
+    /// anything, so they aren't steps of their own.
+    pub fn straight_sequence(&self) -> Vec<char> {
+        self.order
+            .iter()
+            .filter(|c| !self.wild.contains(c))
+            .copied()
+            .collect()
+    }
+
+    /// Index into `types` of the strongest type the hand can make.
+    pub fn classify(&self, cards: &[Card]) -> Result<usize, String> {
+        if cards.len() != self.hand_size {
+            return Err(format!(
+                "Expected {} cards, got {}",
+                self.hand_size,
+                cards.len()
+            ));
+        }
+
+        self.types
+            .iter()
+            .rposition(|t| t.is_achievable(cards, self))
+            .ok_or(format!("No hand type fits: {:?}", cards))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::card::parse_cards;
+
+    fn classify(hand: &str, rules: &Rules) -> usize {
+        rules.classify(&parse_cards(hand, rules).unwrap()).unwrap()
+    }
+
+    // every multiset of `size` faces, each as faces in ascending order
+    fn multisets(faces: &[char], size: usize) -> Vec<Vec<char>> {
+        if size == 0 {
+            return vec![vec![]];
+        }
+
+        let mut all = vec![];
+        for (i, f) in faces.iter().enumerate() {
+            for mut rest in multisets(&faces[i..], size - 1) {
+                rest.insert(0, *f);
+                all.push(rest);
+            }
+        }
+
+        all
+    }
+
+    // tries every combination of faces for the wild cards, with the wild
+    // cards made natural
+    fn brute_force(hand: &[char], wild: &Rules, natural: &Rules) -> usize {
+        let (wilds, mut kept): (Vec<char>, Vec<char>) =
+            hand.iter().partition(|c| wild.wild.contains(c));
+        let faces: Vec<_> = natural
+            .order
+            .iter()
+            .filter(|f| !wild.wild.contains(f))
+            .copied()
+            .collect();
+
+        kept.extend(std::iter::repeat_n('?', wilds.len()));
+        let at = kept.len() - wilds.len();
+
+        multisets(&faces, wilds.len())
+            .into_iter()
+            .map(|faces| {
+                kept.splice(at.., faces);
+                let hand: String = kept.iter().collect();
+                classify(&hand, natural)
+            })
+            .max()
+            .unwrap()
+    }
+
+    fn check_every_hand(wild: &Rules, natural: &Rules) {
+        for hand in multisets(&wild.order, wild.hand_size) {
+            let name: String = hand.iter().collect();
+
+            assert_eq!(
+                brute_force(&hand, wild, natural),
+                classify(&name, wild),
+                "hand {}",
+                name
+            );
+        }
+    }
+
+    #[test]
+    fn rules_classify_groups() {
+        let rules = Rules::camel();
+        let exps = vec![
+            ("23456", 0),
+            ("A23A4", 1),
+            ("23432", 2),
+            ("TTT98", 3),
+            ("23332", 4),
+            ("AA8AA", 5),
+            ("AAAAA", 6),
+        ];
+
+        for (hand, exp) in exps {
+            assert_eq!(exp, classify(hand, &rules), "hand {}", hand);
+        }
+
+        assert!(rules.classify(&parse_cards("2345", &rules).unwrap()).is_err());
+    }
+
+    #[test]
+    fn rules_classify_jokers() {
+        let rules = Rules::camel_jokers();
+        let exps = vec![
+            ("2345J", 1),
+            ("2245J", 3),
+            ("2244J", 4),
+            ("222JJ", 6),
+            ("JJJJJ", 6),
+            ("QJJQ2", 5),
+        ];
+
+        for (hand, exp) in exps {
+            assert_eq!(exp, classify(hand, &rules), "hand {}", hand);
+        }
+    }
+
+    #[test]
+    fn rules_classify_jokers_optimally() {
+        check_every_hand(&Rules::camel_jokers(), &Rules::camel());
+    }
+
+    #[test]
+    fn rules_classify_several_wild_faces_optimally() {
+        let wild = Rules::camel().with_wild("J2");
+        let natural = Rules {
+            wild: vec![],
+            ..wild.clone()
+        };
+
+        check_every_hand(&wild, &natural);
+    }
+
+    #[test]
+    fn rules_classify_six_cards() {
+        let rules = Rules::camel_jokers().with_hand_size(6).unwrap();
+        let types = &rules.types;
+
+        assert_eq!(HandType::groups(&[3, 3]), types[classify("222333", &rules)]);
+        assert_eq!(HandType::groups(&[4, 2]), types[classify("222J33", &rules)]);
+        assert_eq!(HandType::groups(&[6]), types[classify("AAAJJA", &rules)]);
+        assert_eq!(HandType::groups(&[2, 2, 2]), types[classify("223344", &rules)]);
+        assert!(Rules::camel().with_hand_size(0).is_err());
+    }
+
+    #[test]
+    fn rules_classify_poker() {
+        let rules = Rules::poker();
+        let exps = vec![
+            ("2h5h9hQhAh", HandType::Flush),
+            ("Ah2c3d4s5h", HandType::Straight),
+            ("9hThJhQhKh", HandType::StraightFlush),
+            ("9h9c9dQsQh", HandType::groups(&[3, 2])),
+            ("9h9c2dQsKh", HandType::groups(&[2, 1, 1, 1])),
+        ];
+
+        for (hand, exp) in exps {
+            assert_eq!(exp, rules.types[classify(hand, &rules)], "hand {}", hand);
+        }
+    }
+}
@@ -1,68 +1,105 @@
-use map::Map;
+use std::error::Error;
+
+use common::{math::lcm_all, Answer};
 use traversal::traverse;
 
+pub use map::Map;
+
 mod map;
 mod traversal;
 
-pub fn run(contents: &str) -> Result<(), String>{
-    let map: Map = contents.parse()?;
-    let steps = human(&map)?;
+common::params! {
+    /// Where the walks start and end.
+    #[derive(Debug, Clone)]
+    pub struct Ends {
+        /// the node part 1 starts from
+        start: String = "AAA".to_string(),
+        /// the node part 1 ends at
+        end: String = "ZZZ".to_string(),
+        /// what the nodes ghosts start from end with
+        ghost_start: String = "A".to_string(),
+        /// what the nodes ghosts end at end with
+        ghost_end: String = "Z".to_string(),
+    }
+}
 
-    println!("solution 1 (ZZZ): {}", steps);
+/// Steps from `AAA` to `ZZZ`.
+#[tracing::instrument(skip_all)]
+pub fn part1(contents: &str) -> Result<Answer, Box<dyn Error>> {
+    part1_with(contents, &Ends::default())
+}
 
-    let spooky_steps = spooky(&map)?;
+/// Steps from `start` to `end`.
+pub fn part1_with(contents: &str, ends: &Ends) -> Result<Answer, Box<dyn Error>> {
+    Ok(human(&contents.parse()?, ends)?.into())
+}
 
-    println!("solution 2 (**Z): {}", spooky_steps);
+/// Steps until every ghost is on a `Z` node at once.
+#[tracing::instrument(skip_all)]
+pub fn part2(contents: &str) -> Result<Answer, Box<dyn Error>> {
+    part2_with(contents, &Ends::default())
+}
 
-    Ok(())
+/// Steps until every ghost is on a node ending in `ghost_end` at once.
+pub fn part2_with(contents: &str, ends: &Ends) -> Result<Answer, Box<dyn Error>> {
+    Ok(spooky(&contents.parse()?, ends)?.into())
 }
 
-fn human(map: &Map) -> Result<u64, String> {
-    let s = traverse(map, "AAA")?;
+fn human(map: &Map, ends: &Ends) -> Result<u64, String> {
+    if !map.nodes.contains_key(&ends.start) {
+        return Err(format!("there should be a node called {}", ends.start));
+    }
+
+    let s = traverse(map, &ends.start, |n| n == ends.end)?;
 
     Ok(s)
 }
 
-fn spooky(map: &Map) -> Result<u64, String> {
+fn spooky(map: &Map, ends: &Ends) -> Result<u64, String> {
     let key_steps = map.nodes
         .keys()
         .filter_map(|k| {
-            if !k.ends_with('A') { return None; }
-            Some(traverse(map, k))
+            if !k.ends_with(&ends.ghost_start) { return None; }
+            Some(traverse(map, k, |n| n.ends_with(&ends.ghost_end)))
         })
         .collect::<Result<Vec<_>, _>>()?;
 
-    let lcm = lcm_multi(key_steps)?;
+    if key_steps.is_empty() {
+        return Err(format!("there should be a node ending in '{}'", ends.ghost_start));
+    }
 
-    Ok(lcm)
+    lcm_all(&key_steps).ok_or("steps should fit in a u64".to_string())
 }
 
-fn lcm_multi(ns: Vec<u64>) -> Result<u64, String> {
-    let mut last_lcm = *ns.first().ok_or("ns should not be empty")?;
-    for n in ns.iter() {
-        last_lcm = least_common_multiple(n, &last_lcm);
-    }
+#[cfg(test)]
+mod tests {
+    use super::*;
 
-    Ok(last_lcm)
-}
+    #[test]
+    fn solves_samples() {
+        let human =
+r"LLR
 
-fn least_common_multiple(a: &u64, b: &u64) -> u64 {
-    let gcd = gcd(a, b);
+AAA = (BBB, BBB)
+BBB = (AAA, ZZZ)
+ZZZ = (ZZZ, ZZZ)";
 
-    (a * b) / gcd
-}
+        let spooky =
+r"LR
 
-fn gcd(a: &u64, b: &u64) -> u64 {
-    let mut a = *a;
-    let mut b = *b;
+11A = (11B, XXX)
+11B = (XXX, 11Z)
+11Z = (11B, XXX)
+22A = (22B, XXX)
+22B = (22C, 22C)
+22C = (22Z, 22Z)
+22Z = (22B, 22B)
+XXX = (XXX, XXX)";
 
-    let mut r: u64;
+        assert_eq!(Answer::Int(6), part1(human).unwrap());
+        assert_eq!(Answer::Int(6), part2(spooky).unwrap());
 
-    while (a % b) > 0 {
-        r = a % b;
-        a = b;
-        b = r;
+        let backwards = Ends { start: "BBB".to_string(), end: "AAA".to_string(), ..Ends::default() };
+        assert_eq!(Answer::Int(1), part1_with(human, &backwards).unwrap());
     }
-
-    b
 }
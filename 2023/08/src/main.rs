@@ -1,12 +1,24 @@
-use std::{env, error::Error};
+use std::{env, error::Error, path::Path};
 use std::fs;
 
+use wasteland::Ends;
+
 fn main() -> Result<(), Box<dyn Error>>{
-    let args: Vec<_> = env::args().collect();
+    let _day = common::trace::init("wasteland");
+
+    let args: Vec<String> = env::args().collect();
+    let (ends, args) = common::params::load::<Ends>(Path::new(env!("CARGO_MANIFEST_DIR")), &args)?;
     let filename = args.get(1).expect("filename plz");
     let contents = fs::read_to_string(filename)?;
 
-    wasteland::run(&contents)?;
+    if common::answer::for_tools() {
+        common::answer::print(1, &wasteland::part1_with(&contents, &ends)?);
+        common::answer::print(2, &wasteland::part2_with(&contents, &ends)?);
+        return Ok(());
+    }
+
+    println!("solution 1 ({}): {}", ends.end, wasteland::part1_with(&contents, &ends)?);
+    println!("solution 2 (**{}): {}", ends.ghost_end, wasteland::part2_with(&contents, &ends)?);
 
     Ok(())
 }
@@ -1,4 +1,13 @@
-use std::{collections::HashMap, str::FromStr, slice::Iter};
+use std::{collections::HashMap, fmt::Display, str::FromStr, slice::Iter};
+
+use common::parse::{blank_line, lines, parse, ParseError, Res};
+use nom::{
+    character::complete::{alphanumeric1, char, one_of, space0},
+    combinator::{map, map_res, opt},
+    error::context,
+    multi::many1,
+    sequence::{delimited, pair, separated_pair, tuple},
+};
 
 #[derive(Debug, PartialEq)]
 pub enum Direction {
@@ -28,49 +37,58 @@ impl Map {
     }
 }
 
-impl FromStr for Map {
-    type Err = String;
+impl Display for Direction {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Left => write!(f, "L"),
+            Self::Right => write!(f, "R"),
+        }
+    }
+}
 
-    fn from_str(s: &str) -> Result<Self, Self::Err> {
-        let mut lines = s.lines();
-        let directions = lines.next().ok_or("Expected directions")?;
-        lines.next().ok_or("Expected blank line")?;
+impl Display for Map {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        for d in self.directions.iter() {
+            write!(f, "{}", d)?;
+        }
 
-        let mut nodes = HashMap::new();
-        for line in lines {
-            let (src, children) = parse_node(line)?;
-            nodes.insert(src, children);
+        writeln!(f)?;
+        writeln!(f)?;
+
+        let mut nodes: Vec<_> = self.nodes.iter().collect();
+        nodes.sort();
+
+        for (src, (left, right)) in nodes {
+            writeln!(f, "{} = ({}, {})", src, left, right)?;
         }
 
-        Ok(Self {
-            directions: directions
-                .chars()
-                .filter_map(|c| Direction::from_char(c).ok())
-                .collect(),
-            nodes,
-        })
+        Ok(())
     }
 }
 
-fn parse_node(s: &str) -> Result<(String, (String, String)), String> {
-    let s: String = s
-        .chars()
-        .filter(|c| !c.is_whitespace())
-        .collect();
+impl FromStr for Map {
+    type Err = ParseError;
 
-    let (src, dest) = s
-        .split_once('=')
-        .ok_or("Expected '='")?;
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let direction = map_res(one_of("LR"), Direction::from_char);
+        let directions = context("directions", many1(direction));
+        let nodes = opt(lines(parse_node));
 
-    let s = dest.chars()
-        .filter(|c| c == &',' || c.is_alphanumeric())
-        .collect::<String>();
+        let (directions, nodes) = parse(s, separated_pair(directions, blank_line, nodes))?;
 
-    let (left, right) = s
-        .split_once(',')
-        .ok_or("Expected comma")?;
+        Ok(Self {
+            directions,
+            nodes: nodes.unwrap_or_default().into_iter().collect(),
+        })
+    }
+}
+
+/// `AAA = (BBB, CCC)`
+fn parse_node(input: &str) -> Res<'_, (String, (String, String))> {
+    let name = || map(alphanumeric1, str::to_owned);
+    let children = delimited(char('('), separated_pair(name(), pair(char(','), space0), name()), char(')'));
 
-    Ok((src.into(), (left.into(), right.into())))
+    context("a node", separated_pair(name(), tuple((space0, char('='), space0)), children))(input)
 }
 
 #[cfg(test)]
@@ -82,7 +100,7 @@ mod tests {
         let s = "AAA = (BBB, CCC)";
         let exp = ("AAA".into(), ("BBB".into(), "CCC".into()));
 
-        let parsed = parse_node(s).unwrap();
+        let parsed = parse(s, parse_node).unwrap();
 
         assert_eq!(exp, parsed);
     }
@@ -106,4 +124,25 @@ BBB = (DDD, EEE)";
 
         assert_eq!(exp, result);
     }
+
+    #[test]
+    fn map_displays_as_parsed() {
+        let s = "RL\n\nAAA = (BBB, CCC)\nBBB = (DDD, EEE)\n";
+        let map: Map = s.parse().unwrap();
+
+        assert_eq!(s, map.to_string());
+        assert_eq!(s, "RL\n\n".parse::<Map>().unwrap().to_string() + &s[4..]);
+    }
+
+    #[test]
+    fn map_reports_bad_lines() {
+        assert_eq!(
+            "line 1, column 2: expected a blank line",
+            "RXL\n\nAAA = (BBB, CCC)".parse::<Map>().unwrap_err().to_string()
+        );
+        assert_eq!(
+            "line 4, column 11: expected ',' in a node",
+            "RL\n\nAAA = (BBB, CCC)\nBBB = (DDD EEE)".parse::<Map>().unwrap_err().to_string()
+        );
+    }
 }
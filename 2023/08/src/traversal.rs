@@ -1,22 +1,39 @@
+use common::graph::{bfs, Graph};
+
 use crate::map::{Map, Direction};
 
-pub fn traverse<'a>(map: &'a Map, start: &'a str) -> Result<u64, String> {
-    let mut current = start;
-    let mut steps = 0;
+/// Where a walk is: the node, and which direction it takes next.
+type Position<'a> = (&'a str, usize);
+
+struct Walk<'a> {
+    map: &'a Map,
+}
 
-    for d in map.directions() {
-        if current.ends_with('Z') { break; }
-        steps += 1;
+impl<'a> Graph for Walk<'a> {
+    type Node = Position<'a>;
 
-        let (l, r) = map.nodes.get(current).ok_or("Expected node")?;
+    fn neighbours(&self, (node, i): &Position<'a>) -> impl IntoIterator<Item = Position<'a>> {
+        let d = self.map.directions.get(*i);
+        let (l, r) = self.map.nodes.get(*node)?;
 
-        current = match d {
+        let next = match d? {
             Direction::Left => l,
             Direction::Right => r,
-        }
+        };
+
+        Some((next.as_str(), (i + 1) % self.map.directions.len()))
     }
+}
+
+/// Steps from `start` to the first node that's an `end`. Every position only
+/// leads to one other, so this is the walk itself, ending if it loops.
+pub fn traverse<'a>(map: &'a Map, start: &'a str, end: impl Fn(&str) -> bool) -> Result<u64, String> {
+    let walk = Walk { map };
 
-    Ok(steps)
+    bfs(&walk, [(start, 0)])
+        .find(|((node, _), _)| end(node))
+        .map(|(_, steps)| steps as u64)
+        .ok_or(format!("{} should lead to an end node", start))
 }
 
 #[cfg(test)]
@@ -38,8 +55,20 @@ ZZZ = (ZZZ, ZZZ)".parse().unwrap();
 
         let start = "AAA";
         let exp = 2;
-        let traversal = traverse(&map, start.into()).unwrap();
+        let traversal = traverse(&map, start, |n| n == "ZZZ").unwrap();
 
         assert_eq!(exp, traversal);
     }
+
+    #[test]
+    fn traversal_stops_going_round() {
+        let map: Map =
+r"L
+
+AAA = (BBB, BBB)
+BBB = (AAA, AAA)".parse().unwrap();
+
+        assert!(traverse(&map, "AAA", |n| n.ends_with('Z')).is_err());
+        assert!(traverse(&map, "CCC", |n| n.ends_with('Z')).is_err());
+    }
 }
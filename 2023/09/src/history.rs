@@ -1,67 +1,65 @@
-use std::str::FromStr;
+use std::{fmt::Display, str::FromStr};
+
+use common::parse::{numbers, parse, ParseError, Res};
+use nom::{character::complete::space0, combinator::map, sequence::preceded};
+
+use crate::polynomial::Polynomial;
 
 #[derive(Debug, PartialEq, Eq)]
 pub struct History {
-    pub values: Vec<i32>,
+    pub values: Vec<i64>,
 }
 
 impl History {
-    fn sequence(&self) -> Vec<Vec<i32>> {
-        let mut sequences = vec![self.values.clone()];
-
-        while let Some(l) = sequences.last() {
-            let sequence: Vec<_> = l
-                .windows(2)
-                .map(|w| w[1] - w[0])
-                .collect();
-
-            let all_zeros =  sequence.iter().all(|s| *s == 0);
-
-            sequences.push(sequence);
-
-            if all_zeros { break; }
-        }
+    pub fn polynomial(&self) -> Result<Polynomial, String> {
+        Polynomial::fit(&self.values)
+    }
 
-        sequences
+    /// The value at `index`, counting the first reading as 0.
+    pub fn predict(&self, index: i128) -> Result<i128, String> {
+        self.polynomial()?.at(index)
     }
 
-    pub fn predict_prev(&self) -> i32 {
-        let sequences = self.sequence();
+    pub fn predict_prev(&self) -> Result<i128, String> {
+        self.predict(-1)
+    }
 
-        sequences
-            .iter()
-            .rev()
-            .fold(0, |acc, s| {
-                s.first().unwrap() - acc
-            })
+    pub fn predict_next(&self) -> Result<i128, String> {
+        self.predict(self.values.len() as i128)
     }
+}
 
-    pub fn predict_next(&self) -> i32 {
-        let sequences = self.sequence();
+impl Display for History {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        for (i, v) in self.values.iter().enumerate() {
+            if i > 0 {
+                write!(f, " ")?;
+            }
+
+            write!(f, "{}", v)?;
+        }
 
-        sequences
-            .iter()
-            .rev()
-            .fold(0, |acc, s| {
-                acc + s.last().unwrap()
-            })
+        Ok(())
     }
 }
 
 impl FromStr for History {
-    type Err = String;
+    type Err = ParseError;
 
     fn from_str(s: &str) -> Result<Self, Self::Err> {
-        let values = s.split_whitespace()
-            .filter_map(|s| s.parse().ok())
-            .collect();
+        if s.trim().is_empty() {
+            return Ok(Self { values: vec![] });
+        }
 
-        Ok(Self {
-            values,
-        })
+        parse(s, parse_history)
     }
 }
 
+/// Readings separated by spaces, like `10 13 -16 21`.
+pub(crate) fn parse_history(input: &str) -> Res<'_, History> {
+    map(preceded(space0, numbers), |values| History { values })(input)
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -78,22 +76,32 @@ mod tests {
         assert_eq!(exp, history);
     }
 
+    #[test]
+    fn history_displays_as_parsed() {
+        assert_eq!("10 13 -16 21", h("10  13 -16 21").to_string());
+        assert_eq!(
+            Err("line 1, column 4: expected a number".to_string()),
+            "10 x3 16".parse::<History>().map_err(String::from)
+        );
+    }
+
     fn h(s: &str) -> History {
         s.parse().unwrap()
     }
 
     #[test]
-    fn history_sequences() {
-        let history: History = h("0 3 6 9 12 15");
-        let exp = vec![
-            vec![0, 3, 6, 9, 12, 15],
-            vec![3, 3, 3, 3, 3],
-            vec![0, 0, 0, 0],
+    fn history_predicts_next() {
+        let exps = vec![
+            (18, h("0 3 6 9 12 15")),
+            (28, h("1 3 6 10 15 21")),
+            (68, h("10 13 16 21 30 45")),
         ];
 
-        let sequences = history.sequence();
+        for (e, h) in exps.into_iter() {
+            let p = h.predict_next().unwrap();
 
-        assert_eq!(exp, sequences);
+            assert_eq!(e, p);
+        }
     }
 
     #[test]
@@ -105,9 +113,24 @@ mod tests {
         ];
 
         for (e, h) in exps.into_iter() {
-            let p = h.predict_prev();
+            let p = h.predict_prev().unwrap();
 
             assert_eq!(e, p);
         }
     }
+
+    #[test]
+    fn history_predicts_far_away() {
+        let history = h("1 3 6 10 15 21");
+
+        // triangle numbers, (n + 1)(n + 2) / 2
+        assert_eq!(Ok(500_000_500_000), history.predict(1_000_000 - 1));
+        assert_eq!(Ok(45), history.predict(-11));
+    }
+
+    #[test]
+    fn history_errors_without_zero_row() {
+        assert!(h("").predict_next().is_err());
+        assert!(h("1 2 4 8 16").predict_next().is_err());
+    }
 }
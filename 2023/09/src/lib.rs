@@ -1,22 +1,74 @@
-use history::History;
+use std::error::Error;
+
+use common::{
+    parse::{self, lines, ParseError},
+    Answer,
+};
+
+pub use history::History;
+pub use polynomial::Polynomial;
+
+use history::parse_history;
 
 mod history;
+mod polynomial;
 
-pub fn run(contents: &str) {
-    let histories: Vec<History> = contents
-        .lines()
-        .filter_map(|l| l.parse().ok())
-        .collect();
+/// The sum of every history's next value.
+#[tracing::instrument(skip_all)]
+pub fn part1(contents: &str) -> Result<Answer, Box<dyn Error>> {
+    Ok(sum_predictions(&parse(contents)?, |h| h.predict_next())?.into())
+}
 
-    let sum = histories
-        .iter()
-        .fold(0, |acc, h| acc + h.predict_next());
+/// The sum of every history's value before the first.
+#[tracing::instrument(skip_all)]
+pub fn part2(contents: &str) -> Result<Answer, Box<dyn Error>> {
+    Ok(sum_predictions(&parse(contents)?, |h| h.predict_prev())?.into())
+}
 
-    println!("solution 1 (next sum): {}", sum);
+/// The sum of every history's value at `index`.
+pub fn sum_at(contents: &str, index: i128) -> Result<Answer, Box<dyn Error>> {
+    Ok(sum_predictions(&parse(contents)?, |h| h.predict(index))?.into())
+}
 
-    let sum = histories
+pub fn parse(contents: &str) -> Result<Vec<History>, ParseError> {
+    parse::parse(contents, lines(parse_history))
+}
+
+fn sum_predictions(
+    histories: &[History],
+    predict: impl Fn(&History) -> Result<i128, String>,
+) -> Result<i128, String> {
+    histories
         .iter()
-        .fold(0, |acc, h| acc + h.predict_prev());
+        .enumerate()
+        .try_fold(0i128, |acc, (i, h)| {
+            let p = predict(h).map_err(|e| format!("history {}: {}", i + 1, e))?;
+
+            acc.checked_add(p).ok_or("sum overflows".to_string())
+        })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const SAMPLE: &str =
+r"0 3 6 9 12 15
+1 3 6 10 15 21
+10 13 16 21 30 45";
+
+    #[test]
+    fn solves_sample() {
+        assert_eq!(Answer::Int(114), part1(SAMPLE).unwrap());
+        assert_eq!(Answer::Int(2), part2(SAMPLE).unwrap());
+        assert_eq!(part1(SAMPLE).unwrap(), sum_at(SAMPLE, 6).unwrap());
+    }
+
+    #[test]
+    fn parse_reports_bad_histories() {
+        let error = part1("0 3 6\n1 x 6\n10 13 16").unwrap_err();
 
-    println!("solution 2 (prev sum): {}", sum);
+        assert_eq!("line 2, column 3: expected a number", error.to_string());
+        assert!(error.is::<ParseError>());
+    }
 }
@@ -1,9 +1,29 @@
-use std::{env, fs};
+use std::{env, error::Error, fs};
+
+fn main() -> Result<(), Box<dyn Error>> {
+    let _day = common::trace::init("mirage");
 
-fn main() {
     let args: Vec<_> = env::args().collect();
     let filename = args.get(1).expect("filename");
     let contents = fs::read_to_string(filename).expect("contents");
 
-    mirage::run(&contents);
+    let index = match args.get(2) {
+        Some(i) => Some(i.parse().map_err(|_| format!("Could not parse index '{}'", i))?),
+        None => None,
+    };
+
+    if common::answer::for_tools() {
+        common::answer::print(1, &mirage::part1(&contents)?);
+        common::answer::print(2, &mirage::part2(&contents)?);
+        return Ok(());
+    }
+
+    println!("solution 1 (next sum): {}", mirage::part1(&contents)?);
+    println!("solution 2 (prev sum): {}", mirage::part2(&contents)?);
+
+    if let Some(index) = index {
+        println!("sum at {}: {}", index, mirage::sum_at(&contents, index)?);
+    }
+
+    Ok(())
 }
@@ -0,0 +1,133 @@
+/// A polynomial in Newton's forward difference form: `f(n)` is the sum of
+/// `differences[k] * C(n, k)`, where `differences[k]` is the first value of the
+/// k-th row of the difference pyramid.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Polynomial {
+    differences: Vec<i128>,
+}
+
+impl Polynomial {
+    /// The lowest degree polynomial through `values`, where `values[i]` is
+    /// `f(i)`. The pyramid has to reach a row of zeros to be sure of the
+    /// degree, so that needs at least one value more than the degree plus one.
+    pub fn fit(values: &[i64]) -> Result<Self, String> {
+        let mut row: Vec<i128> = values.iter().map(|v| *v as i128).collect();
+        let mut differences = vec![];
+
+        while !row.is_empty() {
+            if row.iter().all(|v| *v == 0) {
+                return Ok(Self { differences });
+            }
+
+            differences.push(row[0]);
+            row = row
+                .windows(2)
+                .map(|w| w[1].checked_sub(w[0]))
+                .collect::<Option<_>>()
+                .ok_or(format!("differences of {} values overflow", values.len()))?;
+        }
+
+        Err(format!(
+            "{} values never reach a row of zero differences",
+            values.len()
+        ))
+    }
+
+    /// `None` for the zero polynomial.
+    pub fn degree(&self) -> Option<usize> {
+        self.differences.len().checked_sub(1)
+    }
+
+    /// `f(n)` for any `n`, before the first value or long after the last.
+    pub fn at(&self, n: i128) -> Result<i128, String> {
+        let overflow = || format!("f({}) overflows", n);
+
+        let mut sum: i128 = 0;
+        // C(n, k), which stays whole even for negative n
+        let mut binomial: i128 = 1;
+
+        for (k, d) in self.differences.iter().enumerate() {
+            if k > 0 {
+                // C(n, k) = C(n, k - 1) * (n - k + 1) / k, divided first so
+                // only the result has to fit
+                let k = k as i128;
+                let g = gcd(binomial, k);
+                let next = n
+                    .checked_sub(k)
+                    .and_then(|m| m.checked_add(1))
+                    .ok_or_else(overflow)?;
+
+                binomial = (binomial / g)
+                    .checked_mul(next / (k / g))
+                    .ok_or_else(overflow)?;
+            }
+
+            let term = d.checked_mul(binomial).ok_or_else(overflow)?;
+            sum = sum.checked_add(term).ok_or_else(overflow)?;
+        }
+
+        Ok(sum)
+    }
+}
+
+fn gcd(a: i128, b: i128) -> i128 {
+    let (mut a, mut b) = (a.abs(), b.abs());
+
+    while b != 0 {
+        (a, b) = (b, a % b);
+    }
+
+    a
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn polynomial_fits_degree() {
+        let exps = vec![
+            (Some(1), vec![0, 3, 6, 9, 12, 15]),
+            (Some(2), vec![1, 3, 6, 10, 15, 21]),
+            (Some(3), vec![10, 13, 16, 21, 30, 45]),
+            (Some(0), vec![7, 7]),
+            (None, vec![0, 0, 0]),
+        ];
+
+        for (e, values) in exps {
+            assert_eq!(e, Polynomial::fit(&values).unwrap().degree());
+        }
+    }
+
+    #[test]
+    fn polynomial_needs_zero_row() {
+        assert!(Polynomial::fit(&[]).is_err());
+        assert!(Polynomial::fit(&[5]).is_err());
+        assert!(Polynomial::fit(&[1, 2, 4, 8]).is_err());
+    }
+
+    #[test]
+    fn polynomial_evaluates_anywhere() {
+        // n^3 - 2n
+        let values: Vec<i64> = (0..6).map(|n| n * n * n - 2 * n).collect();
+        let p = Polynomial::fit(&values).unwrap();
+
+        assert_eq!(Ok(-4), p.at(-2));
+        assert_eq!(Ok(1_000_000_000_000 - 20_000), p.at(10_000));
+        assert_eq!(Ok(values[4] as i128), p.at(4));
+    }
+
+    #[test]
+    fn polynomial_reports_overflow() {
+        let p = Polynomial::fit(&[0, 1, 4, 9]).unwrap();
+
+        assert!(p.at(i128::MAX / 2).is_err());
+        assert!(p.at(i128::MIN).is_err());
+
+        // each row of differences doubles, until it's too big for an i128
+        let alternating: Vec<i64> = (0..70)
+            .map(|i| if i % 2 == 0 { i64::MAX } else { i64::MIN })
+            .collect();
+        assert!(Polynomial::fit(&alternating).is_err());
+    }
+}
@@ -1,4 +1,6 @@
-use std::fmt::Display;
+use std::{fmt::Display, str::FromStr};
+
+use crate::pipe::Pipe;
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub struct Point {
@@ -32,7 +34,7 @@ impl Display for Point {
     }
 }
 
-#[derive(Debug)]
+#[derive(Debug, PartialEq)]
 pub struct Grid<T> {
     array: Vec<T>,
     pub width: usize,
@@ -77,18 +79,44 @@ impl<T> Grid<T> {
     pub fn set(&mut self, value: T, p: Point) {
         self.array[p.y * self.width + p.x] = value;
     }
+}
 
-    pub fn set_all<F>(&mut self, set: F)
-    where
-        F: Fn(Point) -> T {
-        self.array
-            .iter_mut()
-            .enumerate()
-            .for_each(|(i, v)| {
-                *v = set(Point {
-                    x: i % self.width,
-                    y: i / self.width,
-                });
-            })
+impl<T: Display> Display for Grid<T> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        for y in 0..self.height {
+            for x in 0..self.width {
+                write!(f, "{}", self.get(Point::new(x, y)))?;
+            }
+
+            writeln!(f)?;
+        }
+
+        Ok(())
+    }
+}
+
+impl FromStr for Grid<Pipe> {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let width = s.lines().next().ok_or("Expected a row")?.chars().count();
+        let height = s.lines().count();
+
+        let mut array = Vec::with_capacity(width * height);
+        for (y, line) in s.lines().enumerate() {
+            if line.chars().count() != width {
+                return Err(format!("Row {} is not {} wide", y, width));
+            }
+
+            for c in line.chars() {
+                array.push(Pipe::from_char(c)?);
+            }
+        }
+
+        Ok(Self {
+            array,
+            width,
+            height,
+        })
     }
 }
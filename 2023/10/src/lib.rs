@@ -1,40 +1,35 @@
-use grid::Grid;
-use pipe::Pipe;
+use common::{visual::Sink, Answer};
 use walk::Walker;
 
+pub use grid::Grid;
+pub use pipe::Pipe;
+
 mod grid;
 mod pipe;
 mod walk;
 
-pub fn run(s: &str) {
-    let grid = build_grid(s);
-
-    let walker = Walker::new(grid).unwrap();
-
-    let (d, _p) = walker.find_furthest_point().unwrap();
+/// How far along the loop the tile furthest from the start is.
+#[tracing::instrument(skip_all)]
+pub fn part1(s: &str) -> Result<Answer, String> {
+    let (d, _p) = Walker::new(s.parse()?)?.find_furthest_point(None)?;
 
-    println!("solution 1 (furthest): {}", d);
-
-    let i = walker.get_enclosed_tiles();
-    println!("solution 2 (enclosed tiles): {}", i);
+    Ok(d.into())
 }
 
-fn build_grid(s: &str) -> Grid<Pipe> {
-    let input: Vec<Vec<_>> = s
-        .lines()
-        .map(|l| l.chars().collect())
-        .collect();
+/// How many tiles the loop encloses.
+#[tracing::instrument(skip_all)]
+pub fn part2(s: &str) -> Result<Answer, String> {
+    Ok(Walker::new(s.parse()?)?.get_enclosed_tiles(None).into())
+}
 
-    let width = input[0].len();
-    let height = input.len();
+/// Shows the walk around the loop, then the scan for enclosed tiles.
+pub fn animate(s: &str, frames: &mut dyn Sink) -> Result<(), String> {
+    let walker = Walker::new(s.parse()?)?;
 
-    let mut grid = Grid::new(width, height);
-    grid.set_all(|p| {
-        let c = input[p.y][p.x];
-        Pipe::from_char(c)
-    });
+    walker.find_furthest_point(Some(frames))?;
+    walker.get_enclosed_tiles(Some(frames));
 
-    grid
+    frames.finish().map_err(|e| e.to_string())
 }
 
 #[cfg(test)]
@@ -47,7 +42,7 @@ mod tests {
     fn grid_builds() {
         let s = "S.|-LJ7F";
 
-        let grid = build_grid(s);
+        let grid: Grid<Pipe> = s.parse().unwrap();
 
         assert_eq!(&Pipe::Start, grid.get(Point::new(0, 0)));
         assert_eq!(&Pipe::Ground, grid.get(Point::new(1, 0)));
@@ -60,4 +55,39 @@ mod tests {
         assert_eq!(&Pipe::WtoS, grid.get(Point::new(6, 0)));
         assert_eq!(&Pipe::EtoS, grid.get(Point::new(7, 0)));
     }
+
+    #[test]
+    fn grid_displays_as_parsed() {
+        let s = ".F-7.\n.|.|.\n.L-J.\n";
+        let grid: Grid<Pipe> = s.parse().unwrap();
+
+        assert_eq!(s, grid.to_string());
+        assert!("S.\n.".parse::<Grid<Pipe>>().is_err());
+        assert!("S.x".parse::<Grid<Pipe>>().is_err());
+        assert!("".parse::<Grid<Pipe>>().is_err());
+    }
+
+    #[test]
+    fn solves_samples() {
+        let furthest =
+r"..F7.
+.FJ|.
+SJ.L7
+|F--J
+LJ...";
+
+        let enclosed =
+r"...........
+.S-------7.
+.|F-----7|.
+.||.....||.
+.||.....||.
+.|L-7.F-J|.
+.|..|.|..|.
+.L--J.L--J.
+...........";
+
+        assert_eq!(Ok(Answer::Int(8)), part1(furthest));
+        assert_eq!(Ok(Answer::Int(4)), part2(enclosed));
+    }
 }
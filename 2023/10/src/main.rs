@@ -1,10 +1,25 @@
 use std::fs;
 use std::env;
 
-fn main() {
+fn main() -> Result<(), String> {
+    let _day = common::trace::init("pipes");
+
     let args: Vec<_> = env::args().collect();
     let filename = args.get(1).expect("filename");
     let contents = fs::read_to_string(filename).expect("contents");
 
-    pipes::run(&contents);
+    if let Some(mut frames) = common::visual::from_args(&args)? {
+        pipes::animate(&contents, &mut frames)?;
+    }
+
+    if common::answer::for_tools() {
+        common::answer::print(1, &pipes::part1(&contents)?);
+        common::answer::print(2, &pipes::part2(&contents)?);
+        return Ok(());
+    }
+
+    println!("solution 1 (furthest): {}", pipes::part1(&contents)?);
+    println!("solution 2 (enclosed tiles): {}", pipes::part2(&contents)?);
+
+    Ok(())
 }
@@ -1,6 +1,10 @@
-#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+use std::fmt::Display;
+
+#[allow(clippy::enum_variant_names)]
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
 pub enum Pipe {
     Start,
+    #[default]
     Ground,
 
     Pipe,
@@ -18,26 +22,55 @@ pub const PIPES_CONNECT_LEFT: [Pipe; 4] = [Pipe::Start, Pipe::Dash, Pipe::WtoN,
 pub const PIPES_CONNECT_RIGHT: [Pipe; 4] = [Pipe::Start, Pipe::Dash, Pipe::EtoS, Pipe::NtoE];
 
 impl Pipe {
-    pub fn from_char(c: char) -> Self {
+    pub fn from_char(c: char) -> Result<Self, String> {
         match c {
-            'S' => Self::Start,
-            '.' => Self::Ground,
+            'S' => Ok(Self::Start),
+            '.' => Ok(Self::Ground),
+
+            '|' => Ok(Self::Pipe),
+            '-' => Ok(Self::Dash),
+
+            'L' => Ok(Self::NtoE),
+            'J' => Ok(Self::WtoN),
+            '7' => Ok(Self::WtoS),
+            'F' => Ok(Self::EtoS),
+
+            _ => Err(format!("Not matching char: {}", c))
+        }
+    }
 
-            '|' => Self::Pipe,
-            '-' => Self::Dash,
+    pub fn to_char(self) -> char {
+        match self {
+            Self::Start => 'S',
+            Self::Ground => '.',
 
-            'L' => Self::NtoE,
-            'J' => Self::WtoN,
-            '7' => Self::WtoS,
-            'F' => Self::EtoS,
+            Self::Pipe => '|',
+            Self::Dash => '-',
 
-            _ => panic!("Not matching char: {}", c)
+            Self::NtoE => 'L',
+            Self::WtoN => 'J',
+            Self::WtoS => '7',
+            Self::EtoS => 'F',
         }
     }
 }
 
-impl Default for Pipe {
-    fn default() -> Self {
-        Self::Ground
+impl Display for Pipe {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.to_char())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn pipe_chars_round_trip() {
+        for c in "S.|-LJ7F".chars() {
+            assert_eq!(c, Pipe::from_char(c).unwrap().to_char());
+        }
+
+        assert!(Pipe::from_char('x').is_err());
     }
 }
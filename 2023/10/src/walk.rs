@@ -1,3 +1,5 @@
+use common::visual::{Colour, Frame, Sink};
+
 use crate::{grid::{Grid, Point}, pipe::{Pipe, PIPES_CONNECT_UP, PIPES_CONNECT_LEFT, PIPES_CONNECT_RIGHT, PIPES_CONNECT_DOWN}};
 
 #[derive(Debug)]
@@ -103,7 +105,7 @@ impl Walker {
         })
     }
 
-    pub fn find_furthest_point(&self) -> Result<(u32, Point), String> {
+    pub fn find_furthest_point(&self, mut frames: Option<&mut dyn Sink>) -> Result<(u32, Point), String> {
         let mut options = vec![];
 
         let s = self.start;
@@ -141,20 +143,45 @@ impl Walker {
         let mut right_prev = self.start;
         let mut right = options[1];
 
+        // only drawn when someone's watching
+        let mut frame = frames.is_some().then(|| self.frame());
+
         loop {
             let left_visited = distance_grid.get(left).is_some();
             let right_visited = distance_grid.get(right).is_some();
-            if left_visited {
-                break Ok((distance - 1, left_prev));
-            }
-            if right_visited {
-                break Ok((distance - 1, right_prev));
+            let furthest = match (left_visited, right_visited) {
+                (true, _) => Some(left_prev),
+                (_, true) => Some(right_prev),
+                _ => None,
+            };
+
+            if let Some(furthest) = furthest {
+                if let (Some(frame), Some(sink)) = (&mut frame, frames.as_deref_mut()) {
+                    frame.highlight(furthest.x, furthest.y, Colour::Red);
+                    frame.status = format!("furthest is {} along", distance - 1);
+                    sink.frame(frame);
+                }
+
+                break Ok((distance - 1, furthest));
             }
 
             distance += 1;
             *distance_grid.get_mut(left) = Some(distance);
             *distance_grid.get_mut(right) = Some(distance);
 
+            if let (Some(frame), Some(sink)) = (&mut frame, frames.as_deref_mut()) {
+                for p in [left_prev, right_prev] {
+                    frame.highlight(p.x, p.y, Colour::Cyan);
+                }
+                for p in [left, right] {
+                    frame.highlight(p.x, p.y, Colour::Yellow);
+                }
+                frame.highlight(self.start.x, self.start.y, Colour::Magenta);
+
+                frame.status = format!("{} along", distance - 1);
+                sink.frame(frame);
+            }
+
             let temp = left;
             left = self.grid.next(left_prev, left).ok_or("expected left to connect")?;
             left_prev = temp;
@@ -165,10 +192,23 @@ impl Walker {
         }
     }
 
-    pub fn get_enclosed_tiles(&self) -> i32 {
+    pub fn get_enclosed_tiles(&self, mut frames: Option<&mut dyn Sink>) -> i32 {
         // find the loop
         let path = self.get_path();
 
+        let mut frame = frames.is_some().then(|| {
+            let mut frame = self.frame();
+            for y in 0..path.height {
+                for x in 0..path.width {
+                    if *path.get(Point::new(x, y)) != Pipe::Ground {
+                        frame.highlight(x, y, Colour::Cyan);
+                    }
+                }
+            }
+
+            frame
+        });
+
         let mut inside_count = 0;
         // look at every tile
         for y in 0..self.grid.height {
@@ -181,6 +221,10 @@ impl Walker {
                     Pipe::Start => panic!("shouldn't happen"),
                     Pipe::Ground => if inside {
                         inside_count += 1;
+
+                        if let Some(frame) = &mut frame {
+                            frame.highlight(x, y, Colour::Green);
+                        }
                     },
                     Pipe::Pipe |
                     Pipe::NtoE |
@@ -190,11 +234,21 @@ impl Walker {
                     Pipe::EtoS => (),
                 }
             }
+
+            if let (Some(frame), Some(sink)) = (&mut frame, frames.as_deref_mut()) {
+                frame.status = format!("row {}, {} enclosed", y + 1, inside_count);
+                sink.frame(frame);
+            }
         }
 
         inside_count
     }
 
+    // the grid as it was drawn, with the start as the pipe under it
+    fn frame(&self) -> Frame {
+        Frame::from_text(&self.grid.to_string())
+    }
+
 
     fn get_path(&self) -> Grid<Pipe> {
         let mut options = vec![];
@@ -289,13 +343,15 @@ impl Grid<Pipe> {
 
 #[cfg(test)]
 mod tests {
-    use crate::build_grid;
-
     use super::*;
 
+    fn parse_grid(s: &str) -> Grid<Pipe> {
+        s.parse().unwrap()
+    }
+
     #[test]
     fn grid_next() {
-        let grid = build_grid(
+        let grid = parse_grid(
 r".....
 .F-7.
 .|.|.
@@ -327,7 +383,7 @@ r".....
 
     #[test]
     fn grid_connects() {
-        let grid = build_grid(
+        let grid = parse_grid(
 r".....
 .F-7.
 .|.|.
@@ -346,7 +402,7 @@ r".....
 
     #[test]
     fn grid_finds_enclosed() {
-        let grid = build_grid("...........
+        let grid = parse_grid("...........
 .S-------7.
 .|F-----7|.
 .||.....||.
@@ -358,6 +414,36 @@ r".....
 
         let walker = Walker::new(grid).unwrap();
 
-        assert_eq!(walker.get_enclosed_tiles(), 4);
+        assert_eq!(walker.get_enclosed_tiles(None), 4);
+
+        let mut frames = vec![];
+        walker.get_enclosed_tiles(Some(&mut frames));
+
+        // a frame per row, with the inside coloured in as it's found
+        assert_eq!(frames.len(), 9);
+        assert_eq!(frames[6].status, "row 7, 4 enclosed");
+        assert_eq!(frames[6].colour(2, 6), Some(Colour::Green));
+        assert_eq!(frames[6].colour(1, 6), Some(Colour::Cyan));
+        assert_eq!(frames[6].colour(5, 6), None);
+    }
+
+    #[test]
+    fn walker_draws_furthest() {
+        let grid = parse_grid(
+r"..F7.
+.FJ|.
+SJ.L7
+|F--J
+LJ...");
+
+        let walker = Walker::new(grid).unwrap();
+
+        let mut frames = vec![];
+        let (distance, furthest) = walker.find_furthest_point(Some(&mut frames)).unwrap();
+
+        assert_eq!(distance, 8);
+        assert_eq!(frames.last().unwrap().status, "furthest is 8 along");
+        assert_eq!(frames.last().unwrap().colour(furthest.x, furthest.y), Some(Colour::Red));
+        assert_eq!(frames[0].colour(0, 2), Some(Colour::Magenta));
     }
 }
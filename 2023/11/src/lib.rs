@@ -1,6 +1,6 @@
 use std::error::Error;
 
-mod grid;
+use common::Answer;
 
 #[derive(Debug, PartialEq, Eq, Clone, Copy)]
 enum Cosmos {
@@ -14,12 +14,38 @@ struct Universe {
     pub galaxy_count: u32
 }
 
-pub fn run(contents: &str) -> Result<(), Box<dyn Error>> {
-    let mut universe = get_universe(contents);
+common::params! {
+    #[derive(Debug, Clone, Copy)]
+    pub struct Growth {
+        /// how many times bigger an empty row or column gets in part 1
+        growth: usize = 2,
+        /// how many times bigger an empty row or column gets in part 2
+        part2_growth: usize = 100,
+    }
+}
+
+/// The sum of the distances between every pair of galaxies.
+#[tracing::instrument(skip_all)]
+pub fn part1(contents: &str) -> Result<Answer, Box<dyn Error>> {
+    part1_with(contents, Growth::default())
+}
 
-    expand(&mut universe)?;
+pub fn part1_with(contents: &str, growth: Growth) -> Result<Answer, Box<dyn Error>> {
+    let galaxies = expand(&get_universe(contents), growth.growth)?;
 
-    todo!()
+    Ok(distances(&galaxies)?.into())
+}
+
+/// The same sum, once the universe is much older.
+#[tracing::instrument(skip_all)]
+pub fn part2(contents: &str) -> Result<Answer, Box<dyn Error>> {
+    part2_with(contents, Growth::default())
+}
+
+pub fn part2_with(contents: &str, growth: Growth) -> Result<Answer, Box<dyn Error>> {
+    let galaxies = expand(&get_universe(contents), growth.part2_growth)?;
+
+    Ok(distances(&galaxies)?.into())
 }
 
 fn get_universe(s: &str) -> Universe {
@@ -41,97 +67,108 @@ fn get_universe(s: &str) -> Universe {
     }
 }
 
-fn expand(u: &mut Universe) -> Result<(), Box<dyn Error>> {
-    let clone = u.cosmos.clone();
-
-    let mut adjustments = 0;
-    for (y, row) in clone.iter().enumerate() {
-        let is_empty = row.iter().all(|c| c == &Cosmos::Space);
-        if is_empty {
-            u.cosmos.insert(y + adjustments, vec![Cosmos::Space; row.len()]);
-            adjustments += 1;
+/// Where every galaxy ends up once each empty row and column is `growth`
+/// times as big. Only the galaxies move, so any growth costs the same.
+fn expand(u: &Universe, growth: usize) -> Result<Vec<(u64, u64)>, Box<dyn Error>> {
+    if growth == 0 {
+        return Err("growth should be at least 1".into());
+    }
+    let growth = u64::try_from(growth)?;
+
+    let mut galaxies = Vec::with_capacity(u.galaxy_count as usize);
+    for (y, row) in u.cosmos.iter().enumerate() {
+        for (x, c) in row.iter().enumerate() {
+            if c == &Cosmos::Galaxy {
+                galaxies.push((x, y));
+            }
         }
     }
 
-    // Need to do this to get updated values
-    let clone = u.cosmos.clone();
-    let first_row = clone.get(0).ok_or("expected first line")?;
-    let cols: Vec<Vec<_>> = first_row
+    let width = u.cosmos.iter().map(|row| row.len()).max().unwrap_or(0);
+    let mut full_cols = vec![false; width];
+    let mut full_rows = vec![false; u.cosmos.len()];
+    for (x, y) in galaxies.iter() {
+        full_cols[*x] = true;
+        full_rows[*y] = true;
+    }
+
+    let xs = stretch(&full_cols, growth)?;
+    let ys = stretch(&full_rows, growth)?;
+
+    Ok(galaxies
         .iter()
-        .enumerate()
-        .map(|(x, _)| clone
-            .iter()
-            .map(|row| row[x])
-            .collect())
-        .collect();
-        
-    let mut adjustments = 0;
-    for (x, col) in cols.iter().enumerate() {
-        let is_empty = col.iter().all(|c| c == &Cosmos::Space);
-        if is_empty {
-            (0..clone.len()).for_each(|row_index| {
-                u.cosmos[row_index].insert(x + adjustments, Cosmos::Space);
-            });
-            adjustments += 1;
-        }
+        .map(|(x, y)| (xs[*x], ys[*y]))
+        .collect())
+}
+
+/// Where each row (or column) starts once the empty ones are `growth` big.
+fn stretch(full: &[bool], growth: u64) -> Result<Vec<u64>, String> {
+    let mut at = 0u64;
+    let mut starts = Vec::with_capacity(full.len());
+
+    for is_full in full.iter() {
+        starts.push(at);
+        at = at
+            .checked_add(if *is_full { 1 } else { growth })
+            .ok_or("the universe grows past u64::MAX")?;
     }
 
-    Ok(())
+    Ok(starts)
 }
 
-fn format_image(a: &[Vec<Cosmos>]) -> String {
-    let mut s = String::new();
-    for row in a.iter() {
-        for c in row.iter() {
-            let c = match c {
-                Cosmos::Space => '.',
-                Cosmos::Galaxy => '#',
-            };
-            s.push(c);
+/// Sums the steps between every pair of galaxies, moving only up, down, left
+/// and right.
+fn distances(galaxies: &[(u64, u64)]) -> Result<u64, String> {
+    let mut sum = 0u64;
+
+    for (i, a) in galaxies.iter().enumerate() {
+        for b in galaxies[i + 1..].iter() {
+            sum = a.0.abs_diff(b.0)
+                .checked_add(a.1.abs_diff(b.1))
+                .and_then(|d| sum.checked_add(d))
+                .ok_or("the distances add up to more than fit in a u64")?;
         }
-
-        s.push('\n');
     }
-    s
+
+    Ok(sum)
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
 
+    const SAMPLE: &str = r"...#......
+.......#..
+#.........
+..........
+......#...
+.#........
+.........#
+..........
+.......#..
+#...#.....";
+
+    // growing by 1 leaves every galaxy where it is
+    fn galaxies(s: &str) -> Vec<(u64, u64)> {
+        expand(&get_universe(s), 1).unwrap()
+    }
+
     #[test]
     fn fix_simple() {
-        let mut u = get_universe(r"..
+        let u = get_universe(r"..
 .#");
-        let exp = get_universe(r"...
+        let exp = galaxies(r"...
 ...
 ..#");
 
-        // println!("Before");
-        // println!("{}", format_image(&array));
-
-        expand(&mut u).unwrap();
-
-        // println!("\nAfter");
-        // println!("{}", format_image(&array));
-
-        assert_eq!(u, exp);
+        assert_eq!(expand(&u, 2).unwrap(), exp);
     }
 
     #[test]
     fn fix_sample() {
-        let mut universe = get_universe(r"...#......
-.......#..
-#.........
-..........
-......#...
-.#........
-.........#
-..........
-.......#..
-#...#.....");
+        let universe = get_universe(SAMPLE);
 
-        let exp = get_universe(r"....#........
+        let exp = galaxies(r"....#........
 .........#...
 #............
 .............
@@ -144,14 +181,39 @@ mod tests {
 .........#...
 #....#.......");
 
-        // println!("Before");
-        // println!("{}", format_image(&array));
+        assert_eq!(expand(&universe, 2).unwrap(), exp);
+    }
 
-        expand(&mut universe).unwrap();
+    #[test]
+    fn fix_growing() {
+        let u = get_universe(r"..
+.#");
+        let exp = galaxies(r".....
+.....
+.....
+.....
+....#");
+
+        assert_eq!(expand(&u, 4).unwrap(), exp);
+        assert_eq!(expand(&u, 1).unwrap(), vec![(1, 1)]);
+        assert!(expand(&u, 0).is_err());
+    }
+
+    #[test]
+    fn sample_distances() {
+        let at = |growth| Growth { growth, ..Growth::default() };
 
-        // println!("\nAfter");
-        // println!("{}", format_image(&array));
+        assert_eq!(Answer::from(374u64), part1_with(SAMPLE, at(2)).unwrap());
+        assert_eq!(Answer::from(1030u64), part1_with(SAMPLE, at(10)).unwrap());
+        assert_eq!(Answer::from(8410u64), part2(SAMPLE).unwrap());
+    }
+
+    #[test]
+    fn distances_overflow() {
+        let u = get_universe(r"#..#");
 
-        assert_eq!(universe, exp);
+        assert_eq!(Ok(5), distances(&expand(&u, 2).unwrap()));
+        assert!(expand(&u, usize::MAX).is_err());
+        assert!(distances(&[(0, 0), (u64::MAX, 1)]).is_err());
     }
 }
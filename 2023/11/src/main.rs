@@ -1,11 +1,23 @@
-use std::{env, fs, error::Error};
+use std::{env, fs, error::Error, path::Path};
+
+use cosmic::Growth;
 
 fn main() -> Result<(), Box<dyn Error>> {
-    let args: Vec<_> = env::args().collect();
+    let _day = common::trace::init("cosmic");
+
+    let args: Vec<String> = env::args().collect();
+    let (growth, args) = common::params::load::<Growth>(Path::new(env!("CARGO_MANIFEST_DIR")), &args)?;
     let filename = args.get(1).expect("Filename");
     let contents = fs::read_to_string(filename).expect("file to be there");
 
-    cosmic::run(&contents)?;
+    if common::answer::for_tools() {
+        common::answer::print(1, &cosmic::part1_with(&contents, growth)?);
+        common::answer::print(2, &cosmic::part2_with(&contents, growth)?);
+        return Ok(());
+    }
+
+    println!("solution 1: {}", cosmic::part1_with(&contents, growth)?);
+    println!("solution 2: {}", cosmic::part2_with(&contents, growth)?);
 
     Ok(())
 }
@@ -1,62 +1,135 @@
-use std::{
-    collections::{HashMap, HashSet},
-    error::Error,
-    iter::zip,
-};
+use std::{error::Error, fmt::Display, num::ParseIntError};
 
-pub fn run(contents: &str) -> Result<(), Box<dyn Error>> {
-    let (mut list0, mut list1) = parse(contents)?;
+use common::Answer;
+use metrics::PairMetrics;
 
-    list0.sort();
-    list1.sort();
+pub mod metrics;
 
-    let distance: i32 = zip(list0.iter(), list1.iter())
-        .map(|(num0, num1)| (num0 - num1).abs())
-        .sum();
+/// Distance between the first two lists.
+#[tracing::instrument(skip_all)]
+pub fn part1(contents: &str) -> Result<Answer, Box<dyn Error>> {
+    let lists = parse(contents);
+    let (left, right) = lists.first_pair()?;
 
-    println!("distance: {}", distance);
+    Ok(metrics::distance(left, right).into())
+}
+
+/// Similarity of the first two lists.
+#[tracing::instrument(skip_all)]
+pub fn part2(contents: &str) -> Result<Answer, Box<dyn Error>> {
+    let lists = parse(contents);
+    let (left, right) = lists.first_pair()?;
+
+    Ok(metrics::similarity(left, right)?.into())
+}
 
-    let freq0 = freq_map(list0);
-    let freq1 = freq_map(list1);
+/// The columns of a location list file, plus every line that was skipped.
+#[derive(Debug, Default, PartialEq)]
+pub struct Lists {
+    pub columns: Vec<Vec<i32>>,
+    pub warnings: Vec<Warning>,
+}
 
-    let mut similarity = 0;
-    for (num, freq) in freq0.iter() {
-        if let Some(other_freq) = freq1.get(num) {
-            similarity += *num as usize * freq * other_freq;
+impl Lists {
+    /// The two lists the puzzle compares.
+    pub fn first_pair(&self) -> Result<(&[i32], &[i32]), String> {
+        match self.columns.as_slice() {
+            [left, right, ..] => Ok((left, right)),
+            _ => Err(format!("expected two lists, found {}", self.columns.len())),
         }
     }
 
-    println!("similarity: {}", similarity);
+    /// Metrics for every pair of columns, in column order.
+    pub fn pairwise(&self) -> Result<Vec<PairMetrics>, String> {
+        let mut metrics = vec![];
+
+        for i in 0..self.columns.len() {
+            for j in (i + 1)..self.columns.len() {
+                metrics.push(PairMetrics::new(i, j, &self.columns[i], &self.columns[j])?);
+            }
+        }
+
+        Ok(metrics)
+    }
+}
 
-    Ok(())
+#[derive(Debug, PartialEq)]
+pub struct Warning {
+    /// 1-based line number in the input
+    pub line: usize,
+    pub text: String,
+    pub kind: WarningKind,
 }
 
-fn parse(contents: &str) -> Result<(Vec<i32>, Vec<i32>), Box<dyn Error>> {
-    let mut list0: Vec<i32> = vec![];
-    let mut list1: Vec<i32> = vec![];
+#[derive(Debug, PartialEq)]
+pub enum WarningKind {
+    ColumnCount { expected: usize, found: usize },
+    InvalidNumber(ParseIntError),
+}
 
-    for line in contents.lines() {
-        let numbers: Vec<&str> = line.split_whitespace().collect();
+impl Display for Warning {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "line {}: could not parse '{}', ", self.line, self.text)?;
 
-        if numbers.len() != 2 {
-            println!("Could not parse line: '{}', skipping", line);
-            continue;
+        match &self.kind {
+            WarningKind::ColumnCount { expected, found } => {
+                write!(f, "expected {} columns, found {}", expected, found)?
+            }
+            WarningKind::InvalidNumber(e) => write!(f, "{}", e)?,
         }
 
-        list0.push(numbers[0].parse()?);
-        list1.push(numbers[1].parse()?);
+        write!(f, ", skipping")
     }
-
-    Ok((list0, list1))
 }
 
-fn freq_map(list: Vec<i32>) -> HashMap<i32, usize> {
-    let mut m: HashMap<i32, usize> = HashMap::new();
-    for el in list {
-        *m.entry(el).or_default() += 1;
+/// Splits the input into columns. The first non-blank line decides how many
+/// columns there are; any line that disagrees or holds something other than
+/// numbers is skipped and reported as a warning.
+pub fn parse(contents: &str) -> Lists {
+    let mut lists = Lists::default();
+    let mut width = None;
+
+    for (i, line) in contents.lines().enumerate() {
+        let numbers: Vec<&str> = line.split_whitespace().collect();
+
+        if numbers.is_empty() {
+            continue;
+        }
+
+        let warning = |kind| Warning {
+            line: i + 1,
+            text: line.to_string(),
+            kind,
+        };
+
+        let expected = *width.get_or_insert(numbers.len());
+        if numbers.len() != expected {
+            lists.warnings.push(warning(WarningKind::ColumnCount {
+                expected,
+                found: numbers.len(),
+            }));
+            continue;
+        }
+
+        let row = match numbers
+            .iter()
+            .map(|n| n.parse())
+            .collect::<Result<Vec<i32>, _>>()
+        {
+            Ok(row) => row,
+            Err(e) => {
+                lists.warnings.push(warning(WarningKind::InvalidNumber(e)));
+                continue;
+            }
+        };
+
+        lists.columns.resize_with(expected, Vec::new);
+        for (column, n) in lists.columns.iter_mut().zip(row) {
+            column.push(n);
+        }
     }
 
-    return m;
+    lists
 }
 
 #[cfg(test)]
@@ -72,24 +145,52 @@ mod parse_tests {
 3   9
 3   3";
 
-        let result = parse(input);
-        let (list1, list2) = result.expect("to succeed");
+        let lists = parse(input);
 
-        assert_eq!(list1, [3, 4, 2, 1, 3, 3]);
-        assert_eq!(list2, [4, 3, 5, 3, 9, 3]);
-    }
-}
+        assert_eq!(lists.columns[0], [3, 4, 2, 1, 3, 3]);
+        assert_eq!(lists.columns[1], [4, 3, 5, 3, 9, 3]);
+        assert!(lists.warnings.is_empty());
 
-#[test]
-fn freq_map_succeeds() {
-    let list = vec![4, 1, 4, 2, 2, 4, 3, 3, 3, 4];
+        assert_eq!(part1(input).unwrap(), Answer::Int(11));
+        assert_eq!(part2(input).unwrap(), Answer::Int(31));
+        assert!(part1("1\n2").is_err());
+    }
 
-    let map = freq_map(list);
+    #[test]
+    fn parses_n_columns() {
+        let lists = parse("1 2 3\n4 5 6");
+
+        assert_eq!(lists.columns, vec![vec![1, 4], vec![2, 5], vec![3, 6]]);
+
+        let pairs: Vec<_> = lists
+            .pairwise()
+            .unwrap()
+            .iter()
+            .map(|m| (m.left, m.right))
+            .collect();
+        assert_eq!(pairs, vec![(0, 1), (0, 2), (1, 2)]);
+    }
 
-    let mut expected = HashMap::new();
-    expected.insert(1, 1);
-    expected.insert(2, 2);
-    expected.insert(3, 3);
-    expected.insert(4, 4);
-    assert_eq!(map, expected)
+    #[test]
+    fn warns_on_malformed_lines() {
+        let lists = parse("3   4\n4\n\n2   x\n1   3");
+
+        assert_eq!(lists.columns, vec![vec![3, 1], vec![4, 3]]);
+        assert_eq!(lists.warnings.len(), 2);
+
+        let warning = &lists.warnings[0];
+        assert_eq!(warning.line, 2);
+        assert_eq!(warning.text, "4");
+        assert_eq!(
+            warning.kind,
+            WarningKind::ColumnCount {
+                expected: 2,
+                found: 1
+            }
+        );
+
+        let warning = &lists.warnings[1];
+        assert_eq!(warning.line, 4);
+        assert!(matches!(warning.kind, WarningKind::InvalidNumber(_)));
+    }
 }
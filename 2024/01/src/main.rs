@@ -1,11 +1,40 @@
 use std::{env, error::Error, fs};
 
 fn main() -> Result<(), Box<dyn Error>> {
+    let _day = common::trace::init("hysteria");
+
     let args: Vec<_> = env::args().collect();
     let filename = args.get(1).expect("Filename");
     let contents = fs::read_to_string(filename).expect("file to be there");
 
-    hysteria::run(&contents)?;
+    let lists = hysteria::parse(&contents);
+
+    for warning in lists.warnings.iter() {
+        eprintln!("{}", warning);
+    }
+
+    if common::answer::for_tools() {
+        common::answer::print(1, &hysteria::part1(&contents)?);
+        common::answer::print(2, &hysteria::part2(&contents)?);
+        return Ok(());
+    }
+
+    for m in lists.pairwise()? {
+        if lists.columns.len() > 2 {
+            println!("columns {} and {}:", m.left, m.right);
+        }
+
+        println!("distance: {}", m.distance);
+        println!("similarity: {}", m.similarity);
+        println!("squared distance: {}", m.squared_distance);
+        match m.rank_correlation {
+            Some(r) => println!("rank correlation: {:.4}", r),
+            None => println!("rank correlation: undefined"),
+        }
+        println!("in both: {}", m.intersection.len());
+        println!("only on the left: {}", m.left_only.len());
+        println!("only on the right: {}", m.right_only.len());
+    }
 
     Ok(())
 }
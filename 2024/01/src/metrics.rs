@@ -0,0 +1,224 @@
+use std::{
+    collections::{BTreeSet, HashMap},
+    iter::zip,
+};
+
+/// Every metric we know how to compute between two location lists.
+#[derive(Debug, PartialEq)]
+pub struct PairMetrics {
+    pub left: usize,
+    pub right: usize,
+    pub distance: i64,
+    pub similarity: i128,
+    pub squared_distance: i128,
+    pub rank_correlation: Option<f64>,
+    pub intersection: BTreeSet<i32>,
+    pub left_only: BTreeSet<i32>,
+    pub right_only: BTreeSet<i32>,
+}
+
+impl PairMetrics {
+    pub fn new(left: usize, right: usize, a: &[i32], b: &[i32]) -> Result<Self, String> {
+        Ok(Self {
+            left,
+            right,
+            distance: distance(a, b),
+            similarity: similarity(a, b)?,
+            squared_distance: squared_distance(a, b),
+            rank_correlation: rank_correlation(a, b),
+            intersection: intersection(a, b),
+            left_only: difference(a, b),
+            right_only: difference(b, a),
+        })
+    }
+}
+
+/// Sum of the distances between the lists once both are sorted.
+pub fn distance(a: &[i32], b: &[i32]) -> i64 {
+    let (a, b) = (sorted(a), sorted(b));
+
+    zip(a, b).map(|(x, y)| (x as i64 - y as i64).abs()).sum()
+}
+
+/// Every number in `a` weighted by how often it shows up in `b`.
+pub fn similarity(a: &[i32], b: &[i32]) -> Result<i128, String> {
+    let freq_a = freq_map(a);
+    let freq_b = freq_map(b);
+
+    let mut similarity: i128 = 0;
+    for (num, freq) in freq_a.iter() {
+        if let Some(other_freq) = freq_b.get(num) {
+            similarity = (*num as i128)
+                .checked_mul(*freq as i128)
+                .and_then(|s| s.checked_mul(*other_freq as i128))
+                .and_then(|s| s.checked_add(similarity))
+                .ok_or("similarity is too big to add up")?;
+        }
+    }
+
+    Ok(similarity)
+}
+
+/// Minimum total squared distance over every way of pairing the lists up.
+///
+/// The cost is convex, so pairing the sorted lists is already optimal and no
+/// general assignment solver is needed.
+pub fn squared_distance(a: &[i32], b: &[i32]) -> i128 {
+    let (a, b) = (sorted(a), sorted(b));
+
+    // a difference needs 33 bits, so its square can't fit in an i64
+    zip(a, b).map(|(x, y)| (x as i128 - y as i128).pow(2)).sum()
+}
+
+/// Spearman's rank correlation between the lists, row by row.
+///
+/// Ties get the average of the ranks they span. Returns `None` when there
+/// are fewer than two rows or either list is constant.
+pub fn rank_correlation(a: &[i32], b: &[i32]) -> Option<f64> {
+    let n = a.len().min(b.len());
+    if n < 2 {
+        return None;
+    }
+
+    let ranks_a = ranks(&a[..n]);
+    let ranks_b = ranks(&b[..n]);
+
+    let mean = (n as f64 + 1.0) / 2.0;
+    let mut covariance = 0.0;
+    let mut variance_a = 0.0;
+    let mut variance_b = 0.0;
+    for (ra, rb) in zip(ranks_a, ranks_b) {
+        covariance += (ra - mean) * (rb - mean);
+        variance_a += (ra - mean).powi(2);
+        variance_b += (rb - mean).powi(2);
+    }
+
+    if variance_a == 0.0 || variance_b == 0.0 {
+        return None;
+    }
+
+    Some(covariance / (variance_a * variance_b).sqrt())
+}
+
+/// Numbers that show up in both lists.
+pub fn intersection(a: &[i32], b: &[i32]) -> BTreeSet<i32> {
+    let b: BTreeSet<_> = b.iter().copied().collect();
+
+    a.iter().copied().filter(|n| b.contains(n)).collect()
+}
+
+/// Numbers in `a` that never show up in `b`.
+pub fn difference(a: &[i32], b: &[i32]) -> BTreeSet<i32> {
+    let b: BTreeSet<_> = b.iter().copied().collect();
+
+    a.iter().copied().filter(|n| !b.contains(n)).collect()
+}
+
+fn sorted(list: &[i32]) -> Vec<i32> {
+    let mut list = list.to_vec();
+    list.sort();
+    list
+}
+
+fn ranks(list: &[i32]) -> Vec<f64> {
+    let mut order: Vec<usize> = (0..list.len()).collect();
+    order.sort_by_key(|&i| list[i]);
+
+    let mut ranks = vec![0.0; list.len()];
+    let mut start = 0;
+    while start < order.len() {
+        let mut end = start;
+        while end + 1 < order.len() && list[order[end + 1]] == list[order[start]] {
+            end += 1;
+        }
+
+        // ranks are 1-based, ties share the average of their span
+        let rank = (start + end) as f64 / 2.0 + 1.0;
+        for &i in &order[start..=end] {
+            ranks[i] = rank;
+        }
+
+        start = end + 1;
+    }
+
+    ranks
+}
+
+fn freq_map(list: &[i32]) -> HashMap<i32, usize> {
+    let mut m: HashMap<i32, usize> = HashMap::new();
+    for el in list {
+        *m.entry(*el).or_default() += 1;
+    }
+
+    m
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const LEFT: [i32; 6] = [3, 4, 2, 1, 3, 3];
+    const RIGHT: [i32; 6] = [4, 3, 5, 3, 9, 3];
+
+    #[test]
+    fn freq_map_succeeds() {
+        let list = vec![4, 1, 4, 2, 2, 4, 3, 3, 3, 4];
+
+        let map = freq_map(&list);
+
+        let mut expected = HashMap::new();
+        expected.insert(1, 1);
+        expected.insert(2, 2);
+        expected.insert(3, 3);
+        expected.insert(4, 4);
+        assert_eq!(map, expected)
+    }
+
+    #[test]
+    fn sample_distance_and_similarity() {
+        assert_eq!(distance(&LEFT, &RIGHT), 11);
+        assert_eq!(similarity(&LEFT, &RIGHT), Ok(31));
+    }
+
+    #[test]
+    fn metrics_take_extreme_numbers() {
+        let (min, max) = ([i32::MIN, -1], [i32::MAX, -1]);
+
+        assert_eq!(
+            squared_distance(&min[..1], &max[..1]),
+            (u32::MAX as i128).pow(2)
+        );
+        assert_eq!(similarity(&min, &min), Ok(i32::MIN as i128 - 1));
+        assert_eq!(similarity(&max, &min), Ok(-1));
+        assert_eq!(similarity(&[-3, -3], &[-3]), Ok(-6));
+    }
+
+    #[test]
+    fn squared_distance_pairs_sorted() {
+        // sorted: 1 2 3 3 3 4 / 3 3 3 4 5 9, differences 2 1 0 1 2 5
+        assert_eq!(squared_distance(&LEFT, &RIGHT), 35);
+    }
+
+    #[test]
+    fn ranks_average_ties() {
+        assert_eq!(ranks(&[10, 20, 20, 5]), vec![2.0, 3.5, 3.5, 1.0]);
+    }
+
+    #[test]
+    fn rank_correlation_bounds() {
+        let up = [1, 2, 3, 4];
+        let down = [8, 6, 4, 2];
+
+        assert_eq!(rank_correlation(&up, &up), Some(1.0));
+        assert_eq!(rank_correlation(&up, &down), Some(-1.0));
+        assert_eq!(rank_correlation(&up, &[7, 7, 7, 7]), None);
+        assert_eq!(rank_correlation(&[1], &[1]), None);
+    }
+
+    #[test]
+    fn sets_compare() {
+        assert_eq!(intersection(&LEFT, &RIGHT), BTreeSet::from([3, 4]));
+        assert_eq!(difference(&LEFT, &RIGHT), BTreeSet::from([1, 2]));
+        assert_eq!(difference(&RIGHT, &LEFT), BTreeSet::from([5, 9]));
+    }
+}
@@ -1,38 +1,66 @@
 use std::error::Error;
 
+use common::Answer;
 use itertools::Itertools;
-use report::Report;
+
+pub use report::Report;
+
 mod report;
 
-pub fn run(contents: &str) -> Result<(), Box<dyn Error>> {
-    let reports = parse(contents)?;
+common::params! {
+    /// How far apart neighbouring levels can be in a safe report.
+    #[derive(Debug, Clone, Copy)]
+    pub struct Steps {
+        /// the smallest safe difference between levels
+        min_step: i32 = 1,
+        /// the largest safe difference between levels
+        max_step: i32 = 3,
+    }
+}
 
-    let safe_reports = reports.iter().filter(|r| check_safety(&r.levels)).count();
+/// How many reports are safe.
+#[tracing::instrument(skip_all)]
+pub fn part1(contents: &str) -> Result<Answer, Box<dyn Error>> {
+    part1_with(contents, &Steps::default())
+}
 
-    println!("safe reports: {}/{}", safe_reports, reports.len());
+/// How many reports are safe with the given steps.
+pub fn part1_with(contents: &str, steps: &Steps) -> Result<Answer, Box<dyn Error>> {
+    let reports = parse(contents)?;
 
-    let tolerant_safe_reports = reports
+    Ok(reports
         .iter()
-        .filter(|r| check_safety_tolerant(&r.levels))
-        .count();
+        .filter(|r| check_safety(&r.levels, steps))
+        .count()
+        .into())
+}
 
-    println!(
-        "safe reports with fault tolerance: {}/{}",
-        tolerant_safe_reports,
-        reports.len()
-    );
+/// How many reports are safe once one bad level can be dropped.
+#[tracing::instrument(skip_all)]
+pub fn part2(contents: &str) -> Result<Answer, Box<dyn Error>> {
+    part2_with(contents, &Steps::default())
+}
 
-    Ok(())
+/// How many reports are safe with the given steps once one bad level can be
+/// dropped.
+pub fn part2_with(contents: &str, steps: &Steps) -> Result<Answer, Box<dyn Error>> {
+    let reports = parse(contents)?;
+
+    Ok(reports
+        .iter()
+        .filter(|r| check_safety_tolerant(&r.levels, steps))
+        .count()
+        .into())
 }
 
-fn parse(contents: &str) -> Result<Vec<Report>, Box<dyn Error>> {
+pub fn parse(contents: &str) -> Result<Vec<Report>, Box<dyn Error>> {
     Ok(contents
         .lines()
         .map(|l| l.parse())
         .collect::<Result<Vec<Report>, _>>()?)
 }
 
-fn check_safety(levels: &Vec<i32>) -> bool {
+fn check_safety(levels: &[i32], steps: &Steps) -> bool {
     // true is increasing, false is decreasing
     let mut direction: Option<bool> = None;
 
@@ -51,7 +79,7 @@ fn check_safety(levels: &Vec<i32>) -> bool {
             direction = Some(dir);
         }
 
-        if diff < 1 || diff > 3 {
+        if !(steps.min_step..=steps.max_step).contains(&diff) {
             return false;
         }
     }
@@ -59,17 +87,77 @@ fn check_safety(levels: &Vec<i32>) -> bool {
     true
 }
 
-fn check_safety_tolerant(levels: &Vec<i32>) -> bool {
+fn check_safety_tolerant(levels: &[i32], steps: &Steps) -> bool {
     levels
         .iter()
         .copied()
-        .combinations(levels.len() - 1)
-        .any(|c| check_safety(&c))
+        .combinations(levels.len().saturating_sub(1))
+        .any(|c| check_safety(&c, steps))
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use proptest::prelude::*;
+
+    fn levels() -> impl Strategy<Value = Vec<i32>> {
+        prop::collection::vec(0..20, 0..8)
+    }
+
+    // a report that steps 1 to 3 in one direction the whole way
+    fn safe_levels() -> impl Strategy<Value = Vec<i32>> {
+        (
+            0..100i32,
+            prop::collection::vec(1..=3i32, 0..8),
+            any::<bool>(),
+        )
+            .prop_map(|(start, steps, up)| {
+                let sign = if up { 1 } else { -1 };
+                let mut levels = vec![start];
+                for step in steps {
+                    levels.push(levels[levels.len() - 1] + sign * step);
+                }
+
+                levels
+            })
+    }
+
+    proptest! {
+        #[test]
+        fn tolerant_contains_safe(levels in levels()) {
+            if check_safety(&levels, &Steps::default()) {
+                prop_assert!(check_safety_tolerant(&levels, &Steps::default()));
+            }
+        }
+
+        #[test]
+        fn tolerant_matches_removing_each_level(levels in levels()) {
+            let by_removal = levels.is_empty()
+                || (0..levels.len()).any(|i| {
+                    let mut removed = levels.clone();
+                    removed.remove(i);
+                    check_safety(&removed, &Steps::default())
+                });
+
+            prop_assert_eq!(check_safety_tolerant(&levels, &Steps::default()), by_removal);
+        }
+
+        #[test]
+        fn generated_reports_are_safe(levels in safe_levels()) {
+            prop_assert!(check_safety(&levels, &Steps::default()));
+
+            let reversed: Vec<_> = levels.iter().rev().copied().collect();
+            prop_assert!(check_safety(&reversed, &Steps::default()));
+        }
+
+        #[test]
+        fn reports_parse_back(levels in levels()) {
+            let line: Vec<_> = levels.iter().map(|l| l.to_string()).collect();
+            let report: Report = line.join(" ").parse().unwrap();
+
+            prop_assert_eq!(report.levels, levels);
+        }
+    }
 
     #[test]
     fn report_safety() {
@@ -85,7 +173,7 @@ mod tests {
         for (input, is_safe) in cases {
             println!("input: {}, expected safety: {}", input, is_safe);
             let report: Report = input.parse().unwrap();
-            assert_eq!(is_safe, check_safety(&report.levels));
+            assert_eq!(is_safe, check_safety(&report.levels, &Steps::default()));
         }
     }
 
@@ -103,7 +191,24 @@ mod tests {
         for (input, is_safe) in cases {
             println!("input: {}, expected safety: {}", input, is_safe);
             let report: Report = input.parse().unwrap();
-            assert_eq!(is_safe, check_safety_tolerant(&report.levels));
+            assert_eq!(
+                is_safe,
+                check_safety_tolerant(&report.levels, &Steps::default())
+            );
         }
     }
+
+    #[test]
+    fn solves_sample() {
+        let sample = "7 6 4 2 1\n1 2 7 8 9\n9 7 6 2 1\n1 3 2 4 5\n8 6 4 4 1\n1 3 6 7 9";
+
+        assert_eq!(part1(sample).unwrap(), Answer::Int(2));
+        assert_eq!(part2(sample).unwrap(), Answer::Int(4));
+
+        let wider = Steps {
+            max_step: 5,
+            ..Steps::default()
+        };
+        assert_eq!(part1_with(sample, &wider).unwrap(), Answer::Int(4));
+    }
 }
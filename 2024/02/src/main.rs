@@ -1,11 +1,34 @@
-use std::{env, error::Error, fs};
+use std::{env, error::Error, fs, path::Path};
+
+use reports::Steps;
 
 fn main() -> Result<(), Box<dyn Error>> {
-    let args: Vec<_> = env::args().collect();
+    let _day = common::trace::init("reports");
+
+    let args: Vec<String> = env::args().collect();
+    let (steps, args) =
+        common::params::load::<Steps>(Path::new(env!("CARGO_MANIFEST_DIR")), &args)?;
     let filename = args.get(1).expect("Filename");
     let contents = fs::read_to_string(filename).expect("file to be there");
 
-    reports::run(&contents)?;
+    if common::answer::for_tools() {
+        common::answer::print(1, &reports::part1_with(&contents, &steps)?);
+        common::answer::print(2, &reports::part2_with(&contents, &steps)?);
+        return Ok(());
+    }
+
+    let total = reports::parse(&contents)?.len();
+
+    println!(
+        "safe reports: {}/{}",
+        reports::part1_with(&contents, &steps)?,
+        total
+    );
+    println!(
+        "safe reports with fault tolerance: {}/{}",
+        reports::part2_with(&contents, &steps)?,
+        total
+    );
 
     Ok(())
 }
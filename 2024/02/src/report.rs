@@ -1,4 +1,4 @@
-use std::{num::ParseIntError, str::FromStr};
+use std::{fmt::Display, num::ParseIntError, str::FromStr};
 
 #[derive(Debug, PartialEq, Eq)]
 pub struct Report {
@@ -18,6 +18,14 @@ impl FromStr for Report {
     }
 }
 
+impl Display for Report {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let levels: Vec<_> = self.levels.iter().map(|l| l.to_string()).collect();
+
+        write!(f, "{}", levels.join(" "))
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -33,4 +41,11 @@ mod tests {
 
         assert_eq!(report, expected);
     }
+
+    #[test]
+    fn report_displays_as_parsed() {
+        let report: Report = "7  6 4 2 1".parse().unwrap();
+
+        assert_eq!(report.to_string(), "7 6 4 2 1");
+    }
 }
@@ -1,11 +1,12 @@
 use std::{error::Error, num::ParseIntError};
 
+use common::Answer;
 use regex::Regex;
 
-pub fn run(contents: &str) -> Result<(), Box<dyn Error>> {
-    let instructions = parse(&contents)?;
-
-    let sum: i32 = instructions
+/// The sum of every multiplication.
+#[tracing::instrument(skip_all)]
+pub fn part1(contents: &str) -> Result<Answer, Box<dyn Error>> {
+    let sum: i32 = parse(contents)?
         .iter()
         .filter_map(|i| match i {
             Instruction::Multiply(one, two) => Some(one * two),
@@ -13,11 +14,15 @@ pub fn run(contents: &str) -> Result<(), Box<dyn Error>> {
         })
         .sum();
 
-    println!("sum: {}", sum);
+    Ok(sum.into())
+}
 
+/// The sum of the multiplications that aren't switched off by a `don't()`.
+#[tracing::instrument(skip_all)]
+pub fn part2(contents: &str) -> Result<Answer, Box<dyn Error>> {
     let mut enabled = true;
     let mut sum = 0;
-    for i in instructions {
+    for i in parse(contents)? {
         match i {
             Instruction::Enable => enabled = true,
             Instruction::Disable => enabled = false,
@@ -30,9 +35,7 @@ pub fn run(contents: &str) -> Result<(), Box<dyn Error>> {
         }
     }
 
-    println!("sum with toggles: {}", sum);
-
-    Ok(())
+    Ok(sum.into())
 }
 
 fn parse(contents: &str) -> Result<Vec<Instruction>, ParseIntError> {
@@ -46,7 +49,7 @@ fn parse(contents: &str) -> Result<Vec<Instruction>, ParseIntError> {
                 .chars()
                 .take_while(|&c| c != '(')
                 .collect::<String>();
-            println!("extraction: {}, ins: {}", extraction, ins);
+            tracing::trace!(extraction, ins, "extracted");
             match ins.as_str() {
                 "do" => Ok(Instruction::Enable),
                 "don't" => Ok(Instruction::Disable),
@@ -86,8 +89,10 @@ mod tests {
             Instruction::Multiply(8, 5),
         ];
 
-        let parsed = parse(&input).unwrap();
+        let parsed = parse(input).unwrap();
 
         assert_eq!(expectation, parsed);
+        assert_eq!(part1(input).unwrap(), Answer::Int(161));
+        assert_eq!(part2(input).unwrap(), Answer::Int(48));
     }
 }
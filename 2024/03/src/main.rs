@@ -1,11 +1,20 @@
 use std::{env, error::Error, fs};
 
 fn main() -> Result<(), Box<dyn Error>> {
+    let _day = common::trace::init("mull");
+
     let args: Vec<_> = env::args().collect();
     let filename = args.get(1).expect("should provide filename");
     let contents = fs::read_to_string(filename).unwrap();
 
-    mull::run(&contents)?;
+    if common::answer::for_tools() {
+        common::answer::print(1, &mull::part1(&contents)?);
+        common::answer::print(2, &mull::part2(&contents)?);
+        return Ok(());
+    }
+
+    println!("sum: {}", mull::part1(&contents)?);
+    println!("sum with toggles: {}", mull::part2(&contents)?);
 
     Ok(())
 }
@@ -1,19 +1,22 @@
-use std::{fmt::Debug, str::FromStr};
+use std::{
+    fmt::{Debug, Display},
+    str::FromStr,
+};
 
 use crate::{pattern::Pattern, point::Point, vector::Vector};
 
 const DIRECTIONS: [Vector; 8] = [
     Vector { x: -1, y: -1 },
-    Vector { x: 00, y: -1 },
-    Vector { x: 01, y: -1 },
-    Vector { x: 01, y: 00 },
-    Vector { x: 01, y: 01 },
-    Vector { x: 00, y: 01 },
-    Vector { x: -1, y: 01 },
-    Vector { x: -1, y: 00 },
+    Vector { x: 0, y: -1 },
+    Vector { x: 1, y: -1 },
+    Vector { x: 1, y: 0 },
+    Vector { x: 1, y: 1 },
+    Vector { x: 0, y: 1 },
+    Vector { x: -1, y: 1 },
+    Vector { x: -1, y: 0 },
 ];
 
-#[derive(Debug)]
+#[derive(Debug, PartialEq)]
 pub struct Grid<T> {
     array: Vec<T>,
     pub width: usize,
@@ -45,6 +48,20 @@ impl FromStr for Grid<char> {
     }
 }
 
+impl Display for Grid<char> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        for y in 0..self.height {
+            for x in 0..self.width {
+                write!(f, "{}", self.array[y * self.width + x])?;
+            }
+
+            writeln!(f)?;
+        }
+
+        Ok(())
+    }
+}
+
 impl<T> Grid<T>
 where
     T: PartialEq + Debug,
@@ -57,12 +74,12 @@ where
         }
     }
 
-    fn get(self: &Self, point: &Point) -> Option<&T> {
+    fn get(&self, point: &Point) -> Option<&T> {
         self.array.get(point.y * self.width + point.x)
     }
 
-    fn find_target_in_direction(
-        self: &Self,
+    pub fn find_target_in_direction(
+        &self,
         target: &T,
         p_start: &Point,
         direction: &Vector,
@@ -82,7 +99,7 @@ where
     }
 
     fn get_in_direction(
-        self: &Self,
+        &self,
         p_start: &Point,
         v: &Vector,
         max_length: usize,
@@ -103,8 +120,8 @@ where
         Some(points)
     }
 
-    pub fn search(self: &Self, query: &[T]) -> Option<Vec<Vec<(Point, &T)>>> {
-        let q_start = query.get(0)?;
+    pub fn search(&self, query: &[T]) -> Option<Vec<Vec<(Point, &T)>>> {
+        let q_start = query.first()?;
         let q_length = query.len();
 
         // for every letter
@@ -125,7 +142,7 @@ where
 
             let mut sequences: Vec<_> = DIRECTIONS
                 .iter()
-                .filter_map(|d| self.get_in_direction(&current_point, &d, q_length))
+                .filter_map(|d| self.get_in_direction(&current_point, d, q_length))
                 .map(|s| {
                     s.iter()
                         .filter_map(|p| self.get(p).map(|v| (p.clone(), v)))
@@ -146,7 +163,7 @@ where
 }
 
 impl Grid<char> {
-    pub fn find(self: &Self, pattern: &Pattern) -> Option<Vec<usize>> {
+    pub fn find(&self, pattern: &Pattern) -> Option<Vec<usize>> {
         let (offset, char) = pattern.get_start()?;
 
         let positions: Vec<_> = self
@@ -198,7 +215,7 @@ impl Grid<char> {
             }
         }
 
-        if matches.len() > 0 {
+        if !matches.is_empty() {
             Some(matches)
         } else {
             None
@@ -242,6 +259,14 @@ XMAS.S
         );
     }
 
+    #[test]
+    fn grid_displays_as_parsed() {
+        let s = "XMAS\n.A..\nS..X\n";
+        let grid: Grid<char> = s.parse().unwrap();
+
+        assert_eq!(grid.to_string(), s);
+    }
+
     #[test]
     fn grid_finds_target_in_direction() {
         let grid = Grid {
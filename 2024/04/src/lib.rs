@@ -1,19 +1,40 @@
-use grid::Grid;
-use pattern::Pattern;
 use std::error::Error;
 
+use common::{
+    parse::{parse, sections},
+    Answer,
+};
+
+pub use grid::Grid;
+pub use pattern::Pattern;
+
+use pattern::parse_pattern;
+
 mod grid;
 mod pattern;
 mod point;
 mod vector;
 
-pub fn run(contents: &str, search_text: &str) -> Result<(), Box<dyn Error>> {
+// the patterns each part searches for
+pub const PART_1_PATTERNS: &str = include_str!("../pattern1.txt");
+pub const PART_2_PATTERNS: &str = include_str!("../pattern2.txt");
+
+#[tracing::instrument(skip_all)]
+pub fn part1(contents: &str) -> Result<Answer, Box<dyn Error>> {
+    Ok(occurrences(contents, PART_1_PATTERNS)?.into())
+}
+
+#[tracing::instrument(skip_all)]
+pub fn part2(contents: &str) -> Result<Answer, Box<dyn Error>> {
+    Ok(occurrences(contents, PART_2_PATTERNS)?.into())
+}
+
+/// How many times any of the blank line separated patterns in `search_text`
+/// turns up in the grid.
+pub fn occurrences(contents: &str, search_text: &str) -> Result<usize, Box<dyn Error>> {
     let grid: Grid<char> = contents.parse()?;
 
-    let patterns: Vec<_> = search_text
-        .split("\n\n")
-        .filter_map(|s| s.parse::<Pattern>().ok())
-        .collect();
+    let patterns = parse(search_text, sections(parse_pattern))?;
 
     let matches: Vec<_> = patterns
         .iter()
@@ -27,7 +48,25 @@ pub fn run(contents: &str, search_text: &str) -> Result<(), Box<dyn Error>> {
 
     let what: Vec<_> = matches.into_iter().flat_map(|(_, is)| is).collect();
 
-    println!("total occurrences: {}", what.len());
+    Ok(what.len())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const SAMPLE: &str = include_str!("../sample_1.txt");
+
+    #[test]
+    fn solves_sample() {
+        assert_eq!(part1(SAMPLE).unwrap(), Answer::Int(18));
+        assert_eq!(part2(SAMPLE).unwrap(), Answer::Int(9));
+    }
+
+    #[test]
+    fn bad_patterns_are_errors() {
+        let error = occurrences(SAMPLE, "XMAS\n\nS M").unwrap_err();
 
-    Ok(())
+        assert_eq!(error.to_string(), "line 3, column 3: expected nothing more");
+    }
 }
@@ -1,13 +1,31 @@
 use std::{env, error::Error, fs};
 
 fn main() -> Result<(), Box<dyn Error>> {
+    let _day = common::trace::init("search");
+
     let args: Vec<_> = env::args().collect();
     let filename = args.get(1).expect("should provide a filename");
-    let pattern_file = args.get(2).expect("should provide a pattern file");
     let contents = fs::read_to_string(filename)?;
-    let pattern_contents = fs::read_to_string(pattern_file)?;
 
-    search::run(&contents, &pattern_contents)?;
+    if common::answer::for_tools() {
+        common::answer::print(1, &search::part1(&contents)?);
+        common::answer::print(2, &search::part2(&contents)?);
+        return Ok(());
+    }
+
+    // a pattern file searches for its patterns instead of the puzzle's
+    match args.get(2) {
+        Some(pattern_file) => {
+            let pattern_contents = fs::read_to_string(pattern_file)?;
+            let total = search::occurrences(&contents, &pattern_contents)?;
+
+            println!("total occurrences: {}", total);
+        }
+        None => {
+            println!("part 1 occurrences: {}", search::part1(&contents)?);
+            println!("part 2 occurrences: {}", search::part2(&contents)?);
+        }
+    }
 
     Ok(())
 }
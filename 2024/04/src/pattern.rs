@@ -1,4 +1,11 @@
-use std::str::FromStr;
+use std::{fmt::Display, str::FromStr};
+
+use common::parse::{lines, parse, ParseError, Res};
+use nom::{
+    bytes::complete::take_while1,
+    combinator::{map, verify},
+    error::context,
+};
 
 #[derive(Debug, PartialEq, Eq)]
 pub enum Char {
@@ -14,7 +21,16 @@ fn char_to_char(c: char) -> Char {
     }
 }
 
-#[derive(Debug)]
+impl Display for Char {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Char::Match(c) => write!(f, "{}", c),
+            Char::Wildcard => write!(f, "."),
+        }
+    }
+}
+
+#[derive(Debug, PartialEq)]
 pub struct Pattern {
     array: Vec<Char>,
     pub width: usize,
@@ -39,11 +55,15 @@ impl PartialEq<[char]> for Pattern {
 }
 
 impl Pattern {
-    pub fn len(self: &Self) -> usize {
+    pub fn len(&self) -> usize {
         self.array.len()
     }
 
-    pub fn get_start(self: &Self) -> Option<(usize, char)> {
+    pub fn is_empty(&self) -> bool {
+        self.array.is_empty()
+    }
+
+    pub fn get_start(&self) -> Option<(usize, char)> {
         self.array.iter().enumerate().find_map(|(i, c)| {
             if let Char::Match(c) = c {
                 Some((i, *c))
@@ -54,25 +74,49 @@ impl Pattern {
     }
 }
 
-impl FromStr for Pattern {
-    type Err = String;
-
-    fn from_str(s: &str) -> Result<Self, Self::Err> {
-        let height = s.lines().count();
-        let width = s.lines().next().ok_or("empty string")?.len();
-
-        let array: Vec<_> = s.lines().flat_map(|l| l.chars()).collect();
+impl Display for Pattern {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        for row in self.array.chunks(self.width) {
+            for c in row {
+                write!(f, "{}", c)?;
+            }
 
-        if array.len() != width * height {
-            return Err("string was not a grid".to_string());
+            writeln!(f)?;
         }
 
-        let array = array.into_iter().map(char_to_char).collect();
+        Ok(())
+    }
+}
+
+impl FromStr for Pattern {
+    type Err = ParseError;
 
-        Ok(Self { width, array })
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        parse(s, parse_pattern)
     }
 }
 
+/// Rows of the same width, with `.` matching anything. The grid being
+/// searched can't hold whitespace, so patterns can't either.
+pub(crate) fn parse_pattern(input: &str) -> Res<'_, Pattern> {
+    let row = take_while1(|c: char| !c.is_whitespace());
+    let rows = verify(lines(row), |rows: &Vec<&str>| {
+        let width = rows[0].chars().count();
+        rows.iter().all(|r| r.chars().count() == width)
+    });
+
+    let pattern = map(rows, |rows| Pattern {
+        width: rows[0].chars().count(),
+        array: rows
+            .iter()
+            .flat_map(|r| r.chars())
+            .map(char_to_char)
+            .collect(),
+    });
+
+    context("rows of the same width", pattern)(input)
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -104,6 +148,21 @@ M.S"
         }
     }
 
+    #[test]
+    fn pattern_displays_as_parsed() {
+        let s = "M.S\n.A.\nM.S\n";
+        let p: Pattern = s.parse().unwrap();
+
+        assert_eq!(p.to_string(), s);
+        assert!("M.S\n.A\n".parse::<Pattern>().is_err());
+        assert!("M S".parse::<Pattern>().is_err());
+        assert!("\n".parse::<Pattern>().is_err());
+        assert_eq!(
+            "M.S\n.A\n".parse::<Pattern>().unwrap_err().to_string(),
+            "line 1, column 1: expected rows of the same width"
+        );
+    }
+
     #[test]
     fn pattern_gets_start_character() {
         let p: Pattern = "...
@@ -11,7 +11,7 @@ impl Point {
         Self { x, y }
     }
 
-    pub fn transform(self: &Self, v: &Vector) -> Result<Self, &'static str> {
+    pub fn transform(&self, v: &Vector) -> Result<Self, &'static str> {
         let x = self.x as i32 + v.x;
         let y = self.y as i32 + v.y;
 
@@ -1,77 +1,113 @@
-use std::{cmp::Ordering, error::Error, str::FromStr};
+use std::{cmp::Ordering, error::Error, fmt::Display, str::FromStr};
 
-mod rule;
+use common::{
+    graph::{topological_sort, Cycle, Graph},
+    parse::{self, blank_line, lines, list_of, number, pair_of, ParseError, Res},
+    Answer,
+};
+use nom::{combinator::map, error::context, sequence::separated_pair};
 
-pub fn run(contents: &str) -> Result<(), Box<dyn Error>> {
-    let (rules, updates) = contents
-        .split_once("\n\n")
-        .ok_or("expected double newline")?;
+pub mod rule;
 
-    let rules = rules
-        .lines()
-        .map(|l| l.parse())
-        .collect::<Result<Vec<Rule>, _>>()?;
+pub type Update = Vec<usize>;
 
-    let mut updates: Vec<Vec<usize>> = updates
-        .lines()
-        .map(|l| {
-            l.split(",")
-                .map(|n| n.parse())
-                .collect::<Result<Vec<usize>, _>>()
-        })
-        .collect::<Result<Vec<Vec<usize>>, _>>()?;
+/// The sum of the middle pages of updates already in order.
+#[tracing::instrument(skip_all)]
+pub fn part1(contents: &str) -> Result<Answer, Box<dyn Error>> {
+    let (rules, updates) = parse(contents)?;
 
     let mut ordered_middle_sum = 0;
 
-    for update in updates.iter_mut() {
-        println!("checking update: {:?}", update);
+    for update in updates.iter() {
+        tracing::trace!(?update, "checking update");
 
-        let mut violations: Vec<Violation> = vec![];
-        for rule in rules.iter() {
-            if let Some(violation) = rule.evaluate(&update) {
-                violations.push(violation);
-            }
-        }
-
-        if violations.len() == 0 {
+        if is_ordered(&rules, update) {
             ordered_middle_sum += update[(update.len() - 1) / 2];
         }
     }
 
-    let order = |a: &usize, b: &usize| -> Ordering {
-        if rules.contains(&Rule {
-            left: *a,
-            right: *b,
-        }) {
-            Ordering::Less
-        } else if rules.contains(&Rule {
-            left: *b,
-            right: *a,
-        }) {
-            Ordering::Greater
-        } else {
-            Ordering::Equal
-        }
-    };
+    Ok(ordered_middle_sum.into())
+}
+
+/// The sum of the middle pages of updates out of order, once they're put in
+/// order.
+#[tracing::instrument(skip_all)]
+pub fn part2(contents: &str) -> Result<Answer, Box<dyn Error>> {
+    let (rules, updates) = parse(contents)?;
 
     let mut sum = 0;
-    for update in updates.iter_mut() {
-        if !update.is_sorted_by(|a, b| order(a, b) != Ordering::Greater) {
-            update.sort_by(order);
+    for update in updates.iter() {
+        if !is_ordered(&rules, update) {
+            let update = reorder(&rules, update)?;
             sum += update[update.len() / 2];
         }
     }
 
-    println!("ordered sum: {}", ordered_middle_sum);
-    println!("sum: {}", sum);
+    Ok(sum.into())
+}
+
+/// The pages of `update` in an order that follows every rule between them.
+pub fn reorder(rules: &[Rule], update: &[usize]) -> Result<Update, Cycle<usize>> {
+    let pages = Pages { rules, update };
 
-    Ok(())
+    topological_sort(&pages, update.iter().copied())
+}
+
+// pages of an update, each leading to those the rules say go after it
+struct Pages<'a> {
+    rules: &'a [Rule],
+    update: &'a [usize],
+}
+
+impl Graph for Pages<'_> {
+    type Node = usize;
+
+    fn neighbours(&self, page: &usize) -> impl IntoIterator<Item = usize> {
+        self.rules
+            .iter()
+            .filter(move |r| r.left == *page && self.update.contains(&r.right))
+            .map(|r| r.right)
+    }
+}
+
+pub fn parse(contents: &str) -> Result<(Vec<Rule>, Vec<Update>), ParseError> {
+    let update = context("an update", list_of(number, ','));
+
+    parse::parse(
+        contents,
+        separated_pair(lines(parse_rule), blank_line, lines(update)),
+    )
+}
+
+pub fn violations(rules: &[Rule], update: &[usize]) -> Vec<Violation> {
+    rules.iter().filter_map(|r| r.evaluate(update)).collect()
+}
+
+pub fn is_ordered(rules: &[Rule], update: &[usize]) -> bool {
+    violations(rules, update).is_empty()
+}
+
+/// Pages with a rule between them go in the rule's order, the rest are equal.
+pub fn order(rules: &[Rule], a: &usize, b: &usize) -> Ordering {
+    if rules.contains(&Rule {
+        left: *a,
+        right: *b,
+    }) {
+        Ordering::Less
+    } else if rules.contains(&Rule {
+        left: *b,
+        right: *a,
+    }) {
+        Ordering::Greater
+    } else {
+        Ordering::Equal
+    }
 }
 
 #[derive(Debug, PartialEq, Eq)]
-struct Rule {
-    left: usize,
-    right: usize,
+pub struct Rule {
+    pub left: usize,
+    pub right: usize,
 }
 
 impl Rule {
@@ -98,20 +134,154 @@ impl Rule {
 }
 
 impl FromStr for Rule {
-    type Err = Box<dyn Error>;
+    type Err = ParseError;
 
     fn from_str(s: &str) -> Result<Self, Self::Err> {
-        let (left, right) = s.split_once("|").ok_or("could not fine a '|'")?;
+        parse::parse(s, parse_rule)
+    }
+}
+
+/// `47|53`
+fn parse_rule(input: &str) -> Res<'_, Rule> {
+    let rule = pair_of(number, '|', number);
+
+    context("a rule", map(rule, |(left, right)| Rule { left, right }))(input)
+}
 
-        Ok(Self {
-            left: left.parse()?,
-            right: right.parse()?,
-        })
+impl Display for Rule {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}|{}", self.left, self.right)
     }
 }
 
-#[derive(Debug)]
-struct Violation {
-    left_index: usize,
-    right_index: usize,
+#[derive(Debug, PartialEq, Eq)]
+pub struct Violation {
+    pub left_index: usize,
+    pub right_index: usize,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use proptest::prelude::*;
+    use rule::RuleSet;
+
+    const SAMPLE: &str = "47|53
+97|13
+97|61
+97|47
+75|29
+61|13
+75|53
+29|13
+97|29
+53|29
+61|53
+97|53
+61|29
+47|13
+75|47
+97|75
+47|61
+75|61
+47|29
+75|13
+53|13
+
+75,47,61,53,29
+97,61,53,29,13
+75,29,13
+75,97,47,61,53
+61,13,29
+97,13,75,29,47";
+
+    #[test]
+    fn rules_find_violations() {
+        let (rules, updates) = parse(SAMPLE).unwrap();
+
+        let ordered: Vec<_> = updates.iter().map(|u| is_ordered(&rules, u)).collect();
+        assert_eq!(ordered, vec![true, true, true, false, false, false]);
+
+        assert_eq!(
+            violations(&rules, &updates[3]),
+            vec![Violation {
+                left_index: 1,
+                right_index: 0
+            }]
+        );
+    }
+
+    #[test]
+    fn solves_sample() {
+        assert_eq!(part1(SAMPLE).unwrap(), Answer::Int(143));
+        assert_eq!(part2(SAMPLE).unwrap(), Answer::Int(123));
+    }
+
+    #[test]
+    fn rule_displays_as_parsed() {
+        let rule: Rule = "47|53".parse().unwrap();
+
+        assert_eq!(rule.to_string(), "47|53");
+    }
+
+    #[test]
+    fn parse_says_where_it_failed() {
+        let error = parse("47|53\n97|\n\n75,47").unwrap_err();
+        assert_eq!(
+            error.to_string(),
+            "line 2, column 4: expected a number in a rule"
+        );
+
+        let error = parse("47|53\n\n75,47\n97,,13").unwrap_err();
+        assert_eq!(
+            error.to_string(),
+            "line 4, column 4: expected a number in an update"
+        );
+    }
+
+    fn rules_text(rules: &[(usize, usize)]) -> String {
+        let lines: Vec<_> = rules.iter().map(|(l, r)| format!("{}|{}", l, r)).collect();
+        lines.join("\n")
+    }
+
+    // a random page order with a rule for every pair, and an update shuffled from it
+    fn total_order() -> impl Strategy<Value = (Vec<usize>, Vec<usize>)> {
+        prop::collection::hash_set(10..100usize, 1..12)
+            .prop_map(|pages| pages.into_iter().collect::<Vec<_>>())
+            .prop_flat_map(|order| {
+                let shuffled = Just(order.clone()).prop_shuffle();
+                (Just(order), shuffled)
+            })
+    }
+
+    proptest! {
+        #[test]
+        fn rule_engines_agree(
+            rules in prop::collection::vec((0..8usize, 0..8usize), 0..12),
+            update in prop::collection::vec(0..8usize, 0..8),
+        ) {
+            let text = rules_text(&rules);
+            let rules: Vec<Rule> = text.lines().map(|l| l.parse().unwrap()).collect();
+            let rule_set: RuleSet = text.parse().unwrap();
+
+            prop_assert_eq!(is_ordered(&rules, &update), rule_set.accepts(&update));
+        }
+
+        #[test]
+        fn sorting_recovers_total_order((expected, mut update) in total_order()) {
+            let pairs: Vec<_> = expected
+                .iter()
+                .enumerate()
+                .flat_map(|(i, a)| expected[(i + 1)..].iter().map(move |b| (*a, *b)))
+                .collect();
+            let rules: Vec<Rule> = rules_text(&pairs).lines().map(|l| l.parse().unwrap()).collect();
+
+            prop_assert_eq!(is_ordered(&rules, &update), update == expected);
+            prop_assert_eq!(&reorder(&rules, &update).unwrap(), &expected);
+
+            update.sort_by(|a, b| order(&rules, a, b));
+            prop_assert!(is_ordered(&rules, &update));
+            prop_assert_eq!(update, expected);
+        }
+    }
 }
@@ -1,9 +1,18 @@
 use std::{env, fs};
 
 fn main() {
+    let _day = common::trace::init("print");
+
     let args: Vec<_> = env::args().collect();
     let filename = args.get(1).expect("should provide filename");
     let contents = fs::read_to_string(filename).unwrap();
 
-    print::run(&contents).unwrap();
+    if common::answer::for_tools() {
+        common::answer::print(1, &print::part1(&contents).unwrap());
+        common::answer::print(2, &print::part2(&contents).unwrap());
+        return;
+    }
+
+    println!("ordered sum: {}", print::part1(&contents).unwrap());
+    println!("sum: {}", print::part2(&contents).unwrap());
 }
@@ -1,4 +1,7 @@
-use std::{error::Error, str::FromStr};
+use std::{fmt::Display, str::FromStr};
+
+use common::parse::{lines, number, pair_of, parse, ParseError};
+use nom::combinator::map;
 
 #[derive(Debug, PartialEq, Eq, Clone)]
 enum Rule {
@@ -8,7 +11,7 @@ enum Rule {
     Failed,
 }
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, PartialEq, Eq)]
 pub struct RuleSet {
     rules: Vec<Rule>,
 }
@@ -18,6 +21,7 @@ impl RuleSet {
         self.rules.iter().all(|r| !matches!(r, Rule::Failed))
     }
 
+    #[allow(clippy::needless_return)]
     pub fn evaluate(&mut self, value: usize) -> bool {
         for rule in self.rules.iter_mut() {
             match rule {
@@ -41,25 +45,52 @@ impl RuleSet {
 
         return false;
     }
+
+    /// Whether `update` breaks none of the rules, without touching this set.
+    pub fn accepts(&self, update: &[usize]) -> bool {
+        let mut rules = self.clone();
+
+        for value in update {
+            if rules.evaluate(*value) {
+                return false;
+            }
+        }
+
+        rules.is_valid()
+    }
 }
 
 impl FromStr for RuleSet {
-    type Err = Box<dyn Error>;
+    type Err = ParseError;
 
     fn from_str(s: &str) -> Result<Self, Self::Err> {
-        let rules = s
-            .lines()
-            .map(|l| -> Result<Rule, Box<dyn Error>> {
-                let (left, right) = l.split_once("|").ok_or("could not fine a '|'")?;
-                Ok(Rule::Pending(left.parse()?, right.parse()?))
-            })
-            .collect::<Result<Vec<Rule>, _>>()?;
+        if s.trim().is_empty() {
+            return Ok(Self { rules: vec![] });
+        }
+
+        let rule = pair_of(number, '|', number);
+        let rules = parse(s, lines(map(rule, |(l, r)| Rule::Pending(l, r))))?;
 
         Ok(Self { rules })
     }
 }
 
+/// The rules still pending, one `left|right` per line as the puzzle writes
+/// them. Rules already decided by `evaluate` are left out.
+impl Display for RuleSet {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        for rule in self.rules.iter() {
+            if let Rule::Pending(left, right) = rule {
+                writeln!(f, "{}|{}", left, right)?;
+            }
+        }
+
+        Ok(())
+    }
+}
+
 #[cfg(test)]
+#[allow(clippy::get_first)]
 mod tests {
     use super::*;
 
@@ -83,6 +114,16 @@ mod tests {
         assert_eq!(rule, &Rule::Pending(56, 78));
     }
 
+    #[test]
+    fn ruleset_prints_pending_rules() {
+        let mut rules: RuleSet = "12|34\n56|78".parse().unwrap();
+        assert_eq!(rules.to_string(), "12|34\n56|78\n");
+        assert_eq!(rules.to_string().parse::<RuleSet>().unwrap(), rules);
+
+        rules.evaluate(12);
+        assert_eq!(rules.to_string(), "56|78\n");
+    }
+
     #[test]
     fn ruleset_evaluation_passes() {
         let mut rules: RuleSet = "12|34\n56|78".parse().unwrap();
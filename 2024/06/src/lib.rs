@@ -0,0 +1,368 @@
+use std::{collections::HashSet, error::Error};
+
+use common::{
+    visual::{Colour, Frame, Sink},
+    Answer,
+};
+
+type Point = (i32, i32);
+type Map = Vec<Vec<char>>;
+
+/// How many positions the guard visits before walking off the map.
+#[tracing::instrument(skip_all)]
+pub fn part1(input: &str) -> Result<Answer, Box<dyn Error>> {
+    let grid = parse(input);
+    let guard = get_guard_position(&grid).ok_or("should find a guard")?;
+
+    let path = sim(&grid, &guard, None).map_err(|_| "guard should walk off the map")?;
+    let unique_positions = path.into_iter().collect::<HashSet<_>>();
+
+    Ok(unique_positions.len().into())
+}
+
+/// How many places a new obstacle would trap the guard in a loop.
+#[tracing::instrument(skip_all)]
+pub fn part2(input: &str) -> Result<Answer, Box<dyn Error>> {
+    let mut grid = parse(input);
+    let guard = get_guard_position(&grid).ok_or("should find a guard")?;
+
+    let mut infinite_loop_positions = vec![];
+    for y in 0..grid.len() {
+        for x in 0..grid[0].len() {
+            if grid[y][x] == '#' || grid[y][x] == '^' {
+                continue;
+            }
+
+            grid[y][x] = '#';
+            if sim(&grid, &guard, None).is_err() {
+                infinite_loop_positions.push((x, y));
+            }
+            grid[y][x] = '.';
+        }
+    }
+
+    Ok(infinite_loop_positions.len().into())
+}
+
+/// Shows the guard's walk a step at a time.
+pub fn animate(input: &str, frames: &mut dyn Sink) -> Result<(), Box<dyn Error>> {
+    let grid = parse(input);
+    let guard = get_guard_position(&grid).ok_or("should find a guard")?;
+
+    // a loop is still worth watching
+    let _ = sim(&grid, &guard, Some(frames));
+
+    Ok(frames.finish()?)
+}
+
+fn parse(input: &str) -> Map {
+    input
+        .lines()
+        .map(|l| l.chars().collect::<Vec<_>>())
+        .collect()
+}
+
+fn get_guard_position(map: &Map) -> Option<Point> {
+    map.iter()
+        .enumerate()
+        .flat_map(|(y, row)| row.iter().enumerate().map(move |(x, c)| (x, y, c)))
+        .find(|(_, _, c)| **c == '^')
+        .map(|(x, y, _)| (x as i32, y as i32))
+}
+
+#[derive(Debug, PartialEq, Eq)]
+enum SimError {
+    InfiniteLoop,
+}
+fn sim(
+    map: &Map,
+    start: &Point,
+    mut frames: Option<&mut dyn Sink>,
+) -> Result<Vec<Point>, SimError> {
+    let (mut x, mut y) = start;
+    let width = map[0].len() as i32;
+    let height = map.len() as i32;
+
+    let directions = [[0, -1], [1, 0], [0, 1], [-1, 0]];
+    let mut direction_index = 0;
+    let mut path = vec![(x, y)];
+
+    let mut visited_vector = HashSet::new();
+
+    // only drawn when someone's watching
+    let mut frame = frames.is_some().then(|| {
+        let text: Vec<String> = map.iter().map(|row| row.iter().collect()).collect();
+        Frame::from_text(&text.join("\n"))
+    });
+    let mut visited = HashSet::from([*start]);
+
+    loop {
+        let [dx, dy] = directions[direction_index];
+        (x, y) = (x + dx, y + dy);
+
+        let traversed_x_bounds = x < 0 || x >= width;
+        let traversed_y_bounds = y < 0 || y >= height;
+
+        if traversed_x_bounds || traversed_y_bounds {
+            if let (Some(frame), Some(sink)) = (&mut frame, frames.as_deref_mut()) {
+                leave(frame, map, (x - dx, y - dy));
+                frame.status = format!("walked off, {} visited", visited.len());
+                sink.frame(frame);
+            }
+
+            break;
+        }
+
+        let c = map[y as usize][x as usize];
+
+        if c == '#' {
+            (x, y) = (x - dx, y - dy);
+            direction_index = (direction_index + 1) % directions.len();
+
+            if let (Some(frame), Some(sink)) = (&mut frame, frames.as_deref_mut()) {
+                arrive(frame, (x, y), direction_index);
+                sink.frame(frame);
+            }
+
+            continue;
+        }
+
+        if !visited_vector.insert((x, y, dx, dy)) {
+            if let (Some(frame), Some(sink)) = (&mut frame, frames.as_deref_mut()) {
+                frame.status = format!("stuck in a loop after {} steps", path.len() - 1);
+                sink.frame(frame);
+            }
+
+            return Err(SimError::InfiniteLoop);
+        }
+
+        path.push((x, y));
+
+        if let (Some(frame), Some(sink)) = (&mut frame, frames.as_deref_mut()) {
+            visited.insert((x, y));
+            leave(frame, map, (x - dx, y - dy));
+            arrive(frame, (x, y), direction_index);
+            frame.status = format!("step {}, {} visited", path.len() - 1, visited.len());
+            sink.frame(frame);
+        }
+    }
+
+    Ok(path)
+}
+
+// puts back what was under the guard, marked as visited
+fn leave(frame: &mut Frame, map: &Map, (x, y): Point) {
+    let (x, y) = (x as usize, y as usize);
+    let under = match map[y][x] {
+        '^' => '.',
+        c => c,
+    };
+    frame.set(x, y, under);
+    frame.highlight(x, y, Colour::Yellow);
+}
+
+fn arrive(frame: &mut Frame, (x, y): Point, direction_index: usize) {
+    let (x, y) = (x as usize, y as usize);
+    frame.set(x, y, ['^', '>', 'v', '<'][direction_index]);
+    frame.highlight(x, y, Colour::Red);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn solves_sample() {
+        let input = include_str!("../sample_1.txt");
+
+        assert_eq!(part1(input).unwrap(), Answer::Int(41));
+        assert_eq!(part2(input).unwrap(), Answer::Int(6));
+    }
+
+    #[test]
+    fn finds_guard_position() {
+        let map = parse(
+            "....#.....
+.........#
+..........
+..#.......
+.......#..
+..........
+.#..^.....
+........#.
+#.........
+......#...
+",
+        );
+
+        let (x, y) = get_guard_position(&map).unwrap();
+
+        assert_eq!(x, 4);
+        assert_eq!(y, 6);
+    }
+
+    #[test]
+    fn sims() {
+        let map = parse(
+            ".#..
+...#
+.^..
+....",
+        );
+
+        let expected = vec![(1, 2), (1, 1), (2, 1), (2, 2), (2, 3)];
+
+        let path = sim(&map, &(1, 2), None).unwrap();
+
+        assert_eq!(path, expected);
+
+        let map = parse(
+            ".#..
+...#
+#...
+.^#.",
+        );
+
+        let err = sim(&map, &(1, 2), None).unwrap_err();
+
+        assert_eq!(err, SimError::InfiniteLoop)
+    }
+
+    #[test]
+    fn sim_infinite_loops() {
+        // Option 1
+        let map = parse(
+            "....#.....
+.........#
+..........
+..#.......
+.......#..
+..........
+.#.#^.....
+........#.
+#.........
+......#...",
+        );
+
+        let guard = get_guard_position(&map).unwrap();
+        let err = sim(&map, &guard, None).unwrap_err();
+        assert_eq!(err, SimError::InfiniteLoop);
+
+        // Option 2
+        let map = parse(
+            "....#.....
+.........#
+..........
+..#.......
+.......#..
+..........
+.#..^.....
+......#.#.
+#.........
+......#...",
+        );
+
+        let guard = get_guard_position(&map).unwrap();
+        let err = sim(&map, &guard, None).unwrap_err();
+        assert_eq!(err, SimError::InfiniteLoop);
+
+        // Option 3
+        let map = parse(
+            "....#.....
+.........#
+..........
+..#.......
+.......#..
+..........
+.#..^.....
+.......##.
+#.........
+......#...",
+        );
+
+        let guard = get_guard_position(&map).unwrap();
+        let err = sim(&map, &guard, None).unwrap_err();
+        assert_eq!(err, SimError::InfiniteLoop);
+
+        // Option 4
+        let map = parse(
+            "....#.....
+.........#
+..........
+..#.......
+.......#..
+..........
+.#..^.....
+........#.
+##........
+......#...",
+        );
+
+        let guard = get_guard_position(&map).unwrap();
+        let err = sim(&map, &guard, None).unwrap_err();
+        assert_eq!(err, SimError::InfiniteLoop);
+
+        // Option 5
+        let map = parse(
+            "....#.....
+.........#
+..........
+..#.......
+.......#..
+..........
+.#..^.....
+........#.
+#..#......
+......#...",
+        );
+
+        let guard = get_guard_position(&map).unwrap();
+        let err = sim(&map, &guard, None).unwrap_err();
+        assert_eq!(err, SimError::InfiniteLoop);
+
+        // Option 6
+        let map = parse(
+            "....#.....
+.........#
+..........
+..#.......
+.......#..
+..........
+.#..^.....
+........#.
+#.........
+......##..
+",
+        );
+
+        let guard = get_guard_position(&map).unwrap();
+        let err = sim(&map, &guard, None).unwrap_err();
+        assert_eq!(err, SimError::InfiniteLoop);
+    }
+
+    #[test]
+    fn sim_draws_frames() {
+        let map = parse(
+            ".#..
+...#
+.^..
+....",
+        );
+
+        let mut frames = vec![];
+        let path = sim(&map, &(1, 2), Some(&mut frames)).unwrap();
+
+        // a frame per move and turn, and one for walking off
+        assert_eq!(frames.len(), path.len() + 2);
+        assert_eq!(frames[0].get(1, 1), Some('^'));
+        assert_eq!(frames[0].colour(1, 1), Some(Colour::Red));
+        assert_eq!(frames[0].colour(1, 2), Some(Colour::Yellow));
+        assert_eq!(frames[0].get(1, 2), Some('.'));
+
+        // turning happens in place
+        assert_eq!(frames[1].get(1, 1), Some('>'));
+        assert_eq!(frames[4].status, "step 3, 4 visited");
+        assert_eq!(frames[6].status, "walked off, 5 visited");
+        assert_eq!(frames[6].get(2, 3), Some('.'));
+    }
+}
@@ -0,0 +1,282 @@
+use nom::{
+    character::complete::{char, space1},
+    combinator::map,
+    error::context,
+    sequence::{pair, separated_pair},
+};
+use std::{error::Error, fmt::Display, str::FromStr};
+
+use common::{
+    math,
+    parse::{lines, number, numbers, parse, ParseError, Res},
+    Answer,
+};
+
+/// The total of the equations that adding and multiplying can make true.
+#[tracing::instrument(skip_all)]
+pub fn part1(contents: &str) -> Result<Answer, Box<dyn Error>> {
+    let es = equations(contents)?;
+
+    Ok(get_total_calibration(&es, &[Operator::Add, Operator::Multiply]).into())
+}
+
+/// The same, when operands can also be concatenated.
+#[tracing::instrument(skip_all)]
+pub fn part2(contents: &str) -> Result<Answer, Box<dyn Error>> {
+    let es = equations(contents)?;
+
+    Ok(get_total_calibration(&es, &[Operator::Add, Operator::Multiply, Operator::Concat]).into())
+}
+
+#[derive(Debug, PartialEq, Eq, Hash)]
+pub enum Operator {
+    Add,
+    Multiply,
+    Concat,
+}
+
+#[derive(Debug, PartialEq, Eq)]
+pub struct Equation {
+    pub result: u64,
+    pub operands: Vec<u64>,
+}
+
+impl Equation {
+    pub fn find_operator_configurations<'a>(&self, ops: &'a [Operator]) -> Vec<Vec<&'a Operator>> {
+        let num_operators = self.operands.len() - 1;
+
+        let operator_variations = generate_operators(num_operators as u32, ops);
+
+        let mut working_operator_variations = vec![];
+
+        for v in operator_variations {
+            let mut v_iter = v.iter();
+
+            // anything too big to fit is too big to be the result
+            let result = self.operands[1..]
+                .iter()
+                .try_fold(self.operands[0], |a, b| match v_iter.next().unwrap() {
+                    Operator::Add => a.checked_add(*b),
+                    Operator::Multiply => a.checked_mul(*b),
+                    Operator::Concat => math::concat(a, *b),
+                });
+
+            if result == Some(self.result) {
+                working_operator_variations.push(v);
+            }
+        }
+
+        working_operator_variations
+    }
+}
+
+pub fn equations(input: &str) -> Result<Vec<Equation>, ParseError> {
+    parse(input, lines(equation))
+}
+
+/// `3267: 81 40 27`
+pub fn equation(input: &str) -> Res<'_, Equation> {
+    let equation = separated_pair(number, pair(char(':'), space1), numbers);
+
+    context(
+        "an equation",
+        map(equation, |(result, operands)| Equation { result, operands }),
+    )(input)
+}
+
+impl FromStr for Equation {
+    type Err = ParseError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        parse(s, equation)
+    }
+}
+
+impl Display for Equation {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}:", self.result)?;
+
+        for o in self.operands.iter() {
+            write!(f, " {}", o)?;
+        }
+
+        Ok(())
+    }
+}
+
+fn generate_operators(length: u32, supported_operators: &[Operator]) -> Vec<Vec<&Operator>> {
+    let radix = supported_operators.len();
+    let upper_bound = supported_operators.len().pow(length);
+
+    (0..upper_bound)
+        .map(|n| {
+            (0..length)
+                .filter_map(|d| {
+                    let index = (n / radix.pow(d)) % radix;
+
+                    supported_operators.get(index)
+                })
+                .collect()
+        })
+        .collect()
+}
+
+pub fn get_total_calibration(es: &[Equation], ops: &[Operator]) -> u64 {
+    es.iter()
+        .filter_map(|e| {
+            if !e.find_operator_configurations(ops).is_empty() {
+                Some(e.result)
+            } else {
+                None
+            }
+        })
+        .sum()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use proptest::prelude::*;
+
+    #[test]
+    fn generates_operators() {
+        let ops = generate_operators(2, &[Operator::Add, Operator::Multiply]);
+        let exp = vec![
+            vec![&Operator::Add, &Operator::Add],
+            vec![&Operator::Multiply, &Operator::Add],
+            vec![&Operator::Add, &Operator::Multiply],
+            vec![&Operator::Multiply, &Operator::Multiply],
+        ];
+
+        assert_eq!(ops, exp);
+    }
+
+    #[test]
+    fn equation_gets_parsed() {
+        let expectations = [
+            (
+                "190: 10 19",
+                Equation {
+                    result: 190,
+                    operands: vec![10, 19],
+                },
+            ),
+            (
+                "3267: 81 40 27",
+                Equation {
+                    result: 3267,
+                    operands: vec![81, 40, 27],
+                },
+            ),
+        ];
+
+        for (s, exp) in expectations {
+            let (_, equation) = equation(s).unwrap();
+            assert_eq!(equation, exp);
+        }
+    }
+
+    #[test]
+    fn equation_displays_as_parsed() {
+        let e: Equation = "3267: 81 40 27".parse().unwrap();
+
+        assert_eq!(e.to_string(), "3267: 81 40 27");
+        assert!("3267: 81 40 27 x".parse::<Equation>().is_err());
+    }
+
+    #[test]
+    fn equations_say_where_they_failed() {
+        let error = equations("190: 10 19\n3267 81 40 27").unwrap_err();
+
+        assert_eq!(
+            error.to_string(),
+            "line 2, column 5: expected ':' in an equation"
+        );
+    }
+
+    #[test]
+    fn gets_configurations() {
+        let es = equations(
+            "190: 10 19
+3267: 81 40 27
+83: 17 5
+156: 15 6
+7290: 6 8 6 15
+161011: 16 10 13
+192: 17 8 14
+21037: 9 7 18 13
+292: 11 6 16 20",
+        )
+        .unwrap();
+
+        let counts: Vec<_> = es
+            .iter()
+            .map(|e| {
+                e.find_operator_configurations(&[Operator::Add, Operator::Multiply])
+                    .len()
+            })
+            .collect();
+
+        assert_eq!(counts, vec![1, 2, 0, 0, 0, 0, 0, 0, 1]);
+        assert_eq!(
+            get_total_calibration(&es, &[Operator::Add, Operator::Multiply]),
+            3749
+        );
+        assert_eq!(
+            get_total_calibration(&es, &[Operator::Add, Operator::Multiply, Operator::Concat]),
+            11387
+        );
+    }
+
+    const ALL: [Operator; 3] = [Operator::Add, Operator::Multiply, Operator::Concat];
+
+    // operands and the operators to put between them
+    fn expression() -> impl Strategy<Value = (Vec<u64>, Vec<usize>)> {
+        prop::collection::vec(1..100u64, 1..6).prop_flat_map(|operands| {
+            let ops = prop::collection::vec(0..3usize, operands.len() - 1);
+            (Just(operands), ops)
+        })
+    }
+
+    // left to right, written out separately from `find_operator_configurations`
+    fn evaluate(operands: &[u64], ops: &[usize]) -> u64 {
+        ops.iter()
+            .zip(operands[1..].iter())
+            .fold(operands[0], |acc, (op, b)| match op {
+                0 => acc + b,
+                1 => acc * b,
+                _ => acc * 10u64.pow(b.to_string().len() as u32) + b,
+            })
+    }
+
+    proptest! {
+        #[test]
+        fn equations_find_their_own_operators((operands, ops) in expression()) {
+            let result = evaluate(&operands, &ops);
+            let line = format!(
+                "{}: {}",
+                result,
+                operands.iter().map(|o| o.to_string()).collect::<Vec<_>>().join(" ")
+            );
+            let (_, e) = equation(&line).unwrap();
+
+            let configs = e.find_operator_configurations(&ALL);
+            let own: Vec<_> = ops.iter().map(|o| &ALL[*o]).collect();
+
+            prop_assert!(configs.contains(&own));
+        }
+
+        #[test]
+        fn concat_only_adds_configurations((operands, ops) in expression()) {
+            let e = Equation {
+                result: evaluate(&operands, &ops),
+                operands,
+            };
+
+            let two = e.find_operator_configurations(&ALL[..2]);
+            let three = e.find_operator_configurations(&ALL);
+
+            prop_assert!(two.iter().all(|c| three.contains(c)));
+        }
+    }
+}
@@ -0,0 +1,271 @@
+use std::{
+    collections::{BTreeMap, BTreeSet},
+    error::Error,
+    fmt::Display,
+    str::FromStr,
+};
+
+use crate::{point::Point, resonance::Resonance};
+
+/// Antinode positions for each frequency.
+pub type Antinodes = BTreeMap<char, BTreeSet<Point>>;
+
+#[derive(Debug, PartialEq)]
+pub struct Grid {
+    pub width: usize,
+    pub height: usize,
+    pub antennas: BTreeMap<char, Vec<Point>>,
+}
+
+impl Grid {
+    pub fn in_bounds(&self, p: &Point) -> bool {
+        let x_good = 0 <= p.x && p.x < self.width as i32;
+        let y_good = 0 <= p.y && p.y < self.height as i32;
+
+        x_good && y_good
+    }
+
+    pub fn find_antinodes(&self, resonance: &Resonance) -> Antinodes {
+        self.antennas
+            .iter()
+            .map(|(f, ps)| {
+                let nodes = pairs(ps)
+                    .flat_map(|(a, b)| resonance.antinodes(a, b, |p| self.in_bounds(p)))
+                    .collect();
+
+                (*f, nodes)
+            })
+            .collect()
+    }
+
+    /// The map with antinodes drawn as `#` under the antennas.
+    pub fn render(&self, antinodes: &Antinodes) -> String {
+        let mut rows = vec![vec!['.'; self.width]; self.height];
+
+        for p in antinodes.values().flatten() {
+            rows[p.y as usize][p.x as usize] = '#';
+        }
+
+        for (f, ps) in self.antennas.iter() {
+            for p in ps {
+                rows[p.y as usize][p.x as usize] = *f;
+            }
+        }
+
+        rows.into_iter()
+            .map(|r| r.into_iter().collect::<String>() + "\n")
+            .collect()
+    }
+}
+
+/// Every antinode position, whichever frequency made it.
+pub fn unique(antinodes: &Antinodes) -> BTreeSet<Point> {
+    antinodes.values().flatten().copied().collect()
+}
+
+fn pairs<T>(items: &[T]) -> impl Iterator<Item = (&T, &T)> {
+    items
+        .iter()
+        .enumerate()
+        .flat_map(move |(i, a)| items[(i + 1)..].iter().map(move |b| (a, b)))
+}
+
+impl Display for Grid {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.render(&Antinodes::new()))
+    }
+}
+
+impl FromStr for Grid {
+    type Err = Box<dyn Error>;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let height = s.lines().count();
+        let width = s.lines().next().ok_or("no rows")?.chars().count();
+
+        // antennas are letters and digits, anything else is empty space
+        let mut antennas = BTreeMap::new();
+        for (y, line) in s.lines().enumerate() {
+            if line.chars().count() != width {
+                return Err(format!("row {} is not {} wide", y, width).into());
+            }
+
+            for (x, c) in line
+                .chars()
+                .enumerate()
+                .filter(|(_, c)| c.is_alphanumeric())
+            {
+                antennas.entry(c).or_insert(vec![]).push(Point::new(x, y));
+            }
+        }
+
+        Ok(Self {
+            width,
+            height,
+            antennas,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use proptest::prelude::*;
+
+    // empty space with a few antennas dropped on it, from few enough
+    // frequencies that most of them have a partner
+    fn grids() -> impl Strategy<Value = Grid> {
+        (1..10usize, 1..10usize)
+            .prop_flat_map(|(width, height)| {
+                let antenna = (
+                    0..width,
+                    0..height,
+                    prop::sample::select(vec!['a', 'B', '0']),
+                );
+                (Just((width, height)), prop::collection::vec(antenna, 0..8))
+            })
+            .prop_map(|((width, height), antennas)| {
+                let mut rows = vec![vec!['.'; width]; height];
+                for (x, y, frequency) in antennas {
+                    rows[y][x] = frequency;
+                }
+
+                let text: String = rows
+                    .iter()
+                    .map(|r| r.iter().collect::<String>() + "\n")
+                    .collect();
+                text.parse().unwrap()
+            })
+    }
+
+    // part 1 straight from the puzzle: in line with both antennas, and twice
+    // as far from one as from the other
+    fn brute_force_part1(grid: &Grid, ps: &[Point]) -> BTreeSet<Point> {
+        let mut nodes = BTreeSet::new();
+
+        for y in 0..grid.height {
+            for x in 0..grid.width {
+                let p = Point::new(x, y);
+
+                for (a, b) in pairs(ps) {
+                    let (ax, ay) = (a.x - p.x, a.y - p.y);
+                    let (bx, by) = (b.x - p.x, b.y - p.y);
+                    let in_line = ax * by == ay * bx;
+                    let twice = (ax == 2 * bx && ay == 2 * by) || (bx == 2 * ax && by == 2 * ay);
+
+                    if in_line && twice {
+                        nodes.insert(p);
+                    }
+                }
+            }
+        }
+
+        nodes
+    }
+
+    proptest! {
+        #[test]
+        fn part1_matches_brute_force(grid in grids()) {
+            let antinodes = grid.find_antinodes(&Resonance::part1());
+
+            for (f, ps) in grid.antennas.iter() {
+                prop_assert_eq!(&antinodes[f], &brute_force_part1(&grid, ps));
+            }
+        }
+
+        #[test]
+        fn line_contains_harmonics_and_antennas(grid in grids()) {
+            let harmonic = grid.find_antinodes(&Resonance::part1());
+            let line = grid.find_antinodes(&Resonance::Line);
+
+            for (f, ps) in grid.antennas.iter() {
+                prop_assert!(harmonic[f].is_subset(&line[f]));
+
+                if ps.len() > 1 {
+                    prop_assert!(ps.iter().all(|p| line[f].contains(p)));
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn grid_news() {
+        let grid: Grid = "...
+a..
+..b"
+        .parse()
+        .unwrap();
+
+        assert_eq!(grid.width, 3);
+        assert_eq!(grid.height, 3);
+        assert_eq!(
+            grid.antennas,
+            BTreeMap::from([('a', vec![Point::new(0, 1)]), ('b', vec![Point::new(2, 2)])])
+        );
+        assert!("...\n..".parse::<Grid>().is_err());
+    }
+
+    #[test]
+    fn grid_displays_as_parsed() {
+        let s = "....\n.a..\n..#A\n";
+        let grid: Grid = s.parse().unwrap();
+
+        assert_eq!(grid.to_string(), "....\n.a..\n...A\n");
+        assert_eq!(grid.to_string().parse::<Grid>().unwrap(), grid);
+    }
+
+    #[test]
+    fn grid_finds_antinodes() {
+        let exp = BTreeMap::from([('a', BTreeSet::from([Point::new(6, 7), Point::new(3, 1)]))]);
+
+        let grid: Grid = "..........
+..........
+..........
+....a.....
+..........
+.....a....
+..........
+..........
+..........
+.........."
+            .parse()
+            .unwrap();
+
+        let antinodes = grid.find_antinodes(&Resonance::part1());
+
+        assert_eq!(antinodes, exp);
+    }
+
+    #[test]
+    fn grid_finds_unlimited_antinodes() {
+        let grid: Grid = "T.........
+...T......
+.T........
+..........
+..........
+..........
+..........
+..........
+..........
+.........."
+            .parse()
+            .unwrap();
+
+        let antinodes = grid.find_antinodes(&Resonance::Line);
+        assert_eq!(antinodes[&'T'].len(), 9);
+    }
+
+    #[test]
+    fn grid_renders_overlay() {
+        let grid: Grid = "....
+.a..
+..a.
+...."
+            .parse()
+            .unwrap();
+
+        let antinodes = grid.find_antinodes(&Resonance::Line);
+
+        assert_eq!(grid.render(&antinodes), "#...\n.a..\n..a.\n...#\n");
+    }
+}
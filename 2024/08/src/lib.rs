@@ -0,0 +1,74 @@
+use std::error::Error;
+
+use common::Answer;
+
+pub use grid::{unique, Antinodes, Grid};
+pub use resonance::Resonance;
+
+mod grid;
+mod point;
+mod resonance;
+
+/// How many points are antinodes of some pair of antennas.
+#[tracing::instrument(skip_all)]
+pub fn part1(contents: &str) -> Result<Answer, Box<dyn Error>> {
+    unique_antinodes(contents, Resonance::part1())
+}
+
+/// The same, counting every point in line with a pair.
+#[tracing::instrument(skip_all)]
+pub fn part2(contents: &str) -> Result<Answer, Box<dyn Error>> {
+    unique_antinodes(contents, Resonance::Line)
+}
+
+fn unique_antinodes(contents: &str, resonance: Resonance) -> Result<Answer, Box<dyn Error>> {
+    let grid: Grid = contents.parse()?;
+
+    Ok(unique(&grid.find_antinodes(&resonance)).len().into())
+}
+
+/// Reads a harmonic like `2:1`, meaning one antenna is twice as far as the
+/// other. A trailing `+` also counts the points between the antennas.
+pub fn parse_harmonic(s: &str) -> Result<Resonance, Box<dyn Error>> {
+    let (ratio, between) = match s.strip_suffix('+') {
+        Some(ratio) => (ratio, true),
+        None => (s, false),
+    };
+
+    let (far, near) = ratio.split_once(':').ok_or("expected a ratio like 2:1")?;
+    let (far, near): (u32, u32) = (far.parse()?, near.parse()?);
+
+    if far == 0 || near == 0 {
+        return Err("ratio parts should be positive".into());
+    }
+
+    Ok(Resonance::Harmonic { near, far, between })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn solves_sample() {
+        let input = include_str!("../sample_1.txt");
+
+        assert_eq!(part1(input).unwrap(), Answer::Int(14));
+        assert_eq!(part2(input).unwrap(), Answer::Int(34));
+    }
+
+    #[test]
+    fn harmonic_parses() {
+        assert_eq!(parse_harmonic("2:1").unwrap(), Resonance::part1());
+        assert_eq!(
+            parse_harmonic("3:2+").unwrap(),
+            Resonance::Harmonic {
+                near: 2,
+                far: 3,
+                between: true
+            }
+        );
+        assert!(parse_harmonic("2").is_err());
+        assert!(parse_harmonic("0:1").is_err());
+    }
+}
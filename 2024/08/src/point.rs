@@ -0,0 +1,75 @@
+#[derive(Debug, PartialEq, Eq, PartialOrd, Ord, Clone, Copy, Hash)]
+pub struct Point {
+    pub x: i32,
+    pub y: i32,
+}
+
+impl Point {
+    pub fn new(x: usize, y: usize) -> Self {
+        Self {
+            x: x as i32,
+            y: y as i32,
+        }
+    }
+
+    /// The smallest whole step from `self` towards `other`, and how many of
+    /// those steps it takes to get there. Every grid point on the line through
+    /// both is a whole number of steps away from `self`.
+    pub fn step_towards(&self, other: &Self) -> ((i32, i32), i32) {
+        let (dx, dy) = (other.x - self.x, other.y - self.y);
+        let g = gcd(dx, dy);
+
+        ((dx / g, dy / g), g)
+    }
+
+    pub fn along(&self, (dx, dy): (i32, i32), k: i32) -> Self {
+        Self {
+            x: self.x + dx * k,
+            y: self.y + dy * k,
+        }
+    }
+
+    /// `along` for any `k`, or `None` if that's off the i32 plane.
+    pub fn checked_along(&self, (dx, dy): (i32, i32), k: i64) -> Option<Self> {
+        let x = (dx as i64).checked_mul(k)?.checked_add(self.x as i64)?;
+        let y = (dy as i64).checked_mul(k)?.checked_add(self.y as i64)?;
+
+        Some(Self {
+            x: x.try_into().ok()?,
+            y: y.try_into().ok()?,
+        })
+    }
+}
+
+pub fn gcd(a: i32, b: i32) -> i32 {
+    let (mut a, mut b) = (a.abs(), b.abs());
+
+    while b != 0 {
+        (a, b) = (b, a % b);
+    }
+
+    a
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn gcd_handles_signs_and_zero() {
+        assert_eq!(gcd(4, 6), 2);
+        assert_eq!(gcd(-4, 6), 2);
+        assert_eq!(gcd(0, -3), 3);
+        assert_eq!(gcd(7, 0), 7);
+    }
+
+    #[test]
+    fn point_steps_towards() {
+        let a = Point::new(1, 1);
+
+        assert_eq!(a.step_towards(&Point::new(7, 4)), ((2, 1), 3));
+        assert_eq!(a.step_towards(&Point::new(1, 5)), ((0, 1), 4));
+        assert_eq!(Point::new(7, 4).step_towards(&a), ((-2, -1), 3));
+        assert_eq!(a.along((2, 1), 3), Point::new(7, 4));
+    }
+}
@@ -0,0 +1,175 @@
+use crate::point::Point;
+
+/// Where a pair of antennas of the same frequency creates antinodes.
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub enum Resonance {
+    /// Points in line with both antennas where one is `far / near` times as
+    /// far away as the other. Part 1 is 2:1 without the points in between.
+    Harmonic { near: u32, far: u32, between: bool },
+    /// Every grid point in line with both antennas, the antennas included
+    Line,
+}
+
+impl Resonance {
+    pub fn part1() -> Self {
+        Self::Harmonic {
+            near: 1,
+            far: 2,
+            between: false,
+        }
+    }
+
+    /// Antinodes of the antennas at `a` and `b` that fall inside `in_bounds`.
+    pub fn antinodes(
+        &self,
+        a: &Point,
+        b: &Point,
+        in_bounds: impl Fn(&Point) -> bool,
+    ) -> Vec<Point> {
+        let (step, g) = a.step_towards(b);
+
+        // every point in line is `a.along(step, k)` for some whole k, and b is at k = g
+        match *self {
+            Resonance::Line => {
+                let forwards = (0..).map(|k| a.along(step, k)).take_while(&in_bounds);
+                let backwards = (1..).map(|k| a.along(step, -k)).take_while(&in_bounds);
+
+                forwards.chain(backwards).collect()
+            }
+            Resonance::Harmonic { near, far, between } => {
+                // in i64 so any u32 ratio fits, and so does far * g
+                let (near, far) = (near.min(far) as i64, near.max(far) as i64);
+                let g = g as i64;
+
+                // |k| = far / near * |k - g|, solved on both sides of b and between
+                let mut ks = vec![];
+                if far != near {
+                    ks.push(far.checked_mul(g).and_then(|n| exact_div(n, far - near)));
+                }
+                if between {
+                    ks.push(far.checked_mul(g).and_then(|n| exact_div(n, far + near)));
+                }
+
+                let mut nodes: Vec<_> = ks
+                    .into_iter()
+                    .flatten()
+                    .flat_map(|k| [Some(k), g.checked_sub(k)])
+                    .flatten()
+                    .filter_map(|k| a.checked_along(step, k))
+                    .filter(|p| in_bounds(p))
+                    .collect();
+
+                nodes.sort();
+                nodes.dedup();
+                nodes
+            }
+        }
+    }
+}
+
+fn exact_div(a: i64, b: i64) -> Option<i64> {
+    (a % b == 0).then(|| a / b)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn within(size: i32) -> impl Fn(&Point) -> bool {
+        move |p| 0 <= p.x && p.x < size && 0 <= p.y && p.y < size
+    }
+
+    #[test]
+    fn resonance_finds_harmonics() {
+        let a = Point::new(3, 3);
+        let b = Point::new(6, 6);
+
+        let nodes = Resonance::part1().antinodes(&a, &b, within(20));
+        assert_eq!(nodes, vec![Point::new(0, 0), Point::new(9, 9)]);
+
+        let between = Resonance::Harmonic {
+            near: 1,
+            far: 2,
+            between: true,
+        };
+        let nodes = between.antinodes(&a, &b, within(20));
+        assert_eq!(
+            nodes,
+            vec![
+                Point::new(0, 0),
+                Point::new(4, 4),
+                Point::new(5, 5),
+                Point::new(9, 9)
+            ]
+        );
+    }
+
+    #[test]
+    fn resonance_finds_other_ratios() {
+        let a = Point::new(4, 4);
+        let b = Point::new(6, 4);
+
+        let three = Resonance::Harmonic {
+            near: 1,
+            far: 3,
+            between: false,
+        };
+        assert_eq!(
+            three.antinodes(&a, &b, within(20)),
+            vec![Point::new(3, 4), Point::new(7, 4)]
+        );
+        // one step apart, 3:1 lands between grid points
+        assert!(three
+            .antinodes(&a, &Point::new(5, 4), within(20))
+            .is_empty());
+
+        let half = Resonance::Harmonic {
+            near: 2,
+            far: 3,
+            between: false,
+        };
+        assert_eq!(
+            half.antinodes(&a, &b, within(20)),
+            vec![Point::new(0, 4), Point::new(10, 4)]
+        );
+    }
+
+    #[test]
+    fn resonance_takes_any_u32_ratio() {
+        let a = Point::new(3, 3);
+        let b = Point::new(6, 6);
+
+        // the only solutions are billions of steps away
+        let huge = Resonance::Harmonic {
+            near: u32::MAX - 1,
+            far: u32::MAX,
+            between: true,
+        };
+        assert!(huge.antinodes(&a, &b, within(20)).is_empty());
+
+        let lopsided = Resonance::Harmonic {
+            near: 1,
+            far: u32::MAX,
+            between: true,
+        };
+        assert!(lopsided.antinodes(&a, &b, |_| true).is_empty());
+    }
+
+    #[test]
+    fn resonance_lines_use_smallest_step() {
+        let a = Point::new(0, 0);
+        let b = Point::new(4, 2);
+
+        let nodes = Resonance::Line.antinodes(&a, &b, within(7));
+
+        assert_eq!(
+            nodes,
+            vec![
+                Point::new(0, 0),
+                Point::new(2, 1),
+                Point::new(4, 2),
+                Point::new(6, 3)
+            ]
+        );
+    }
+}
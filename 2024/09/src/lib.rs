@@ -0,0 +1,310 @@
+mod list;
+
+use std::{
+    error::Error,
+    fmt::{self, Display},
+    str::FromStr,
+};
+
+use common::Answer;
+use list::parse_list;
+
+type Blocks = Vec<Block>;
+
+/// The two ways of compacting a disk.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Compaction {
+    /// one block at a time, as in part 1
+    Blocks,
+    /// whole files at a time, as in part 2
+    Files,
+}
+
+impl FromStr for Compaction {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "blocks" => Ok(Compaction::Blocks),
+            "files" => Ok(Compaction::Files),
+            s => Err(format!("{} isn't blocks or files", s)),
+        }
+    }
+}
+
+impl Display for Compaction {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Compaction::Blocks => write!(f, "blocks"),
+            Compaction::Files => write!(f, "files"),
+        }
+    }
+}
+
+common::params! {
+    #[derive(Debug, Clone, Copy)]
+    pub struct Rules {
+        /// how part 2 compacts the disk, "files" or "blocks", so the two can
+        /// be compared on the same input
+        part2_compaction: Compaction = Compaction::Files,
+    }
+}
+
+/// The checksum once blocks are moved one at a time into the first free space.
+#[tracing::instrument(skip_all)]
+pub fn part1(input: &str) -> Result<Answer, Box<dyn Error>> {
+    let mut blocks = parse(input);
+    compress(&mut blocks);
+
+    Ok(checksum(&blocks).into())
+}
+
+/// The checksum once whole files are moved into the first space they fit.
+#[tracing::instrument(skip_all)]
+pub fn part2(input: &str) -> Result<Answer, Box<dyn Error>> {
+    part2_with(input, &Rules::default())
+}
+
+/// The checksum once the disk is compacted the way `rules` says.
+pub fn part2_with(input: &str, rules: &Rules) -> Result<Answer, Box<dyn Error>> {
+    match rules.part2_compaction {
+        Compaction::Blocks => part1(input),
+        Compaction::Files => {
+            let mut blocks = parse_list(input);
+            blocks.compress();
+
+            Ok(blocks.checksum().into())
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Block {
+    Free,
+    File(u32),
+}
+
+impl Display for Block {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let c = match self {
+            Block::Free => ".",
+            Block::File(id) => &id.to_string(),
+        };
+        write!(f, "{c}")
+    }
+}
+
+fn parse(s: &str) -> Blocks {
+    let mut blocks = vec![];
+    for (i, c) in s.chars().enumerate() {
+        if let Some(d) = c.to_digit(10) {
+            let block_type = if i % 2 == 0 {
+                // file
+                Block::File(i as u32 / 2)
+            } else {
+                Block::Free
+            };
+
+            let mut new_blocks = vec![block_type; d as usize];
+            blocks.append(&mut new_blocks);
+        }
+    }
+
+    blocks
+}
+
+fn compress(blocks: &mut Blocks) {
+    let mut front_i = 0;
+    let mut back_i = blocks.len().saturating_sub(1);
+
+    while front_i < back_i {
+        let front = blocks[front_i];
+        let back = blocks[back_i];
+
+        if front != Block::Free {
+            front_i += 1;
+            continue;
+        }
+
+        if back == Block::Free {
+            back_i -= 1;
+            continue;
+        }
+
+        blocks[front_i] = back;
+        blocks[back_i] = front;
+
+        front_i += 1;
+        back_i -= 1;
+    }
+}
+
+fn checksum(blocks: &Blocks) -> usize {
+    blocks
+        .iter()
+        .enumerate()
+        .filter_map(|(i, b)| {
+            if let Block::File(id) = b {
+                Some(*id as usize * i)
+            } else {
+                None
+            }
+        })
+        .sum()
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::BTreeMap;
+
+    use super::*;
+    use list::Block as ListBlock;
+    use proptest::prelude::*;
+
+    fn display(blocks: &Blocks) -> String {
+        blocks.iter().map(|b| b.to_string()).collect()
+    }
+
+    #[test]
+    fn parses() {
+        let blocks = parse("12345");
+        assert_eq!(display(&blocks), "0..111....22222");
+
+        let blocks = parse("2333133121414131402");
+        assert_eq!(
+            display(&blocks),
+            "00...111...2...333.44.5555.6666.777.888899"
+        );
+    }
+
+    #[test]
+    fn compresses() {
+        let mut blocks = parse("12345");
+        compress(&mut blocks);
+        assert_eq!(display(&blocks), "022111222......");
+
+        let mut blocks = parse("2333133121414131402");
+        compress(&mut blocks);
+        assert_eq!(
+            display(&blocks),
+            "0099811188827773336446555566.............."
+        );
+    }
+
+    #[test]
+    fn solves_sample() {
+        assert_eq!(part1("2333133121414131402").unwrap(), Answer::Int(1928));
+        assert_eq!(part2("2333133121414131402").unwrap(), Answer::Int(2858));
+
+        let blocks = Rules {
+            part2_compaction: "blocks".parse().unwrap(),
+        };
+        assert_eq!(
+            part2_with("2333133121414131402", &blocks).unwrap(),
+            Answer::Int(1928)
+        );
+    }
+
+    #[test]
+    fn checksums() {
+        let mut blocks = parse("2333133121414131402");
+        compress(&mut blocks);
+        let cs = checksum(&blocks);
+
+        assert_eq!(cs, 1928)
+    }
+
+    // file sizes 1 to 9 with free space 0 to 9 between them, like the puzzle
+    fn disk_map(file_sizes: std::ops::RangeInclusive<u32>) -> impl Strategy<Value = String> {
+        prop::collection::vec((file_sizes, 0..=9u32), 1..30).prop_map(|entries| {
+            entries
+                .iter()
+                .flat_map(|(file, free)| [file, free])
+                .filter_map(|d| char::from_digit(*d, 10))
+                .collect()
+        })
+    }
+
+    fn file_ids(blocks: impl Iterator<Item = Option<usize>>) -> Vec<usize> {
+        let mut ids: Vec<_> = blocks.flatten().collect();
+        ids.sort();
+        ids
+    }
+
+    fn block_ids(blocks: &Blocks) -> Vec<Option<usize>> {
+        blocks
+            .iter()
+            .map(|b| match b {
+                Block::File(id) => Some(*id as usize),
+                Block::Free => None,
+            })
+            .collect()
+    }
+
+    fn list_ids(list: &list::Blocks) -> Vec<Option<usize>> {
+        list.expand()
+            .iter()
+            .map(|b| match b {
+                ListBlock::File(id) => Some(*id),
+                ListBlock::Free => None,
+            })
+            .collect()
+    }
+
+    // where each file starts
+    fn starts(ids: &[Option<usize>]) -> BTreeMap<usize, usize> {
+        let mut starts = BTreeMap::new();
+        for (i, id) in ids.iter().enumerate() {
+            if let Some(id) = id {
+                starts.entry(*id).or_insert(i);
+            }
+        }
+        starts
+    }
+
+    proptest! {
+        #[test]
+        fn block_compaction_keeps_files_and_closes_gaps(map in disk_map(1..=9)) {
+            let mut blocks = parse(&map);
+            let before = block_ids(&blocks);
+            compress(&mut blocks);
+            let after = block_ids(&blocks);
+
+            prop_assert_eq!(file_ids(before.into_iter()), file_ids(after.iter().copied()));
+
+            let files = after.iter().take_while(|b| b.is_some()).count();
+            prop_assert!(after[files..].iter().all(|b| b.is_none()));
+        }
+
+        #[test]
+        fn file_compaction_keeps_files_whole(map in disk_map(1..=9)) {
+            let mut list = parse_list(&map);
+            let before = list_ids(&list);
+            list.compress();
+            let after = list_ids(&list);
+
+            prop_assert_eq!(file_ids(before.iter().copied()), file_ids(after.iter().copied()));
+
+            let (before_starts, after_starts) = (starts(&before), starts(&after));
+            for (id, start) in after_starts.iter() {
+                // files only ever move left, and stay in one piece
+                prop_assert!(*start <= before_starts[id]);
+
+                let size = after.iter().filter(|b| **b == Some(*id)).count();
+                prop_assert!(after[*start..(*start + size)].iter().all(|b| *b == Some(*id)));
+            }
+        }
+
+        #[test]
+        fn compactions_agree_on_single_block_files(map in disk_map(1..=1)) {
+            let mut blocks = parse(&map);
+            compress(&mut blocks);
+
+            let mut list = parse_list(&map);
+            list.compress();
+
+            prop_assert_eq!(block_ids(&blocks), list_ids(&list));
+            prop_assert_eq!(checksum(&blocks) as u64, list.checksum());
+        }
+    }
+}
@@ -34,17 +34,22 @@ impl Blocks {
     }
 
     pub fn checksum(&self) -> u64 {
-        let mut index = 0;
-        let mut total = 0;
-        for (block, size) in self.list.iter() {
-            if let Block::File(id) = block {
-                let sum: u64 = (index..index + size).map(|i| i as u64 * *id as u64).sum();
-                total += sum;
-            }
-            index = index + size;
-        }
+        self.expand()
+            .iter()
+            .enumerate()
+            .map(|(i, block)| match block {
+                Block::File(id) => i as u64 * *id as u64,
+                Block::Free => 0,
+            })
+            .sum()
+    }
 
-        total
+    /// One entry per block, the way the disk lays them out.
+    pub fn expand(&self) -> Vec<Block> {
+        self.list
+            .iter()
+            .flat_map(|(block, size)| std::iter::repeat_n(*block, *size as usize))
+            .collect()
     }
 
     pub fn compress(&mut self) {
@@ -0,0 +1,50 @@
+use std::error::Error;
+
+use common::Answer;
+
+pub use map::{Map, MapError, Point};
+pub use trails::{Paths, Steps, Trailhead, Trails, MAX_HEIGHT};
+
+mod map;
+mod trails;
+
+/// The sum of how many peaks each trailhead reaches.
+#[tracing::instrument(skip_all)]
+pub fn part1(input: &str) -> Result<Answer, Box<dyn Error>> {
+    part1_with(input, Steps::default())
+}
+
+/// The sum of how many peaks each trailhead reaches, taking `steps` along
+/// trails.
+pub fn part1_with(input: &str, steps: Steps) -> Result<Answer, Box<dyn Error>> {
+    let (score, _) = input.parse::<Map>()?.score_with(steps)?;
+
+    Ok(score.into())
+}
+
+/// The sum of how many trails lead up from each trailhead.
+#[tracing::instrument(skip_all)]
+pub fn part2(input: &str) -> Result<Answer, Box<dyn Error>> {
+    part2_with(input, Steps::default())
+}
+
+/// The sum of how many trails lead up from each trailhead, taking `steps`
+/// along trails.
+pub fn part2_with(input: &str, steps: Steps) -> Result<Answer, Box<dyn Error>> {
+    let (_, rating) = input.parse::<Map>()?.score_with(steps)?;
+
+    Ok(rating.into())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn solves_sample() {
+        let input = include_str!("../sample_1.txt");
+
+        assert_eq!(part1(input).unwrap(), Answer::Int(36));
+        assert_eq!(part2(input).unwrap(), Answer::Int(81));
+    }
+}
@@ -1,19 +1,56 @@
-use std::{env, error::Error, fs};
+use std::{env, error::Error, fs, path::Path};
 
-use map::Map;
-
-mod map;
+use hoof::{Map, Steps, Trails};
 
 fn main() -> Result<(), Box<dyn Error>> {
-    let filename = env::args().skip(1).next().expect("filename");
-    let input = fs::read_to_string(&filename).expect("file");
+    let _day = common::trace::init("hoof");
 
-    let map: Map = input.parse()?;
+    let args: Vec<String> = env::args().skip(1).collect();
+    let (mut steps, args) =
+        common::params::load::<Steps>(Path::new(env!("CARGO_MANIFEST_DIR")), &args)?;
+    let filename = args.first().expect("filename");
+    let input = fs::read_to_string(filename).expect("file");
+
+    let mut print_trails = false;
+    let mut flags = args.iter().skip(1);
+    while let Some(flag) = flags.next() {
+        match flag.as_str() {
+            "--diagonal" => steps.diagonal = true,
+            "--delta" => steps.delta = flags.next().ok_or("--delta needs a value")?.parse()?,
+            "--max" => steps.max_height = flags.next().ok_or("--max needs a value")?.parse()?,
+            "--trails" => print_trails = true,
+            f => return Err(format!("unknown flag: {}", f).into()),
+        }
+    }
+
+    if common::answer::for_tools() {
+        common::answer::print(1, &hoof::part1_with(&input, steps)?);
+        common::answer::print(2, &hoof::part2_with(&input, steps)?);
+        return Ok(());
+    }
 
-    let (score, rating) = map.score().expect("should score");
+    println!("pt1 score: {}", hoof::part1_with(&input, steps)?);
+    println!("pt2 rating: {}", hoof::part2_with(&input, steps)?);
+
+    if args.len() == 1 {
+        return Ok(());
+    }
+
+    let map: Map = input.parse()?;
+    let trails = Trails::new(&map, steps)?;
+    for th in trails.trailheads() {
+        println!(
+            "trailhead {},{}: score {}, rating {}",
+            th.start.x, th.start.y, th.score, th.rating
+        );
 
-    println!("pt1 score: {}", score);
-    println!("pt2 rating: {}", rating);
+        if print_trails {
+            for path in trails.paths(th.start) {
+                let path: Vec<_> = path.iter().map(|p| format!("{},{}", p.x, p.y)).collect();
+                println!("  {}", path.join(" "));
+            }
+        }
+    }
 
     Ok(())
 }
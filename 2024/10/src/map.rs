@@ -1,73 +1,103 @@
-use std::{
-    collections::{HashMap, HashSet, VecDeque},
-    error::Error,
-    str::FromStr,
-};
+use std::{error::Error, fmt::Display, str::FromStr};
 
-const MAX_HEIGHT: u32 = 9;
+use crate::trails::{Steps, Trails};
 
 #[derive(Debug)]
 pub enum MapError {
     TrailheadNotFound,
+    /// steps that don't go up, so trails could go round in circles
+    FlatSteps,
+    /// more trails than a count can hold
+    TooManyTrails,
 }
 
-#[derive(Debug)]
+impl Display for MapError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            MapError::TrailheadNotFound => write!(f, "no trailhead found"),
+            MapError::FlatSteps => write!(f, "steps should go up by at least 1"),
+            MapError::TooManyTrails => write!(f, "too many trails to count"),
+        }
+    }
+}
+
+impl Error for MapError {}
+
+/// Heights in row order. Anything that isn't a digit is impassable.
+#[derive(Debug, PartialEq)]
 pub struct Map {
-    heights_by_position: HashMap<u32, Vec<Point>>,
+    pub width: usize,
+    pub height: usize,
+    heights: Vec<Option<u32>>,
 }
 
 impl Map {
+    /// Total score and rating of every trailhead with the puzzle's rules.
     pub fn score(&self) -> Result<(usize, usize), MapError> {
-        let trailheads = self
-            .heights_by_position
-            .get(&0)
-            .ok_or(MapError::TrailheadNotFound)?;
-
-        let mut queue = VecDeque::new();
-        let mut score = 0;
-        let mut rating = 0;
-
-        for th in trailheads {
-            queue.push_back((th, 0));
-
-            let mut visited_ends = HashSet::new();
-
-            while let Some((point, height)) = queue.pop_front() {
-                let next_height = height + 1;
-
-                let Some(points) = self.heights_by_position.get(&next_height) else {
-                    continue;
-                };
-
-                let valid_points: Vec<_> = points
-                    .into_iter()
-                    .filter_map(|p| {
-                        if p.is_next_to(point) {
-                            Some((p, next_height))
-                        } else {
-                            None
-                        }
-                    })
-                    .collect();
-
-                for vp in valid_points {
-                    if next_height == MAX_HEIGHT {
-                        if visited_ends.insert(vp) {
-                            score += 1;
-                        }
-
-                        rating += 1;
-                    }
-                    if next_height == MAX_HEIGHT && visited_ends.insert(vp) {
-                        score += 1;
-                        continue;
-                    }
-                    queue.push_back(vp);
+        self.score_with(Steps::default())
+    }
+
+    /// Total score and rating of every trailhead, taking `steps` along trails.
+    pub fn score_with(&self, steps: Steps) -> Result<(usize, usize), MapError> {
+        let trails = Trails::new(self, steps)?;
+        let trailheads = trails.trailheads();
+
+        if trailheads.is_empty() {
+            return Err(MapError::TrailheadNotFound);
+        }
+
+        trailheads.iter().try_fold((0usize, 0usize), |(s, r), th| {
+            let s = s.checked_add(th.score).ok_or(MapError::TooManyTrails)?;
+            let r = r.checked_add(th.rating).ok_or(MapError::TooManyTrails)?;
+
+            Ok((s, r))
+        })
+    }
+
+    pub fn len(&self) -> usize {
+        self.heights.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.heights.is_empty()
+    }
+
+    pub fn height_at(&self, i: usize) -> Option<u32> {
+        self.heights[i]
+    }
+
+    pub fn point(&self, i: usize) -> Point {
+        Point::new(i % self.width, i / self.width)
+    }
+
+    /// Index of the cell `(dx, dy)` away from cell `i`, if it's on the map.
+    pub fn offset(&self, i: usize, (dx, dy): (i32, i32)) -> Option<usize> {
+        let p = self.point(i);
+        let x = p.x as i32 + dx;
+        let y = p.y as i32 + dy;
+
+        if x < 0 || y < 0 || x >= self.width as i32 || y >= self.height as i32 {
+            return None;
+        }
+
+        Some(y as usize * self.width + x as usize)
+    }
+}
+
+impl Display for Map {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        for y in 0..self.height {
+            for h in &self.heights[y * self.width..(y + 1) * self.width] {
+                match h {
+                    Some(h) => write!(f, "{}", h)?,
+                    None => write!(f, ".")?,
                 }
             }
+
+            writeln!(f)?;
         }
 
-        Ok((score, rating))
+        Ok(())
     }
 }
 
@@ -75,29 +105,30 @@ impl FromStr for Map {
     type Err = Box<dyn Error>;
 
     fn from_str(s: &str) -> Result<Self, Self::Err> {
-        let mut heights_by_position = HashMap::new();
+        let height = s.lines().count();
+        let width = s.lines().next().ok_or("no rows")?.chars().count();
+        let mut heights = Vec::with_capacity(width * height);
 
         for (y, l) in s.lines().enumerate() {
-            for (x, c) in l.chars().enumerate() {
-                if let Some(n) = c.to_digit(10) {
-                    heights_by_position
-                        .entry(n)
-                        .or_insert(Vec::new())
-                        .push(Point::new(x, y));
-                }
+            if l.chars().count() != width {
+                return Err(format!("row {} is not {} wide", y, width).into());
             }
+
+            heights.extend(l.chars().map(|c| c.to_digit(10)));
         }
 
         Ok(Self {
-            heights_by_position,
+            width,
+            height,
+            heights,
         })
     }
 }
 
-#[derive(Debug, Hash, PartialEq, Eq)]
+#[derive(Debug, Hash, PartialEq, Eq, Clone, Copy)]
 pub struct Point {
-    x: usize,
-    y: usize,
+    pub x: usize,
+    pub y: usize,
 }
 
 impl PartialEq<(usize, usize)> for Point {
@@ -110,17 +141,6 @@ impl Point {
     pub fn new(x: usize, y: usize) -> Self {
         Self { x, y }
     }
-
-    pub fn is_next_to(&self, other: &Self) -> bool {
-        let transforms = [(0, 1), (1, 0), (0, -1), (-1, 0)];
-
-        transforms.into_iter().any(|(dx, dy)| {
-            let new_x = self.x as i32 + dx;
-            let new_y = self.y as i32 + dy;
-
-            new_x == other.x as i32 && new_y == other.y as i32
-        })
-    }
 }
 
 #[cfg(test)]
@@ -134,12 +154,54 @@ mod tests {
             .parse()
             .unwrap();
 
-        assert_eq!(map.heights_by_position[&0], vec![(1, 0)]);
-        assert_eq!(map.heights_by_position[&1], vec![(1, 1)]);
-        assert_eq!(map.heights_by_position[&8], vec![(3, 1)]);
-        assert_eq!(map.heights_by_position[&9], vec![(0, 0), (3, 0), (2, 1)]);
+        assert_eq!(map.width, 4);
+        assert_eq!(map.height, 2);
+        assert_eq!(map.height_at(1), Some(0));
+        assert_eq!(map.height_at(2), None);
+        assert_eq!(map.height_at(7), Some(8));
+        assert_eq!(map.point(6), (2, 1));
+        assert!("90\n1".parse::<Map>().is_err());
+        assert!("90\né".parse::<Map>().is_err());
+    }
 
-        assert_eq!(map.heights_by_position.len(), 4)
+    #[test]
+    fn map_displays_as_parsed() {
+        let map: Map = "90.9\n.1x8".parse().unwrap();
+
+        assert_eq!(map.to_string(), "90.9\n.1.8\n");
+    }
+
+    #[test]
+    fn map_offsets_stay_on_map() {
+        let map: Map = "012\n345".parse().unwrap();
+
+        assert_eq!(map.offset(0, (1, 1)), Some(4));
+        assert_eq!(map.offset(0, (-1, 0)), None);
+        assert_eq!(map.offset(2, (1, 0)), None);
+        assert_eq!(map.offset(5, (0, 1)), None);
+    }
+
+    #[test]
+    fn map_rejects_flat_steps() {
+        let map: Map = "00\n00".parse().unwrap();
+        let steps = Steps {
+            delta: 0,
+            max_height: 0,
+            ..Steps::default()
+        };
+
+        assert!(matches!(map.score_with(steps), Err(MapError::FlatSteps)));
+    }
+
+    #[test]
+    fn map_takes_steps_too_big_for_any_height() {
+        let map: Map = "09\n19".parse().unwrap();
+        let steps = Steps {
+            delta: u32::MAX,
+            ..Steps::default()
+        };
+
+        assert_eq!(map.score_with(steps).unwrap(), (0, 0));
     }
 
     #[test]
@@ -247,4 +309,11 @@ mod tests {
         let (_, rating) = map.score().unwrap();
         assert_eq!(rating, 227);
     }
+
+    #[test]
+    fn map_without_trailheads_errors() {
+        let map: Map = "123\n456".parse().unwrap();
+
+        assert!(map.score().is_err());
+    }
 }
@@ -0,0 +1,383 @@
+use common::graph::{self, CountError, Graph};
+
+use crate::map::{Map, MapError, Point};
+
+pub const MAX_HEIGHT: u32 = 9;
+
+const STRAIGHT: [(i32, i32); 4] = [(0, 1), (1, 0), (0, -1), (-1, 0)];
+const DIAGONAL: [(i32, i32); 4] = [(1, 1), (1, -1), (-1, 1), (-1, -1)];
+
+common::params! {
+    /// What counts as a step along a trail.
+    #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+    pub struct Steps {
+        /// how much higher every step has to be
+        delta: u32 = 1,
+        /// whether trails can also go diagonally
+        diagonal: bool = false,
+        /// the height trails end at
+        max_height: u32 = MAX_HEIGHT,
+    }
+}
+
+impl Steps {
+    fn directions(&self) -> impl Iterator<Item = (i32, i32)> {
+        let diagonal = if self.diagonal { &DIAGONAL[..] } else { &[] };
+
+        STRAIGHT.into_iter().chain(diagonal.iter().copied())
+    }
+}
+
+#[derive(Debug, PartialEq, Eq)]
+pub struct Trailhead {
+    pub start: Point,
+    /// how many trail ends it reaches
+    pub score: usize,
+    /// how many distinct trails start here
+    pub rating: usize,
+}
+
+/// Every step of the map as a DAG over cell indexes. Steps always go up, so
+/// working from the highest cells down visits every cell after everything
+/// it leads to.
+///
+/// Ratings take one pass over the steps, O(cells + steps). Scores have to
+/// tell ends apart, so each cell keeps a bitset of the ends it reaches, which
+/// makes them O(steps × ends / 64) on top of sorting the cells by height.
+#[derive(Debug)]
+pub struct Trails<'a> {
+    map: &'a Map,
+    steps: Steps,
+    next: Vec<Vec<usize>>,
+    // trails from each cell to an end
+    ratings: Vec<usize>,
+    // ends reachable from each cell, one bit per end
+    reachable: Vec<Vec<u64>>,
+}
+
+impl<'a> Trails<'a> {
+    /// Fails if `steps` don't go up, as trails could go round in circles.
+    pub fn new(map: &'a Map, steps: Steps) -> Result<Self, MapError> {
+        if steps.delta == 0 {
+            return Err(MapError::FlatSteps);
+        }
+
+        let next: Vec<Vec<usize>> = (0..map.len())
+            .map(|i| match map.height_at(i) {
+                Some(h) if h < steps.max_height => steps
+                    .directions()
+                    .filter_map(|d| map.offset(i, d))
+                    .filter(|j| {
+                        h.checked_add(steps.delta)
+                            .is_some_and(|up| map.height_at(*j) == Some(up))
+                    })
+                    .collect(),
+                _ => vec![],
+            })
+            .collect();
+
+        let mut trails = Self {
+            map,
+            steps,
+            next,
+            ratings: vec![],
+            reachable: vec![],
+        };
+
+        let counts = graph::count_paths(&trails, 0..map.len(), |i| trails.is_end(*i)).map_err(
+            |e| match e {
+                CountError::Cycle(_) => MapError::FlatSteps,
+                CountError::Overflow(_) => MapError::TooManyTrails,
+            },
+        )?;
+        trails.ratings = (0..map.len()).map(|i| counts[&i] as usize).collect();
+        trails.reachable = trails.reachable();
+
+        Ok(trails)
+    }
+
+    fn reachable(&self) -> Vec<Vec<u64>> {
+        let mut end_ids = vec![None; self.map.len()];
+        let mut ends = 0usize;
+        for (i, id) in end_ids.iter_mut().enumerate() {
+            if self.is_end(i) {
+                *id = Some(ends);
+                ends += 1;
+            }
+        }
+
+        let mut order: Vec<_> = (0..self.map.len())
+            .filter(|i| self.map.height_at(*i).is_some())
+            .collect();
+        order.sort_unstable_by_key(|i| std::cmp::Reverse(self.map.height_at(*i)));
+
+        let mut reachable = vec![vec![0u64; ends.div_ceil(64)]; self.map.len()];
+
+        for i in order {
+            if let Some(id) = end_ids[i] {
+                reachable[i][id / 64] |= 1 << (id % 64);
+                continue;
+            }
+
+            for j in self.next[i].iter() {
+                let (from, to) = (reachable[*j].clone(), &mut reachable[i]);
+                for (t, f) in to.iter_mut().zip(from) {
+                    *t |= f;
+                }
+            }
+        }
+
+        reachable
+    }
+
+    /// Every height 0 cell with its score and rating, in row order.
+    pub fn trailheads(&self) -> Vec<Trailhead> {
+        (0..self.map.len())
+            .filter(|i| self.map.height_at(*i) == Some(0))
+            .map(|i| Trailhead {
+                start: self.map.point(i),
+                score: self.reachable[i]
+                    .iter()
+                    .map(|w| w.count_ones() as usize)
+                    .sum(),
+                rating: self.ratings[i],
+            })
+            .collect()
+    }
+
+    /// The trails starting at `start`, one at a time. Only cells that lead to
+    /// an end are ever visited.
+    pub fn paths(&self, start: Point) -> Paths<'_> {
+        let i = start.y * self.map.width + start.x;
+        let stack = if start.x < self.map.width && self.ratings.get(i) > Some(&0) {
+            vec![(i, 0)]
+        } else {
+            vec![]
+        };
+
+        Paths {
+            trails: self,
+            stack,
+        }
+    }
+
+    fn is_end(&self, i: usize) -> bool {
+        self.map.height_at(i) == Some(self.steps.max_height)
+    }
+}
+
+impl Graph for Trails<'_> {
+    type Node = usize;
+
+    fn neighbours(&self, i: &usize) -> impl IntoIterator<Item = usize> {
+        self.next[*i].iter().copied()
+    }
+}
+
+/// Depth first walk over the trails from one trailhead.
+pub struct Paths<'a> {
+    trails: &'a Trails<'a>,
+    // cells on the current trail, each with the next step to try from it
+    stack: Vec<(usize, usize)>,
+}
+
+impl Iterator for Paths<'_> {
+    type Item = Vec<Point>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        while let Some(&(cell, k)) = self.stack.last() {
+            if self.trails.is_end(cell) {
+                let path = self
+                    .stack
+                    .iter()
+                    .map(|(c, _)| self.trails.map.point(*c))
+                    .collect();
+
+                self.stack.pop();
+                return Some(path);
+            }
+
+            let Some(&child) = self.trails.next[cell].get(k) else {
+                self.stack.pop();
+                continue;
+            };
+
+            if let Some(top) = self.stack.last_mut() {
+                top.1 += 1;
+            }
+
+            if self.trails.ratings[child] > 0 {
+                self.stack.push((child, 0));
+            }
+        }
+
+        None
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashSet;
+
+    use super::*;
+    use proptest::prelude::*;
+
+    // low heights so trails are common
+    fn maps() -> impl Strategy<Value = Map> {
+        (1..7usize)
+            .prop_flat_map(|width| {
+                let row = prop::string::string_regex(&format!("[0-3.]{{{}}}", width)).unwrap();
+                prop::collection::vec(row, 1..7)
+            })
+            .prop_map(|rows| rows.join("\n").parse().unwrap())
+    }
+
+    fn steps() -> impl Strategy<Value = Steps> {
+        (1..3u32, any::<bool>(), 1..4u32).prop_map(|(delta, diagonal, max_height)| Steps {
+            delta,
+            diagonal,
+            max_height,
+        })
+    }
+
+    fn height(map: &Map, p: Point) -> Option<u32> {
+        map.height_at(p.y * map.width + p.x)
+    }
+
+    proptest! {
+        #[test]
+        fn ratings_count_scores_ends(map in maps(), steps in steps()) {
+            for th in Trails::new(&map, steps).unwrap().trailheads() {
+                prop_assert!(th.rating >= th.score);
+                prop_assert_eq!(th.rating == 0, th.score == 0);
+            }
+        }
+
+        #[test]
+        fn paths_agree_with_trailheads(map in maps(), steps in steps()) {
+            let trails = Trails::new(&map, steps).unwrap();
+
+            for th in trails.trailheads() {
+                let paths: Vec<_> = trails.paths(th.start).collect();
+                let ends: HashSet<_> = paths.iter().map(|p| p[p.len() - 1]).collect();
+
+                prop_assert_eq!(paths.len(), th.rating);
+                prop_assert_eq!(ends.len(), th.score);
+            }
+        }
+
+        #[test]
+        fn paths_take_valid_steps(map in maps(), steps in steps()) {
+            let trails = Trails::new(&map, steps).unwrap();
+
+            for th in trails.trailheads() {
+                for path in trails.paths(th.start) {
+                    prop_assert_eq!(path[0], th.start);
+                    prop_assert_eq!(height(&map, path[path.len() - 1]), Some(steps.max_height));
+
+                    for w in path.windows(2) {
+                        let dx = w[0].x.abs_diff(w[1].x);
+                        let dy = w[0].y.abs_diff(w[1].y);
+                        let adjacent = dx.max(dy) == 1 && (steps.diagonal || dx + dy == 1);
+
+                        prop_assert!(adjacent, "{:?} to {:?}", w[0], w[1]);
+                        prop_assert_eq!(
+                            height(&map, w[1]),
+                            height(&map, w[0]).map(|h| h + steps.delta)
+                        );
+                    }
+                }
+            }
+        }
+    }
+
+    const SAMPLE: &str = "89010123
+78121874
+87430965
+96549874
+45678903
+32019012
+01329801
+10456732";
+
+    #[test]
+    fn trails_score_each_trailhead() {
+        let map: Map = SAMPLE.parse().unwrap();
+        let trails = Trails::new(&map, Steps::default()).unwrap();
+
+        let scores: Vec<_> = trails.trailheads().iter().map(|t| t.score).collect();
+        let ratings: Vec<_> = trails.trailheads().iter().map(|t| t.rating).collect();
+
+        assert_eq!(scores, vec![5, 6, 5, 3, 1, 3, 5, 3, 5]);
+        assert_eq!(ratings, vec![20, 24, 10, 4, 1, 4, 5, 8, 5]);
+    }
+
+    #[test]
+    fn trails_enumerate_paths() {
+        let map: Map = "0123\n7654\n89..".parse().unwrap();
+        let trails = Trails::new(&map, Steps::default()).unwrap();
+
+        let paths: Vec<_> = trails.paths(Point::new(0, 0)).collect();
+
+        assert_eq!(paths.len(), 1);
+        assert_eq!(paths[0].len(), 10);
+        assert_eq!(paths[0][0], (0, 0));
+        assert_eq!(paths[0][9], (1, 2));
+    }
+
+    #[test]
+    fn trails_paths_match_ratings() {
+        let map: Map = SAMPLE.parse().unwrap();
+        let trails = Trails::new(&map, Steps::default()).unwrap();
+
+        for th in trails.trailheads() {
+            let paths: Vec<_> = trails.paths(th.start).collect();
+            assert_eq!(paths.len(), th.rating);
+
+            for p in paths {
+                assert_eq!(p.len(), 10);
+                assert_eq!(p[0], th.start);
+            }
+        }
+
+        assert_eq!(trails.paths(Point::new(9, 9)).count(), 0);
+    }
+
+    #[test]
+    fn trails_follow_step_rules() {
+        let map: Map = "0.2\n.13".parse().unwrap();
+
+        let straight = Trails::new(
+            &map,
+            Steps {
+                max_height: 3,
+                ..Steps::default()
+            },
+        )
+        .unwrap();
+        assert_eq!(straight.trailheads()[0].rating, 0);
+
+        let diagonal = Trails::new(
+            &map,
+            Steps {
+                diagonal: true,
+                max_height: 3,
+                ..Steps::default()
+            },
+        )
+        .unwrap();
+        assert_eq!(diagonal.trailheads()[0].rating, 1);
+
+        let map: Map = "0246".parse().unwrap();
+        let evens = Trails::new(
+            &map,
+            Steps {
+                delta: 2,
+                max_height: 6,
+                ..Steps::default()
+            },
+        )
+        .unwrap();
+        assert_eq!(evens.paths(Point::new(0, 0)).next().unwrap().len(), 4);
+    }
+}
@@ -0,0 +1,60 @@
+use std::error::Error;
+
+use common::Answer;
+use tree::stones_after;
+
+pub mod tree;
+
+common::params! {
+    #[derive(Debug, Clone)]
+    pub struct Blinks {
+        /// blinks for part 1
+        part1_limit: usize = 25,
+        /// blinks for part 2
+        part2_limit: usize = 75,
+        /// the stones to start with when no input file is given
+        stones: String = "572556 22 0 528 4679021 1 10725 2790".to_string(),
+    }
+}
+
+/// How many stones there are after 25 blinks.
+#[tracing::instrument(skip_all)]
+pub fn part1(input: &str) -> Result<Answer, Box<dyn Error>> {
+    part1_with(input, &Blinks::default())
+}
+
+/// How many stones there are after `part1_limit` blinks.
+pub fn part1_with(input: &str, blinks: &Blinks) -> Result<Answer, Box<dyn Error>> {
+    Ok(stones_after(input, blinks.part1_limit)?.into())
+}
+
+/// How many stones there are after 75 blinks.
+#[tracing::instrument(skip_all)]
+pub fn part2(input: &str) -> Result<Answer, Box<dyn Error>> {
+    part2_with(input, &Blinks::default())
+}
+
+/// How many stones there are after `part2_limit` blinks.
+pub fn part2_with(input: &str, blinks: &Blinks) -> Result<Answer, Box<dyn Error>> {
+    Ok(stones_after(input, blinks.part2_limit)?.into())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn solves_sample() {
+        assert_eq!(part1("125 17\n").unwrap(), Answer::Int(55312));
+    }
+
+    #[test]
+    fn blinks_can_change() {
+        let blinks = Blinks {
+            part1_limit: 6,
+            ..Blinks::default()
+        };
+
+        assert_eq!(part1_with("125 17\n", &blinks).unwrap(), Answer::Int(22));
+    }
+}
@@ -1,11 +1,27 @@
-use tree::part2;
+use std::{env, error::Error, fs, path::Path};
 
-mod tree;
+use blink::Blinks;
 
-fn main() {
-    let count = part2("572556 22 0 528 4679021 1 10725 2790", 25);
-    println!("pt1 stones: {}", count);
+fn main() -> Result<(), Box<dyn Error>> {
+    let _day = common::trace::init("blink");
 
-    let count = part2("572556 22 0 528 4679021 1 10725 2790", 75);
-    println!("pt2 stones: {}", count);
+    let args: Vec<String> = env::args().collect();
+    let (blinks, args) =
+        common::params::load::<Blinks>(Path::new(env!("CARGO_MANIFEST_DIR")), &args)?;
+
+    let input = match args.get(1) {
+        Some(filename) => fs::read_to_string(filename)?,
+        None => blinks.stones.clone(),
+    };
+
+    if common::answer::for_tools() {
+        common::answer::print(1, &blink::part1_with(&input, &blinks)?);
+        common::answer::print(2, &blink::part2_with(&input, &blinks)?);
+        return Ok(());
+    }
+
+    println!("pt1 stones: {}", blink::part1_with(&input, &blinks)?);
+    println!("pt2 stones: {}", blink::part2_with(&input, &blinks)?);
+
+    Ok(())
 }
@@ -1,8 +1,15 @@
 use std::collections::HashMap;
 
-pub fn part2(s: &str, limit: usize) -> usize {
-    let stones = parse_stones(s);
-    count_until(&stones, limit)
+use common::{
+    math::split_digits,
+    parse::{numbers, parse, ParseError},
+};
+use nom::{character::complete::multispace0, sequence::preceded};
+
+/// How many stones there are after blinking `limit` times.
+pub fn stones_after(s: &str, limit: usize) -> Result<usize, ParseError> {
+    let stones = parse_stones(s)?;
+    Ok(count_until(&stones, limit))
 }
 
 #[derive(Debug, PartialEq, Eq)]
@@ -58,30 +65,7 @@ impl Stone {
     }
 
     fn split(&self) -> Option<(Stone, Stone)> {
-        let mut digits = Vec::new();
-
-        let mut value = self.value;
-
-        while value > 0 {
-            digits.push(value % 10);
-            value /= 10;
-        }
-
-        let (d2, d1) = digits.split_at(digits.len() / 2);
-
-        if d1.len() != d2.len() {
-            return None;
-        }
-
-        let d1 = d1
-            .into_iter()
-            .enumerate()
-            .fold(0, |acc, (i, d)| acc + d * 10u64.pow(i as u32));
-
-        let d2 = d2
-            .into_iter()
-            .enumerate()
-            .fold(0, |acc, (i, d)| acc + d * 10u64.pow(i as u32));
+        let (d1, d2) = split_digits(self.value)?;
 
         Some((
             Stone::new(d1, self.depth + 1),
@@ -90,17 +74,17 @@ impl Stone {
     }
 }
 
-pub fn count_until(stones: &Vec<Stone>, limit: usize) -> usize {
+pub fn count_until(stones: &[Stone], limit: usize) -> usize {
     let mut cache = HashMap::new();
 
     stones.iter().map(|s| s.count(limit, &mut cache)).sum()
 }
 
-pub fn parse_stones(s: &str) -> Vec<Stone> {
-    s.split(" ")
-        .filter_map(|s| u64::from_str_radix(s, 10).ok())
-        .map(|v| Stone::new(v, 0))
-        .collect()
+/// The numbers engraved on each stone, separated by spaces.
+pub fn parse_stones(s: &str) -> Result<Vec<Stone>, ParseError> {
+    let stones = parse(s, preceded(multispace0, numbers))?;
+
+    Ok(stones.into_iter().map(|v| Stone::new(v, 0)).collect())
 }
 
 #[cfg(test)]
@@ -127,8 +111,14 @@ mod tests {
 
     #[test]
     fn stone_counts_until() {
-        let stones = parse_stones("125 17");
+        let stones = parse_stones("125 17").unwrap();
         let count = count_until(&stones, 6);
         assert_eq!(count, 22);
     }
+
+    #[test]
+    fn stones_must_be_numbers() {
+        let error = parse_stones("125 17 -3").unwrap_err();
+        assert_eq!(error.to_string(), "line 1, column 8: expected a number");
+    }
 }
@@ -0,0 +1,72 @@
+/// A puzzle crate, kept at `<year>/<day>` from the repository root.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Day {
+    pub year: u16,
+    pub day: u8,
+    pub name: &'static str,
+}
+
+impl Day {
+    pub const fn new(year: u16, day: u8, name: &'static str) -> Self {
+        Self { year, day, name }
+    }
+
+    pub fn path(&self) -> String {
+        path(self.year, self.day)
+    }
+}
+
+/// Where a day lives from the repository root, whether it's registered or not.
+pub fn path(year: u16, day: u8) -> String {
+    format!("{}/{:02}", year, day)
+}
+
+// `aoc new` adds to this list, so keep it one day per line and in order
+pub const DAYS: &[Day] = &[
+    Day::new(2023, 1, "trebuchet"),
+    Day::new(2023, 2, "cube"),
+    Day::new(2023, 3, "gear"),
+    Day::new(2023, 4, "scratch"),
+    Day::new(2023, 5, "seed"),
+    Day::new(2023, 6, "wait"),
+    Day::new(2023, 7, "camel"),
+    Day::new(2023, 8, "wasteland"),
+    Day::new(2023, 9, "mirage"),
+    Day::new(2023, 10, "pipes"),
+    Day::new(2023, 11, "cosmic"),
+    Day::new(2024, 1, "hysteria"),
+    Day::new(2024, 2, "reports"),
+    Day::new(2024, 3, "mull"),
+    Day::new(2024, 4, "search"),
+    Day::new(2024, 5, "print"),
+    Day::new(2024, 6, "guard"),
+    Day::new(2024, 7, "bridge"),
+    Day::new(2024, 8, "resonant"),
+    Day::new(2024, 9, "frag"),
+    Day::new(2024, 10, "hoof"),
+    Day::new(2024, 11, "blink"),
+    Day::new(2024, 12, "garden"),
+];
+
+pub fn find(year: u16, day: u8) -> Option<&'static Day> {
+    DAYS.iter().find(|d| d.year == year && d.day == day)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn days_are_in_order() {
+        assert!(DAYS
+            .windows(2)
+            .all(|w| (w[0].year, w[0].day) < (w[1].year, w[1].day)));
+    }
+
+    #[test]
+    fn days_are_found() {
+        assert_eq!(find(2023, 7).map(|d| d.name), Some("camel"));
+        assert_eq!(find(2023, 7).unwrap().path(), "2023/07");
+        assert_eq!(find(2022, 1), None);
+    }
+}
@@ -0,0 +1,120 @@
+use std::{
+    error::Error,
+    fs,
+    io::Write,
+    path::PathBuf,
+    process::{Command, Stdio},
+};
+
+use crate::days;
+
+/// What can be downloaded for a day.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Resource {
+    Input,
+    /// the puzzle description page
+    Puzzle,
+}
+
+impl Resource {
+    /// The file it's kept in inside a day's directory.
+    pub fn file_name(&self) -> &'static str {
+        match self {
+            Resource::Input => "input.txt",
+            Resource::Puzzle => "puzzle.html",
+        }
+    }
+}
+
+pub trait Fetcher {
+    fn fetch(&self, year: u16, day: u8, resource: Resource) -> Result<String, Box<dyn Error>>;
+}
+
+/// Sent with every request, so the site knows who's asking.
+const USER_AGENT: &str = "github.com/andrewschmidgit/advent-of-code aoc runner";
+
+/// adventofcode.com, logged in with the `session` cookie. Goes through `curl`
+/// so the runner doesn't need a TLS stack.
+pub struct Site {
+    pub session: String,
+}
+
+impl Site {
+    // curl options read from its stdin, keeping the cookie out of `ps`
+    fn config(&self) -> String {
+        format!(
+            "cookie = \"session={}\"\nuser-agent = \"{}\"\n",
+            quoted(&self.session),
+            quoted(USER_AGENT)
+        )
+    }
+}
+
+impl Fetcher for Site {
+    fn fetch(&self, year: u16, day: u8, resource: Resource) -> Result<String, Box<dyn Error>> {
+        let mut url = format!("https://adventofcode.com/{}/day/{}", year, day);
+        if resource == Resource::Input {
+            url.push_str("/input");
+        }
+
+        let mut curl = Command::new("curl")
+            .args(["--silent", "--fail", "--config", "-"])
+            .arg(&url)
+            .stdin(Stdio::piped())
+            .stdout(Stdio::piped())
+            .spawn()?;
+
+        if let Some(mut stdin) = curl.stdin.take() {
+            stdin.write_all(self.config().as_bytes())?;
+        }
+
+        let output = curl.wait_with_output()?;
+
+        if !output.status.success() {
+            return Err(format!("could not fetch {} ({})", url, output.status).into());
+        }
+
+        Ok(String::from_utf8(output.stdout)?)
+    }
+}
+
+fn quoted(s: &str) -> String {
+    s.replace('\\', "\\\\").replace('"', "\\\"")
+}
+
+/// A directory laid out like the repository, `<year>/<day>/input.txt` and
+/// `<year>/<day>/puzzle.html`. Stands in for the site in tests and offline.
+pub struct Mirror {
+    pub dir: PathBuf,
+}
+
+impl Fetcher for Mirror {
+    fn fetch(&self, year: u16, day: u8, resource: Resource) -> Result<String, Box<dyn Error>> {
+        let path = self
+            .dir
+            .join(days::path(year, day))
+            .join(resource.file_name());
+
+        fs::read_to_string(&path).map_err(|e| format!("{}: {}", path.display(), e).into())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn site_keeps_session_off_the_command_line() {
+        let site = Site {
+            session: "ab\"c\\".into(),
+        };
+
+        assert_eq!(
+            site.config(),
+            format!(
+                "cookie = \"session=ab\\\"c\\\\\"\nuser-agent = \"{}\"\n",
+                USER_AGENT
+            )
+        );
+    }
+}
@@ -0,0 +1,51 @@
+use std::{fmt::Display, str::FromStr};
+
+use crate::shrink::Form;
+
+// the days whose input, or each line of it, parses to something that prints
+// back, as the fuzz targets check
+const FORMS: &[(u16, u8, Form)] = &[
+    (2023, 2, lines::<cube::Game>),
+    (2023, 3, whole::<gear::Grid>),
+    (2023, 4, lines::<scratch::Card>),
+    (2023, 8, whole::<wasteland::Map>),
+    (2023, 9, lines::<mirage::History>),
+    (2023, 10, whole::<pipes::Grid<pipes::Pipe>>),
+    (2024, 2, lines::<reports::Report>),
+    (2024, 4, whole::<search::Grid<char>>),
+    (2024, 7, lines::<bridge::Equation>),
+    (2024, 8, whole::<resonant::Grid>),
+    (2024, 10, whole::<hoof::Map>),
+];
+
+/// How a day's parser reprints its input, if it can.
+pub fn find(year: u16, day: u8) -> Option<Form> {
+    FORMS
+        .iter()
+        .find(|(y, d, _)| *y == year && *d == day)
+        .map(|(_, _, form)| *form)
+}
+
+fn whole<T: FromStr + Display>(s: &str) -> Option<String> {
+    Some(s.parse::<T>().ok()?.to_string())
+}
+
+fn lines<T: FromStr + Display>(s: &str) -> Option<String> {
+    s.lines()
+        .map(|l| Some(l.parse::<T>().ok()?.to_string() + "\n"))
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn forms_reprint_inputs() {
+        let reports = find(2024, 2).unwrap();
+
+        assert_eq!(reports("1 2 3\n7 6\n").as_deref(), Some("1 2 3\n7 6\n"));
+        assert_eq!(reports("1 2 3\nseven\n"), None);
+        assert!(find(2024, 9).is_none());
+    }
+}
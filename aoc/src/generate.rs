@@ -0,0 +1,186 @@
+use std::ops::RangeInclusive;
+
+mod y2023;
+mod y2024;
+
+/// SplitMix64. Small, fast and the same on every platform, so a seed always
+/// gives the same input.
+#[derive(Debug, Clone)]
+pub struct Rng(u64);
+
+impl Rng {
+    pub fn new(seed: u64) -> Self {
+        Self(seed)
+    }
+
+    pub fn next_u64(&mut self) -> u64 {
+        self.0 = self.0.wrapping_add(0x9e37_79b9_7f4a_7c15);
+
+        let mut z = self.0;
+        z = (z ^ (z >> 30)).wrapping_mul(0xbf58_476d_1ce4_e5b9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94d0_49bb_1331_11eb);
+        z ^ (z >> 31)
+    }
+
+    /// Somewhere in `0..n`. Panics when `n` is 0.
+    pub fn below(&mut self, n: usize) -> usize {
+        (self.next_u64() % n as u64) as usize
+    }
+
+    pub fn range(&mut self, range: RangeInclusive<usize>) -> usize {
+        range.start() + self.below(range.end() - range.start() + 1)
+    }
+
+    /// True with probability `p`.
+    pub fn chance(&mut self, p: f64) -> bool {
+        ((self.next_u64() >> 11) as f64 / (1u64 << 53) as f64) < p
+    }
+
+    pub fn pick<T: Copy>(&mut self, items: &[T]) -> T {
+        items[self.below(items.len())]
+    }
+
+    /// One of the characters in `chars`, which should be ASCII.
+    pub fn char_from(&mut self, chars: &str) -> char {
+        chars.as_bytes()[self.below(chars.len())] as char
+    }
+
+    pub fn shuffle<T>(&mut self, items: &mut [T]) {
+        for i in (1..items.len()).rev() {
+            items.swap(i, self.below(i + 1));
+        }
+    }
+}
+
+/// Makes inputs for a day, as big as asked for.
+#[derive(Debug, Clone, Copy)]
+pub struct Generator {
+    pub year: u16,
+    pub day: u8,
+    /// what the size counts
+    pub size: &'static str,
+    /// about the size of a real input
+    pub default_size: usize,
+    build: fn(&mut Rng, usize) -> String,
+}
+
+impl Generator {
+    const fn new(
+        year: u16,
+        day: u8,
+        size: &'static str,
+        default_size: usize,
+        build: fn(&mut Rng, usize) -> String,
+    ) -> Self {
+        Self {
+            year,
+            day,
+            size,
+            default_size,
+            build,
+        }
+    }
+
+    pub fn generate(&self, seed: u64, size: usize) -> String {
+        (self.build)(&mut Rng::new(seed), size)
+    }
+}
+
+pub const GENERATORS: &[Generator] = &[
+    Generator::new(2023, 1, "lines", 1000, y2023::trebuchet),
+    Generator::new(2023, 2, "games", 100, y2023::cube),
+    Generator::new(2023, 3, "side", 140, y2023::gear),
+    Generator::new(2023, 4, "cards", 200, y2023::scratch),
+    Generator::new(2023, 5, "ranges per map", 40, y2023::seed),
+    Generator::new(2023, 6, "races", 4, y2023::wait),
+    Generator::new(2023, 7, "hands", 1000, y2023::camel),
+    Generator::new(2023, 8, "nodes", 750, y2023::wasteland),
+    Generator::new(2023, 9, "histories", 200, y2023::mirage),
+    Generator::new(2023, 10, "side", 140, y2023::pipes),
+    Generator::new(2024, 1, "pairs", 1000, y2024::hysteria),
+    Generator::new(2024, 2, "reports", 1000, y2024::reports),
+    Generator::new(2024, 3, "instructions", 700, y2024::mull),
+    Generator::new(2024, 4, "side", 140, y2024::search),
+    Generator::new(2024, 5, "updates", 200, y2024::print),
+    Generator::new(2024, 6, "side", 130, y2024::guard),
+    Generator::new(2024, 7, "operands", 12, y2024::bridge),
+    Generator::new(2024, 8, "side", 50, y2024::resonant),
+    Generator::new(2024, 9, "entries", 19999, y2024::frag),
+    Generator::new(2024, 10, "side", 50, y2024::hoof),
+    Generator::new(2024, 11, "stones", 8, y2024::blink),
+    Generator::new(2024, 12, "side", 140, y2024::garden),
+];
+
+pub fn find(year: u16, day: u8) -> Option<&'static Generator> {
+    GENERATORS.iter().find(|g| g.year == year && g.day == day)
+}
+
+fn lines(count: usize, mut line: impl FnMut(usize) -> String) -> String {
+    (0..count).map(|i| line(i) + "\n").collect()
+}
+
+fn grid(side: usize, mut cell: impl FnMut(usize, usize) -> char) -> String {
+    lines(side, |y| (0..side).map(|x| cell(x, y)).collect())
+}
+
+fn join<T: ToString>(items: &[T], separator: &str) -> String {
+    items
+        .iter()
+        .map(T::to_string)
+        .collect::<Vec<_>>()
+        .join(separator)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn generators_are_reproducible() {
+        for g in GENERATORS {
+            for size in [1, 2, 10] {
+                let input = g.generate(7, size);
+
+                assert!(!input.trim().is_empty(), "{} day {}", g.year, g.day);
+                assert_eq!(input, g.generate(7, size), "{} day {}", g.year, g.day);
+            }
+        }
+    }
+
+    #[test]
+    fn generators_follow_the_seed() {
+        for g in GENERATORS {
+            assert_ne!(
+                g.generate(1, g.default_size),
+                g.generate(2, g.default_size),
+                "{} day {}",
+                g.year,
+                g.day
+            );
+        }
+    }
+
+    #[test]
+    fn generators_are_for_registered_days() {
+        assert!(GENERATORS
+            .windows(2)
+            .all(|w| (w[0].year, w[0].day) < (w[1].year, w[1].day)));
+        assert!(GENERATORS
+            .iter()
+            .all(|g| crate::days::find(g.year, g.day).is_some()));
+        assert!(find(2023, 11).is_none());
+    }
+
+    #[test]
+    fn rng_stays_in_range() {
+        let mut rng = Rng::new(0);
+
+        assert!((0..1000).all(|_| (3..=5).contains(&rng.range(3..=5))));
+        assert!((0..1000).all(|_| rng.below(1) == 0));
+
+        let mut items: Vec<_> = (0..20).collect();
+        rng.shuffle(&mut items);
+        items.sort();
+        assert_eq!(items, (0..20).collect::<Vec<_>>());
+    }
+}
@@ -0,0 +1,522 @@
+use super::{grid, join, lines, Rng};
+
+const DIGIT_WORDS: &[&str] = &[
+    "one", "two", "three", "four", "five", "six", "seven", "eight", "nine",
+];
+
+/// Letters with digits and spelled out digits mixed in. Every line has at
+/// least one plain digit so part 1 can read it.
+pub fn trebuchet(rng: &mut Rng, size: usize) -> String {
+    lines(size, |_| {
+        let len = rng.range(5..=40);
+        let mut line = String::new();
+
+        while line.len() < len {
+            match rng.below(10) {
+                0 => line.push_str(rng.pick(DIGIT_WORDS)),
+                1 | 2 => line.push(rng.char_from("123456789")),
+                _ => line.push(rng.char_from("abcdefghijklmnopqrstuvwxyz")),
+            }
+        }
+
+        if !line.chars().any(|c| c.is_ascii_digit()) {
+            let at = rng.below(line.len() + 1);
+            line.insert(at, rng.char_from("123456789"));
+        }
+
+        line
+    })
+}
+
+pub fn cube(rng: &mut Rng, size: usize) -> String {
+    lines(size, |i| {
+        let sets: Vec<_> = (0..rng.range(1..=6))
+            .map(|_| {
+                let mut colours = ["red", "green", "blue"];
+                rng.shuffle(&mut colours);
+
+                let picked = &colours[..rng.range(1..=3)];
+                let cubes: Vec<_> = picked
+                    .iter()
+                    .map(|c| format!("{} {}", rng.range(1..=20), c))
+                    .collect();
+
+                cubes.join(", ")
+            })
+            .collect();
+
+        format!("Game {}: {}", i + 1, sets.join("; "))
+    })
+}
+
+/// Part numbers and symbols on a square schematic. Numbers on a row always
+/// have something else between them.
+pub fn gear(rng: &mut Rng, size: usize) -> String {
+    lines(size, |_| {
+        let mut row = String::with_capacity(size);
+        let mut after_number = false;
+
+        while row.len() < size {
+            let number = rng.range(1..=999).to_string();
+
+            match rng.below(12) {
+                0 => row.push(rng.char_from("*#+$/=@%&-*")),
+                1 | 2 if !after_number && number.len() <= size - row.len() => {
+                    row.push_str(&number);
+                    after_number = true;
+                    continue;
+                }
+                _ => row.push('.'),
+            }
+
+            after_number = false;
+        }
+
+        row
+    })
+}
+
+/// Ten winning numbers and twenty five of yours. Wins are few, like the
+/// puzzle's, so the copies won don't grow without bound, and never reach past
+/// the last card.
+pub fn scratch(rng: &mut Rng, size: usize) -> String {
+    lines(size, |i| {
+        let mut wins = 0;
+        while wins < 10.min(size - i - 1) && rng.chance(0.45) {
+            wins += 1;
+        }
+
+        let mut numbers: Vec<usize> = (1..100).collect();
+        rng.shuffle(&mut numbers);
+
+        let winning = &numbers[..10];
+        let mut yours: Vec<_> = winning[..wins]
+            .iter()
+            .chain(&numbers[10..35 - wins])
+            .copied()
+            .collect();
+        rng.shuffle(&mut yours);
+
+        let column = |ns: &[usize]| {
+            let ns: Vec<_> = ns.iter().map(|n| format!("{:>2}", n)).collect();
+            ns.join(" ")
+        };
+
+        format!(
+            "Card {:>3}: {} | {}",
+            i + 1,
+            column(winning),
+            column(&yours)
+        )
+    })
+}
+
+const SEED_MAPS: [&str; 7] = [
+    "seed-to-soil",
+    "soil-to-fertilizer",
+    "fertilizer-to-water",
+    "water-to-light",
+    "light-to-temperature",
+    "temperature-to-humidity",
+    "humidity-to-location",
+];
+
+const ALMANAC_SPAN: usize = 4_000_000_000;
+
+/// Five seed ranges, then every map cuts the numbers up into `size` pieces and
+/// lays them out again in a shuffled order. A few pieces are left out so
+/// they keep their numbers.
+pub fn seed(rng: &mut Rng, size: usize) -> String {
+    let seeds: Vec<_> = (0..5)
+        .map(|_| {
+            let start = rng.below(ALMANAC_SPAN);
+            let len = rng.range(1..=(ALMANAC_SPAN - start).min(ALMANAC_SPAN / 20));
+
+            format!("{} {}", start, len)
+        })
+        .collect();
+
+    let mut almanac = format!("seeds: {}\n", seeds.join(" "));
+
+    for name in SEED_MAPS {
+        let mut cuts: Vec<_> = (1..size).map(|_| rng.below(ALMANAC_SPAN)).collect();
+        cuts.extend([0, ALMANAC_SPAN]);
+        cuts.sort();
+        cuts.dedup();
+
+        let mut pieces: Vec<_> = cuts.windows(2).map(|w| (w[0], w[1] - w[0])).collect();
+        rng.shuffle(&mut pieces);
+
+        let mut dest = 0;
+        let mut mappings = vec![];
+        for (src, len) in pieces {
+            mappings.push((dest, src, len));
+            dest += len;
+        }
+        rng.shuffle(&mut mappings);
+
+        almanac.push_str(&format!("\n{} map:\n", name));
+        for (dest, src, len) in mappings {
+            if rng.chance(0.9) {
+                almanac.push_str(&format!("{} {} {}\n", dest, src, len));
+            }
+        }
+    }
+
+    almanac
+}
+
+/// Every race can be won. Part 2 reads all of them as one number, so past a
+/// handful of races it no longer fits.
+pub fn wait(rng: &mut Rng, size: usize) -> String {
+    let (times, distances): (Vec<_>, Vec<_>) = (0..size)
+        .map(|_| {
+            let time = rng.range(7..=99);
+            let best = (time / 2) * (time - time / 2);
+
+            (time, rng.range(best / 2..=best - 1))
+        })
+        .unzip();
+
+    let column = |ns: &[usize]| -> String { ns.iter().map(|n| format!("{:>6}", n)).collect() };
+
+    format!(
+        "Time:    {}\nDistance:{}\n",
+        column(&times),
+        column(&distances)
+    )
+}
+
+const CAMEL_FACES: &str = "23456789TJQKA";
+
+/// Each hand is dealt from a few faces, so pairs and better turn up.
+pub fn camel(rng: &mut Rng, size: usize) -> String {
+    lines(size, |_| {
+        let faces: String = (0..rng.range(1..=5))
+            .map(|_| rng.char_from(CAMEL_FACES))
+            .collect();
+        let hand: String = (0..5).map(|_| rng.char_from(&faces)).collect();
+
+        format!("{} {}", hand, rng.range(1..=1000))
+    })
+}
+
+const GHOST_LOOPS: [usize; 9] = [43, 47, 53, 59, 61, 67, 71, 73, 79];
+
+/// Six ghost paths, one of them `AAA` to `ZZZ`, with about `size` nodes between
+/// them. Each path runs from its `A` node to its `Z` node and then loops back
+/// to just after the `A` node, in a multiple of the directions' length like
+/// the puzzle's. Both of a node's children are the next node on its path.
+pub fn wasteland(rng: &mut Rng, size: usize) -> String {
+    let mut loops = GHOST_LOOPS;
+    rng.shuffle(&mut loops);
+    let loops = &loops[..6];
+
+    let turns = (size / loops.iter().sum::<usize>()).max(1);
+    let directions: String = (0..turns).map(|_| rng.char_from("LR")).collect();
+
+    let count: usize = loops.iter().map(|l| l * turns + 1).sum();
+    let mut width = 2;
+    while 24usize.pow(width) < count {
+        width += 1;
+    }
+
+    // names that can't be mistaken for a start or an end, in a random order
+    let mut ids: Vec<_> = (0..count).collect();
+    rng.shuffle(&mut ids);
+    let mut ids = ids.into_iter();
+    let mut name = |rng: &mut Rng, last: char| -> String {
+        let mut id = ids.next().expect("a name per node");
+        let mut s: String = (0..width)
+            .map(|_| {
+                let c = (b'B' + (id % 24) as u8) as char;
+                id /= 24;
+                c
+            })
+            .collect();
+
+        s.push(match last {
+            '?' => rng.char_from("BCDEFGHIJKLMNOPQRSTUVWXY"),
+            last => last,
+        });
+        s
+    };
+
+    let mut nodes = vec![];
+    for (i, l) in loops.iter().enumerate() {
+        let mut path = vec![name(rng, 'A')];
+        path.extend((1..l * turns).map(|_| name(rng, '?')));
+        path.push(name(rng, 'Z'));
+
+        if i == 0 {
+            path[0] = "AAA".into();
+            path[l * turns] = "ZZZ".into();
+        }
+
+        for w in path.windows(2) {
+            nodes.push(format!("{} = ({}, {})", w[0], w[1], w[1]));
+        }
+        nodes.push(format!("{} = ({}, {})", path[l * turns], path[1], path[1]));
+    }
+
+    rng.shuffle(&mut nodes);
+
+    format!("{}\n\n{}\n", directions, nodes.join("\n"))
+}
+
+/// Values of polynomials of low degree, so every history bottoms out in zeros.
+pub fn mirage(rng: &mut Rng, size: usize) -> String {
+    lines(size, |_| {
+        let coefficients: Vec<i64> = (0..=rng.range(1..=6))
+            .map(|_| rng.range(0..=20) as i64 - 10)
+            .collect();
+        let start = rng.range(0..=10) as i64 - 5;
+
+        let values: Vec<_> = (start..start + 21)
+            .map(|x| coefficients.iter().fold(0, |acc, c| acc * x + c))
+            .collect();
+
+        join(&values, " ")
+    })
+}
+
+const NORTH: u8 = 1;
+const EAST: u8 = 2;
+const SOUTH: u8 = 4;
+const WEST: u8 = 8;
+
+/// A single loop with junk pipes everywhere else. The loop goes around a
+/// random tree of 2x2 blocks, which visits every tile of the blocks, and is
+/// then drawn at twice the scale so it leaves tiles inside it.
+pub fn pipes(rng: &mut Rng, size: usize) -> String {
+    let side = size.max(3);
+    let blocks = (side + 1) / 4;
+    let (right, down) = spanning_tree(rng, blocks);
+
+    // the loop through the tiles of the blocks, as the directions it leaves
+    // each tile in
+    let fine = blocks * 2;
+    let mut exits = vec![vec![0u8; fine]; fine];
+    let mut link = |(x, y): (usize, usize), exit: u8| {
+        exits[y][x] |= exit;
+        match exit {
+            EAST => exits[y][x + 1] |= WEST,
+            _ => exits[y + 1][x] |= NORTH,
+        }
+    };
+
+    for by in 0..blocks {
+        for bx in 0..blocks {
+            let (x, y) = (bx * 2, by * 2);
+
+            if bx == 0 || !right[by][bx - 1] {
+                link((x, y), SOUTH);
+            }
+            if by == 0 || !down[by - 1][bx] {
+                link((x, y), EAST);
+            }
+            match right[by][bx] {
+                true => {
+                    link((x + 1, y), EAST);
+                    link((x + 1, y + 1), EAST);
+                }
+                false => link((x + 1, y), SOUTH),
+            }
+            match down[by][bx] {
+                true => {
+                    link((x, y + 1), SOUTH);
+                    link((x + 1, y + 1), SOUTH);
+                }
+                false => link((x, y + 1), EAST),
+            }
+        }
+    }
+
+    let mut tiles = vec![vec![None; side]; side];
+    for (y, row) in exits.iter().enumerate() {
+        for (x, exit) in row.iter().enumerate() {
+            tiles[2 * y][2 * x] = Some(pipe(*exit));
+            if exit & EAST != 0 {
+                tiles[2 * y][2 * x + 1] = Some('-');
+            }
+            if exit & SOUTH != 0 {
+                tiles[2 * y + 1][2 * x] = Some('|');
+            }
+        }
+    }
+
+    let (sx, sy) = (2 * rng.below(fine), 2 * rng.below(fine));
+    tiles[sy][sx] = Some('S');
+
+    // nothing off the loop may look like it connects to the start
+    let around = [
+        (sx + 1, sy),
+        (sx, sy + 1),
+        (sx.wrapping_sub(1), sy),
+        (sx, sy.wrapping_sub(1)),
+    ];
+    for (x, y) in around {
+        if let Some(tile @ None) = tiles.get_mut(y).and_then(|row| row.get_mut(x)) {
+            *tile = Some('.');
+        }
+    }
+
+    grid(side, |x, y| {
+        tiles[y][x].unwrap_or_else(|| rng.char_from("|-LJ7F."))
+    })
+}
+
+fn pipe(exits: u8) -> char {
+    match exits {
+        e if e == NORTH | SOUTH => '|',
+        e if e == EAST | WEST => '-',
+        e if e == NORTH | EAST => 'L',
+        e if e == NORTH | WEST => 'J',
+        e if e == SOUTH | WEST => '7',
+        _ => 'F',
+    }
+}
+
+/// A random spanning tree of a square of `side` cells, as whether each cell is
+/// joined to the one to its right and the one below it.
+fn spanning_tree(rng: &mut Rng, side: usize) -> (Vec<Vec<bool>>, Vec<Vec<bool>>) {
+    let mut right = vec![vec![false; side]; side];
+    let mut down = vec![vec![false; side]; side];
+    let mut seen = vec![vec![false; side]; side];
+
+    let mut frontier = vec![((0, 0), (0, 0))];
+    while !frontier.is_empty() {
+        let ((fx, fy), (x, y)) = frontier.swap_remove(rng.below(frontier.len()));
+        if seen[y][x] {
+            continue;
+        }
+        seen[y][x] = true;
+
+        if fx + 1 == x {
+            right[fy][fx] = true;
+        } else if x + 1 == fx {
+            right[y][x] = true;
+        } else if fy + 1 == y {
+            down[fy][fx] = true;
+        } else if y + 1 == fy {
+            down[y][x] = true;
+        }
+
+        if x + 1 < side {
+            frontier.push(((x, y), (x + 1, y)));
+        }
+        if x > 0 {
+            frontier.push(((x, y), (x - 1, y)));
+        }
+        if y + 1 < side {
+            frontier.push(((x, y), (x, y + 1)));
+        }
+        if y > 0 {
+            frontier.push(((x, y), (x, y - 1)));
+        }
+    }
+
+    (right, down)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // the neighbours a tile connects to, as offsets
+    fn connects(c: char) -> Vec<(isize, isize)> {
+        match c {
+            '|' => vec![(0, -1), (0, 1)],
+            '-' => vec![(-1, 0), (1, 0)],
+            'L' => vec![(0, -1), (1, 0)],
+            'J' => vec![(0, -1), (-1, 0)],
+            '7' => vec![(0, 1), (-1, 0)],
+            'F' => vec![(0, 1), (1, 0)],
+            _ => vec![],
+        }
+    }
+
+    #[test]
+    fn pipes_have_a_single_loop_through_the_start() {
+        for (seed, size) in [(0, 3), (1, 4), (2, 9), (3, 40), (4, 141)] {
+            let input = pipes(&mut Rng::new(seed), size);
+            let tiles: Vec<Vec<char>> = input.lines().map(|l| l.chars().collect()).collect();
+            let at = |(x, y): (isize, isize)| {
+                tiles
+                    .get(y as usize)
+                    .and_then(|row| row.get(x as usize))
+                    .copied()
+                    .unwrap_or('.')
+            };
+
+            assert_eq!(tiles.len(), size.max(3));
+            let start = (0..tiles.len() as isize)
+                .flat_map(|y| (0..tiles.len() as isize).map(move |x| (x, y)))
+                .find(|p| at(*p) == 'S')
+                .unwrap();
+
+            let exits: Vec<_> = [(0, -1), (1, 0), (0, 1), (-1, 0)]
+                .into_iter()
+                .map(|(dx, dy)| (start.0 + dx, start.1 + dy))
+                .filter(|n| {
+                    connects(at(*n))
+                        .iter()
+                        .any(|(dx, dy)| (n.0 + dx, n.1 + dy) == start)
+                })
+                .collect();
+            assert_eq!(exits.len(), 2, "seed {}", seed);
+
+            let (mut previous, mut current) = (start, exits[0]);
+            let mut steps = 1;
+            while current != start {
+                let next = connects(at(current))
+                    .into_iter()
+                    .map(|(dx, dy)| (current.0 + dx, current.1 + dy))
+                    .find(|n| *n != previous)
+                    .unwrap();
+
+                (previous, current) = (current, next);
+                steps += 1;
+                assert!(steps <= size * size, "seed {}", seed);
+            }
+
+            assert_eq!(previous, exits[1]);
+        }
+    }
+
+    #[test]
+    fn seed_maps_never_overlap() {
+        let input = seed(&mut Rng::new(3), 50);
+
+        for map in input.split("\n\n").skip(1) {
+            let mut src: Vec<_> = map
+                .lines()
+                .skip(1)
+                .map(|l| {
+                    let ns: Vec<usize> = l.split(' ').map(|n| n.parse().unwrap()).collect();
+                    (ns[1], ns[2])
+                })
+                .collect();
+            src.sort();
+
+            assert!(src.windows(2).all(|w| w[0].0 + w[0].1 <= w[1].0));
+        }
+    }
+
+    #[test]
+    fn wasteland_names_are_unique() {
+        let input = wasteland(&mut Rng::new(5), 1000);
+        let mut names: Vec<_> = input
+            .lines()
+            .skip(2)
+            .map(|l| &l[..l.find(' ').unwrap()])
+            .collect();
+        let count = names.len();
+        names.sort();
+        names.dedup();
+
+        assert_eq!(names.len(), count);
+        assert!(names.contains(&"AAA") && names.contains(&"ZZZ"));
+        assert_eq!(names.iter().filter(|n| n.ends_with('A')).count(), 6);
+    }
+}
@@ -0,0 +1,345 @@
+use std::collections::VecDeque;
+
+use super::{grid, join, lines, Rng};
+
+/// Two columns of five digit ids, with the right one often repeating ids from
+/// the left so similarity scores aren't all zero.
+pub fn hysteria(rng: &mut Rng, size: usize) -> String {
+    let mut left = vec![];
+
+    lines(size, |_| {
+        let id = rng.range(10000..=99999);
+        left.push(id);
+
+        let other = match rng.chance(0.3) {
+            true => rng.pick(&left),
+            false => rng.range(10000..=99999),
+        };
+
+        format!("{}   {}", id, other)
+    })
+}
+
+/// About half the reports are safe. The rest have one bad step somewhere,
+/// which the dampener may or may not save.
+pub fn reports(rng: &mut Rng, size: usize) -> String {
+    lines(size, |_| {
+        let len = rng.range(5..=8);
+        let bad = match rng.chance(0.5) {
+            true => rng.range(1..=len - 1),
+            false => 0,
+        };
+        let sign = match rng.chance(0.5) {
+            true => 1,
+            false => -1,
+        };
+
+        let mut level = rng.range(40..=60) as i32;
+        let mut levels = vec![level];
+        for i in 1..len {
+            let step = match i == bad {
+                true => rng.pick(&[0, 4, 5, -2]),
+                false => rng.range(1..=3) as i32,
+            };
+
+            level += sign * step;
+            levels.push(level);
+        }
+
+        join(&levels, " ")
+    })
+}
+
+const CORRUPTED: &[&str] = &[
+    "mul(4*",
+    "mul[3,7]",
+    "mul ( 2 , 4 )",
+    "mul(6,9!",
+    "?(12,34)",
+    "mul(1234,5)",
+];
+
+const JUNK: &str = "!@#$%^&*()[]{}<>,'?:;+-/ whyselectfromwhenwhatwhohowwhere";
+
+/// `mul`s, `do()`s and `don't()`s among junk and broken instructions.
+pub fn mull(rng: &mut Rng, size: usize) -> String {
+    let mut memory = String::new();
+
+    for i in 0..size {
+        match rng.below(10) {
+            0 => memory.push_str("do()"),
+            1 => memory.push_str("don't()"),
+            2 => memory.push_str(rng.pick(CORRUPTED)),
+            _ => memory.push_str(&format!(
+                "mul({},{})",
+                rng.range(1..=999),
+                rng.range(1..=999)
+            )),
+        }
+
+        for _ in 0..rng.range(0..=8) {
+            memory.push(rng.char_from(JUNK));
+        }
+
+        if i % 120 == 119 {
+            memory.push('\n');
+        }
+    }
+
+    memory.push('\n');
+    memory
+}
+
+pub fn search(rng: &mut Rng, size: usize) -> String {
+    grid(size, |_, _| rng.char_from("XMAS"))
+}
+
+/// One ordering of 49 pages with a rule for every pair of them, and updates of
+/// an odd number of pages. About half are in order already.
+pub fn print(rng: &mut Rng, size: usize) -> String {
+    let mut pages: Vec<usize> = (10..100).collect();
+    rng.shuffle(&mut pages);
+    pages.truncate(49);
+
+    let mut rules = vec![];
+    for (i, before) in pages.iter().enumerate() {
+        for after in pages[i + 1..].iter() {
+            rules.push(format!("{}|{}", before, after));
+        }
+    }
+    rng.shuffle(&mut rules);
+
+    let updates = lines(size, |_| {
+        let mut picked: Vec<_> = (0..pages.len()).collect();
+        rng.shuffle(&mut picked);
+        picked.truncate(2 * rng.range(2..=11) + 1);
+
+        if rng.chance(0.5) {
+            picked.sort();
+        }
+
+        let update: Vec<_> = picked.iter().map(|i| pages[*i]).collect();
+        join(&update, ",")
+    });
+
+    format!("{}\n\n{}", rules.join("\n"), updates)
+}
+
+/// Scattered obstacles and a guard that walks off the map in the end, since
+/// part 1 can't finish otherwise. Of a few starts that do, the one with the
+/// longest walk is kept.
+pub fn guard(rng: &mut Rng, size: usize) -> String {
+    loop {
+        let mut map: Vec<Vec<char>> = (0..size)
+            .map(|_| {
+                (0..size)
+                    .map(|_| if rng.chance(0.05) { '#' } else { '.' })
+                    .collect()
+            })
+            .collect();
+
+        let start = (0..16)
+            .map(|_| (rng.below(size), rng.below(size)))
+            .filter(|(x, y)| map[*y][*x] == '.')
+            .filter_map(|start| Some((walk_off(&map, start)?, start)))
+            .max();
+
+        if let Some((_, (x, y))) = start {
+            map[y][x] = '^';
+            return lines(size, |y| map[y].iter().collect());
+        }
+    }
+}
+
+// how many steps a guard starting at `start`, facing up, takes to walk off
+// the map, or nothing if it never does
+fn walk_off(map: &[Vec<char>], start: (usize, usize)) -> Option<usize> {
+    const DIRECTIONS: [(isize, isize); 4] = [(0, -1), (1, 0), (0, 1), (-1, 0)];
+
+    let side = map.len();
+    let mut seen = vec![0u8; side * side];
+    let (mut x, mut y) = start;
+    let mut facing = 0;
+    let mut steps = 0;
+
+    loop {
+        if seen[y * side + x] & (1 << facing) != 0 {
+            return None;
+        }
+        seen[y * side + x] |= 1 << facing;
+
+        let (dx, dy) = DIRECTIONS[facing];
+        let (nx, ny) = (x.wrapping_add_signed(dx), y.wrapping_add_signed(dy));
+        if nx >= side || ny >= side {
+            return Some(steps);
+        }
+
+        match map[ny][nx] {
+            '#' => facing = (facing + 1) % 4,
+            _ => {
+                (x, y) = (nx, ny);
+                steps += 1;
+            }
+        }
+    }
+}
+
+/// 850 equations of `size` operands each. Results come from applying real
+/// operators, falling back to adding wherever another would overflow, so
+/// every equation can be solved with all three.
+pub fn bridge(rng: &mut Rng, size: usize) -> String {
+    lines(850, |_| {
+        let operands: Vec<_> = (0..size).map(|_| rng.range(1..=99) as u64).collect();
+
+        let result = operands[1..].iter().fold(operands[0], |acc, n| {
+            let applied = match rng.below(3) {
+                0 => acc.checked_mul(*n),
+                1 => format!("{}{}", acc, n).parse().ok(),
+                _ => None,
+            };
+
+            applied.unwrap_or(acc + n)
+        });
+
+        format!("{}: {}", result, join(&operands, " "))
+    })
+}
+
+const FREQUENCIES: &str = "0123456789abcdefghijklmnopqrstuvwxyzABCDEFGHIJKLMNOPQRSTUVWXYZ";
+
+pub fn resonant(rng: &mut Rng, size: usize) -> String {
+    grid(size, |_, _| match rng.chance(1.0 / 12.0) {
+        true => rng.char_from(FREQUENCIES),
+        false => '.',
+    })
+}
+
+/// A disk map of `size` entries, rounded up to end on a file. Files always
+/// take some space.
+pub fn frag(rng: &mut Rng, size: usize) -> String {
+    let mut map: String = (0..size | 1)
+        .map(|i| match i % 2 {
+            0 => rng.char_from("123456789"),
+            _ => rng.char_from("0123456789"),
+        })
+        .collect();
+
+    map.push('\n');
+    map
+}
+
+/// Hills around scattered peaks, falling by one with each step away from the
+/// nearest and starting over at 9 every ten steps, so every 0 has a trail up.
+/// A few tiles are noise.
+pub fn hoof(rng: &mut Rng, size: usize) -> String {
+    let mut distance = vec![vec![usize::MAX; size]; size];
+    let mut queue = VecDeque::new();
+
+    for _ in 0..(size * size / 150).max(1) {
+        let (x, y) = (rng.below(size), rng.below(size));
+        distance[y][x] = 0;
+        queue.push_back((x, y));
+    }
+
+    while let Some((x, y)) = queue.pop_front() {
+        let next = [
+            (x + 1, y),
+            (x.wrapping_sub(1), y),
+            (x, y + 1),
+            (x, y.wrapping_sub(1)),
+        ];
+
+        for (nx, ny) in next {
+            if nx < size && ny < size && distance[ny][nx] == usize::MAX {
+                distance[ny][nx] = distance[y][x] + 1;
+                queue.push_back((nx, ny));
+            }
+        }
+    }
+
+    grid(size, |x, y| match rng.chance(0.02) {
+        true => rng.char_from("0123456789"),
+        false => char::from_digit(9 - (distance[y][x] % 10) as u32, 10).unwrap(),
+    })
+}
+
+pub fn blink(rng: &mut Rng, size: usize) -> String {
+    let stones: Vec<_> = (0..size)
+        .map(|_| match rng.chance(0.1) {
+            true => 0,
+            false => rng.range(1..=9_999_999),
+        })
+        .collect();
+
+    join(&stones, " ") + "\n"
+}
+
+/// Plots of one plant in square blocks, with their edges roughened by letting
+/// plots take after the plot to their left or above.
+pub fn garden(rng: &mut Rng, size: usize) -> String {
+    let block = rng.range(3..=8);
+    let per_row = size.div_ceil(block);
+    let plants: Vec<_> = (0..per_row * per_row)
+        .map(|_| rng.char_from("ABCDEFGHIJKLMNOPQRSTUVWXYZ"))
+        .collect();
+
+    let mut plots = vec![vec![' '; size]; size];
+    for y in 0..size {
+        for x in 0..size {
+            plots[y][x] = match rng.below(8) {
+                0 if x > 0 => plots[y][x - 1],
+                1 if y > 0 => plots[y - 1][x],
+                _ => plants[(y / block) * per_row + x / block],
+            };
+        }
+    }
+
+    lines(size, |y| plots[y].iter().collect())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn guards_leave() {
+        for seed in 0..20 {
+            let input = guard(&mut Rng::new(seed), 30);
+            let map: Vec<Vec<char>> = input.lines().map(|l| l.chars().collect()).collect();
+            let y = map.iter().position(|row| row.contains(&'^')).unwrap();
+            let x = map[y].iter().position(|c| *c == '^').unwrap();
+
+            assert!(walk_off(&map, (x, y)).is_some());
+        }
+    }
+
+    #[test]
+    fn guards_are_caught_looping() {
+        let map: Vec<Vec<char>> = [".#..", "...#", "#...", "..#."]
+            .iter()
+            .map(|l| l.chars().collect())
+            .collect();
+
+        assert_eq!(walk_off(&map, (1, 2)), None);
+        assert_eq!(walk_off(&map, (0, 3)), Some(1));
+    }
+
+    #[test]
+    fn frag_maps_end_on_a_file() {
+        for size in [1, 2, 9, 10] {
+            let map = frag(&mut Rng::new(0), size);
+
+            assert_eq!(map.trim_end().len() % 2, 1);
+            assert!(map.trim_end().len() >= size);
+        }
+    }
+
+    #[test]
+    fn print_updates_have_a_middle_page() {
+        let input = print(&mut Rng::new(1), 100);
+        let (rules, updates) = input.split_once("\n\n").unwrap();
+
+        assert_eq!(rules.lines().count(), 49 * 48 / 2);
+        assert!(updates.lines().all(|u| u.split(',').count() % 2 == 1));
+    }
+}
@@ -0,0 +1,348 @@
+use std::{
+    error::Error,
+    io::Write,
+    path::{Path, PathBuf},
+    process::Command,
+    str::FromStr,
+    time::Duration,
+};
+
+use common::trace::{self, Format};
+use fetch::{Fetcher, Mirror, Site};
+use shrink::{Predicate, Runner};
+use store::InputStore;
+
+mod days;
+mod fetch;
+#[cfg(feature = "days")]
+mod forms;
+mod generate;
+mod samples;
+mod scaffold;
+#[cfg(feature = "serve")]
+mod serve;
+mod shrink;
+#[cfg(feature = "serve")]
+mod solvers;
+mod store;
+#[cfg(target_os = "linux")]
+mod watch;
+
+const USAGE: &str = "usage:
+    aoc list
+    aoc run <year> <day> [-v | --trace] [--json] [--param <name>=<value>...] [args...]
+    aoc new <year> <day> <name>
+    aoc fetch <year> <day> [--mirror <dir>]
+    aoc samples <year> <day> [page.html]
+    aoc inputs [<year> <day>] [--normalise]
+    aoc gen <year> <day> [--size <n>] [--seed <n>]
+    aoc watch <year> <day>
+    aoc serve [--addr <host:port>]
+    aoc shrink <year> <day> <input> (--panics | --slower <ms> | --differs <args>)";
+
+fn main() -> Result<(), Box<dyn Error>> {
+    let args: Vec<_> = std::env::args().skip(1).collect();
+    let root = root();
+
+    match args.first().map(String::as_str) {
+        Some("list") => {
+            for d in days::DAYS {
+                println!("{} {:>2} {}", d.year, d.day, d.name);
+            }
+        }
+        Some("run") => {
+            let (year, day) = year_and_day(&args)?;
+            let d =
+                days::find(year, day).ok_or(format!("{} day {} is not registered", year, day))?;
+
+            run(&root, d, &args[3..])?;
+        }
+        Some("new") => {
+            let (year, day) = year_and_day(&args)?;
+            let name = args.get(3).ok_or(USAGE)?;
+
+            scaffold::new_day(&root, year, day, name)?;
+            println!("created {}/{:02} ({})", year, day, name);
+        }
+        Some("fetch") => {
+            let (year, day) = year_and_day(&args)?;
+            let fetcher: Box<dyn Fetcher> = match args.get(3).map(String::as_str) {
+                Some("--mirror") => Box::new(Mirror {
+                    dir: args.get(4).ok_or(USAGE)?.into(),
+                }),
+                Some(_) => return Err(USAGE.into()),
+                None => Box::new(Site {
+                    session: std::env::var("AOC_SESSION")
+                        .map_err(|_| "set AOC_SESSION to your session cookie")?,
+                }),
+            };
+
+            for resource in InputStore::new(&root).fetch(year, day, fetcher.as_ref())? {
+                println!("fetched {}", resource.file_name());
+            }
+        }
+        Some("samples") => {
+            let (year, day) = year_and_day(&args)?;
+            let store = InputStore::new(&root);
+            let page = match args.get(3) {
+                Some(page) => PathBuf::from(page),
+                None => store.dir(year, day).join("puzzle.html"),
+            };
+
+            let samples = samples::extract(&std::fs::read_to_string(&page)?);
+            for path in store.add_samples(year, day, &samples)? {
+                println!("added {}", path.display());
+            }
+        }
+        Some("inputs") => inputs(&InputStore::new(&root), &args)?,
+        Some("gen") => {
+            let (year, day) = year_and_day(&args)?;
+            let generator = generate::find(year, day)
+                .ok_or(format!("no generator for {} day {}", year, day))?;
+            let size = option(&args, "--size")?.unwrap_or(generator.default_size);
+            let seed = option(&args, "--seed")?.unwrap_or(0);
+
+            if size == 0 {
+                return Err(format!("--size counts {}, so it can't be 0", generator.size).into());
+            }
+
+            std::io::stdout()
+                .lock()
+                .write_all(generator.generate(seed, size).as_bytes())?;
+        }
+        Some("watch") => {
+            let (year, day) = year_and_day(&args)?;
+            let d =
+                days::find(year, day).ok_or(format!("{} day {} is not registered", year, day))?;
+
+            watch(&root, d)?;
+        }
+        Some("serve") => {
+            let addr: String = option(&args, "--addr")?.unwrap_or("127.0.0.1:8023".into());
+
+            serve(&addr)?;
+        }
+        Some("shrink") => {
+            let (year, day) = year_and_day(&args)?;
+            let d =
+                days::find(year, day).ok_or(format!("{} day {} is not registered", year, day))?;
+            let input = std::fs::read_to_string(args.get(3).ok_or(USAGE)?)?;
+
+            let predicate = if args.iter().any(|a| a == "--panics") {
+                Predicate::Panics { at: None }
+            } else if let Some(ms) = option(&args, "--slower")? {
+                Predicate::Slower(Duration::from_millis(ms))
+            } else if let Some(other) = option::<String>(&args, "--differs")? {
+                Predicate::Differs(other.split_whitespace().map(String::from).collect())
+            } else {
+                return Err(USAGE.into());
+            };
+
+            let shrunk = shrink(&root, d, &input, &predicate)?;
+            std::io::stdout().lock().write_all(shrunk.as_bytes())?;
+        }
+        _ => return Err(USAGE.into()),
+    }
+
+    Ok(())
+}
+
+// the runner lives one level below the repository root
+fn root() -> PathBuf {
+    Path::new(env!("CARGO_MANIFEST_DIR"))
+        .parent()
+        .expect("runner should be inside the repository")
+        .to_path_buf()
+}
+
+fn year_and_day(args: &[String]) -> Result<(u16, u8), Box<dyn Error>> {
+    let year = args.get(1).ok_or(USAGE)?.parse()?;
+    let day = args.get(2).ok_or(USAGE)?.parse()?;
+
+    Ok((year, day))
+}
+
+/// The value following `flag`, if it was given.
+fn option<T>(args: &[String], flag: &str) -> Result<Option<T>, Box<dyn Error>>
+where
+    T: FromStr,
+    T::Err: Error + 'static,
+{
+    let Some(i) = args.iter().position(|a| a == flag) else {
+        return Ok(None);
+    };
+
+    let value = args.get(i + 1).ok_or(USAGE)?;
+
+    Ok(Some(value.parse()?))
+}
+
+fn inputs(store: &InputStore, args: &[String]) -> Result<(), Box<dyn Error>> {
+    let apply = args.iter().any(|a| a == "--normalise");
+    let args: Vec<_> = args
+        .iter()
+        .filter(|a| *a != "--normalise")
+        .cloned()
+        .collect();
+
+    let days: Vec<_> = match args.len() {
+        1 => days::DAYS.iter().map(|d| (d.year, d.day)).collect(),
+        _ => vec![year_and_day(&args)?],
+    };
+
+    for (year, day) in days {
+        let renames = match apply {
+            true => store.normalise(year, day)?,
+            false => store.plan(year, day)?,
+        };
+
+        for (from, to) in renames {
+            println!("{} -> {}", from.display(), to.display());
+        }
+    }
+
+    for group in store.duplicates()? {
+        let paths: Vec<_> = group.iter().map(|p| p.display().to_string()).collect();
+        println!("same sample: {}", paths.join(", "));
+    }
+
+    Ok(())
+}
+
+#[cfg(feature = "serve")]
+fn serve(addr: &str) -> Result<(), Box<dyn Error>> {
+    serve::serve(addr)
+}
+
+#[cfg(not(feature = "serve"))]
+fn serve(_addr: &str) -> Result<(), Box<dyn Error>> {
+    Err("serving needs the solvers built in, with `--features serve`".into())
+}
+
+#[cfg(target_os = "linux")]
+fn watch(root: &Path, d: &days::Day) -> Result<(), Box<dyn Error>> {
+    watch::watch(root, d)
+}
+
+#[cfg(not(target_os = "linux"))]
+fn watch(_root: &Path, _d: &days::Day) -> Result<(), Box<dyn Error>> {
+    Err("watching needs inotify, so it only works on Linux".into())
+}
+
+/// The smallest input the predicate still holds for, saying how it went on
+/// stderr.
+fn shrink(
+    root: &Path,
+    d: &days::Day,
+    input: &str,
+    predicate: &Predicate,
+) -> Result<String, Box<dyn Error>> {
+    let runner = Runner::build(root, d)?;
+
+    // keep to the panic there is now, not whatever one a smaller input hits
+    let predicate = match predicate {
+        Predicate::Panics { at: None } => Predicate::Panics {
+            at: runner.panics_at(input)?,
+        },
+        p => p.clone(),
+    };
+    let predicate = &predicate;
+
+    if !runner.check(predicate, input)? {
+        return Err(format!("{} doesn't do that with this input to start with", d.name).into());
+    }
+
+    let mut runs = 0;
+    let mut failed = None;
+    let shrunk = shrink::shrink(input, form(d), |candidate| {
+        if failed.is_some() {
+            return false;
+        }
+
+        runs += 1;
+        let kept = runner.check(predicate, candidate).unwrap_or_else(|e| {
+            failed.get_or_insert(e);
+            false
+        });
+
+        // every candidate that's kept is smaller than the last
+        if kept {
+            eprint!("\r{} bytes after {} runs", candidate.len(), runs);
+        }
+
+        kept
+    });
+
+    if let Some(e) = failed {
+        return Err(e);
+    }
+
+    eprintln!(
+        "\rshrunk {} bytes to {} in {} runs",
+        input.len(),
+        shrunk.len(),
+        runs
+    );
+
+    Ok(shrunk)
+}
+
+#[cfg(feature = "days")]
+fn form(d: &days::Day) -> Option<shrink::Form> {
+    forms::find(d.year, d.day)
+}
+
+// without the days built in, candidates are tried as they are
+#[cfg(not(feature = "days"))]
+fn form(_d: &days::Day) -> Option<shrink::Form> {
+    None
+}
+
+/// Runs a day's binary on `args`, or on its own input file when there are none.
+/// `-v` or `--trace` has the day trace to stderr, as JSON with `--json`.
+fn run(root: &Path, d: &days::Day, args: &[String]) -> Result<(), Box<dyn Error>> {
+    let dir = root.join(d.path());
+
+    let format = if args.iter().any(|a| a == "--json") {
+        Some(Format::Json)
+    } else if args.iter().any(|a| a == "-v" || a == "--trace") {
+        Some(Format::Text)
+    } else {
+        None
+    };
+    let args: Vec<_> = args
+        .iter()
+        .filter(|a| !["-v", "--trace", "--json"].contains(&a.as_str()))
+        .cloned()
+        .collect();
+
+    // the input goes first, so it's found for `aoc run 2024 10 --param ...` too
+    let args = match args.first() {
+        Some(first) if !first.starts_with('-') => args,
+        _ => match InputStore::new(root).input(d.year, d.day) {
+            Some(input) => [vec![input.display().to_string()], args].concat(),
+            // some days have their input built in
+            None if !args.is_empty() => args,
+            None => return Err(format!("no input file in {}", dir.display()).into()),
+        },
+    };
+
+    let mut command = Command::new("cargo");
+    command
+        .args(["run", "--quiet", "--release", "--manifest-path"])
+        .arg(dir.join("Cargo.toml"))
+        .arg("--")
+        .args(args);
+
+    if let Some(format) = format {
+        command.env(trace::VAR, format.var());
+    }
+
+    let status = command.status()?;
+
+    if !status.success() {
+        return Err(format!("{} exited with {}", d.name, status).into());
+    }
+
+    Ok(())
+}
@@ -0,0 +1,148 @@
+/// An example from the puzzle description, with the answers the text gives
+/// for it.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Sample {
+    pub text: String,
+    /// `(part, answer)`, in the order they appear
+    pub answers: Vec<(u8, String)>,
+}
+
+impl Sample {
+    /// One `part N: answer` line per answer.
+    pub fn expected(&self) -> String {
+        self.answers
+            .iter()
+            .map(|(part, answer)| format!("part {}: {}\n", part, answer))
+            .collect()
+    }
+}
+
+const PRE: &str = "<pre><code>";
+const PRE_END: &str = "</code></pre>";
+const ANSWER: &str = "<code><em>";
+const ANSWER_END: &str = "</em></code>";
+
+/// Pulls the examples out of a saved puzzle page. Each `<article>` is a part.
+/// An example's answer is the last emphasised code after it and before the
+/// next example; a part without examples of its own answers the last one.
+pub fn extract(html: &str) -> Vec<Sample> {
+    let mut samples: Vec<Sample> = vec![];
+
+    for (i, article) in html.split("<article").skip(1).enumerate() {
+        let part = i as u8 + 1;
+        let article = article.split("</article>").next().unwrap_or_default();
+
+        let mut blocks = article.split(PRE);
+        let before = blocks.next().unwrap_or_default();
+
+        let mut found = false;
+        for block in blocks {
+            let Some((code, after)) = block.split_once(PRE_END) else {
+                continue;
+            };
+
+            found = true;
+            samples.push(Sample {
+                text: decode(&strip_tags(code)),
+                answers: last_answer(after)
+                    .map(|a| vec![(part, a)])
+                    .unwrap_or_default(),
+            });
+        }
+
+        if !found {
+            if let (Some(sample), Some(answer)) = (samples.last_mut(), last_answer(before)) {
+                sample.answers.push((part, answer));
+            }
+        }
+    }
+
+    samples
+}
+
+fn last_answer(html: &str) -> Option<String> {
+    let (_, rest) = html.rsplit_once(ANSWER)?;
+    let (answer, _) = rest.split_once(ANSWER_END)?;
+
+    Some(decode(&strip_tags(answer)))
+}
+
+fn strip_tags(html: &str) -> String {
+    let mut text = String::with_capacity(html.len());
+    let mut in_tag = false;
+
+    for c in html.chars() {
+        match c {
+            '<' => in_tag = true,
+            '>' if in_tag => in_tag = false,
+            c if !in_tag => text.push(c),
+            _ => {}
+        }
+    }
+
+    text
+}
+
+fn decode(text: &str) -> String {
+    text.replace("&lt;", "<")
+        .replace("&gt;", ">")
+        .replace("&quot;", "\"")
+        .replace("&#39;", "'")
+        .replace("&amp;", "&")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const PAGE: &str = r#"<main>
+<article class="day-desc"><h2>--- Day 1 ---</h2>
+<p>For example:</p>
+<pre><code>1abc2
+<em>pqr3stu8vwx</em>
+</code></pre>
+<p>These values are <code>12</code> and <code>38</code>, for <code><em>50</em></code>.</p>
+<p>Another, with <code>a &lt; b</code>:</p>
+<pre><code>a &lt; b &amp;&amp; c
+</code></pre>
+<p>That gives <code><em>7</em></code>.</p>
+</article>
+<article class="day-desc"><h2>--- Part Two ---</h2>
+<p>Using the same example, the answer is <code><em>281</em></code>.</p>
+</article>
+</main>"#;
+
+    #[test]
+    fn samples_extract_blocks_and_answers() {
+        let samples = extract(PAGE);
+
+        assert_eq!(
+            samples,
+            vec![
+                Sample {
+                    text: "1abc2\npqr3stu8vwx\n".into(),
+                    answers: vec![(1, "50".into())],
+                },
+                Sample {
+                    text: "a < b && c\n".into(),
+                    answers: vec![(1, "7".into()), (2, "281".into())],
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn samples_write_expected() {
+        let sample = Sample {
+            text: String::new(),
+            answers: vec![(1, "50".into()), (2, "281".into())],
+        };
+
+        assert_eq!(sample.expected(), "part 1: 50\npart 2: 281\n");
+    }
+
+    #[test]
+    fn samples_skip_pages_without_articles() {
+        assert!(extract("<pre><code>1</code></pre>").is_empty());
+    }
+}
@@ -0,0 +1,359 @@
+use std::{error::Error, fs, path::Path};
+
+use crate::days;
+
+const CARGO_TOML: &str = include_str!("../templates/Cargo.toml");
+const MAIN_RS: &str = include_str!("../templates/main.rs");
+const LIB_RS: &str = include_str!("../templates/lib.rs");
+
+/// Creates `<root>/<year>/<day>` from the templates and adds it to the
+/// registry in `<root>/aoc/src/days.rs`, the solvers in
+/// `<root>/aoc/src/solvers.rs` and the runner's `days` feature.
+pub fn new_day(root: &Path, year: u16, day: u8, name: &str) -> Result<(), Box<dyn Error>> {
+    check_name(name)?;
+
+    if !(1..=25).contains(&day) {
+        return Err(format!("day {} is not between 1 and 25", day).into());
+    }
+
+    let dir = root.join(days::path(year, day));
+    if dir.exists() || days::find(year, day).is_some() {
+        return Err(format!("{} already exists", dir.display()).into());
+    }
+
+    let registry = root.join("aoc/src/days.rs");
+    let registered = fs::read_to_string(&registry)?;
+    let manifest = root.join("aoc/Cargo.toml");
+    let dependencies = fs::read_to_string(&manifest)?;
+    check_unique(name, &registered, &dependencies)?;
+
+    let source = register(&registered, year, day, name)?;
+    let solvers = root.join("aoc/src/solvers.rs");
+    let solver = add_solver(&fs::read_to_string(&solvers)?, year, day, name)?;
+    let dependency = add_dependency(&dependencies, year, day, name)?;
+
+    fs::create_dir_all(dir.join("src"))?;
+    fs::write(dir.join("Cargo.toml"), render(CARGO_TOML, name))?;
+    fs::write(dir.join("src/main.rs"), render(MAIN_RS, name))?;
+    fs::write(dir.join("src/lib.rs"), render(LIB_RS, name))?;
+    fs::write(dir.join("input.txt"), "")?;
+    fs::write(dir.join("sample_1.txt"), "")?;
+
+    fs::write(registry, source)?;
+    fs::write(solvers, solver)?;
+    fs::write(manifest, dependency)?;
+
+    Ok(())
+}
+
+fn render(template: &str, name: &str) -> String {
+    template
+        .replace("{{name}}", name)
+        .replace("{{lib}}", &name.replace('-', "_"))
+}
+
+fn check_name(name: &str) -> Result<(), String> {
+    let mut chars = name.chars();
+    let starts_with_letter = chars.next().is_some_and(|c| c.is_ascii_lowercase());
+    let rest_valid =
+        chars.all(|c| c.is_ascii_lowercase() || c.is_ascii_digit() || c == '_' || c == '-');
+
+    if !starts_with_letter || !rest_valid {
+        return Err(format!(
+            "'{}' should be lowercase letters, digits, '_' or '-', starting with a letter",
+            name
+        ));
+    }
+
+    Ok(())
+}
+
+/// Days become dependencies of the runner, so a name can't be one it already
+/// has: another day's, in `DAYS` or the registry source, or any key in its
+/// manifest. `-` and `_` name the same crate.
+fn check_unique(name: &str, registry: &str, manifest: &str) -> Result<(), String> {
+    let crate_name = |n: &str| n.replace('-', "_");
+
+    let registered = days::DAYS
+        .iter()
+        .map(|d| d.name)
+        .chain(registry.lines().filter_map(|l| {
+            let (_, rest) = l.trim().strip_prefix("Day::new(")?.split_once('"')?;
+            rest.split_once('"').map(|(n, _)| n)
+        }));
+
+    // the package's own name and every dependency
+    let mut section = "";
+    let mut keys = vec![];
+    for line in manifest.lines().map(str::trim) {
+        if line.starts_with('[') {
+            section = line;
+        } else if let Some((key, value)) = line.split_once('=') {
+            match section {
+                "[package]" if key.trim() == "name" => keys.push(value.trim().trim_matches('"')),
+                s if s.ends_with("dependencies]") => keys.push(key.trim()),
+                _ => {}
+            }
+        }
+    }
+
+    match registered
+        .chain(keys)
+        .find(|taken| crate_name(taken) == crate_name(name))
+    {
+        Some(taken) => Err(format!("there's already a crate called '{}'", taken)),
+        None => Ok(()),
+    }
+}
+
+/// The registry source with the day added in order.
+fn register(source: &str, year: u16, day: u8, name: &str) -> Result<String, String> {
+    let mut lines: Vec<_> = source.lines().map(String::from).collect();
+
+    let start = lines
+        .iter()
+        .position(|l| l.starts_with("pub const DAYS"))
+        .ok_or("registry has no DAYS list")?;
+    let end = start
+        + lines[start..]
+            .iter()
+            .position(|l| l.trim() == "];")
+            .ok_or("registry DAYS list is not closed")?;
+
+    let at = (start + 1..end)
+        .find(|i| parse_entry(&lines[*i]).is_some_and(|e| e > (year, day)))
+        .unwrap_or(end);
+
+    lines.insert(
+        at,
+        format!("    Day::new({}, {}, \"{}\"),", year, day, name),
+    );
+
+    Ok(lines.join("\n") + "\n")
+}
+
+/// The solvers source with the day's parts added in order.
+fn add_solver(source: &str, year: u16, day: u8, name: &str) -> Result<String, String> {
+    let mut lines: Vec<_> = source.lines().map(String::from).collect();
+
+    let start = lines
+        .iter()
+        .position(|l| l.starts_with("const SOLVERS"))
+        .ok_or("solvers have no SOLVERS list")?;
+    let end = start
+        + lines[start..]
+            .iter()
+            .position(|l| l.trim() == "];")
+            .ok_or("solvers SOLVERS list is not closed")?;
+
+    let at = (start + 1..end)
+        .find(|i| parse_entry(&lines[*i]).is_some_and(|e| e > (year, day)))
+        .unwrap_or(end);
+
+    lines.insert(
+        at,
+        format!(
+            "    solver!({}, {}, {}),",
+            year,
+            day,
+            name.replace('-', "_")
+        ),
+    );
+
+    Ok(lines.join("\n") + "\n")
+}
+
+/// The runner's manifest with the day as an optional dependency, turned on
+/// by the `days` feature.
+fn add_dependency(manifest: &str, year: u16, day: u8, name: &str) -> Result<String, String> {
+    let mut lines: Vec<_> = manifest.lines().map(String::from).collect();
+
+    let feature = lines
+        .iter()
+        .position(|l| l.starts_with("days = ["))
+        .ok_or("manifest has no days feature")?;
+    let end = feature
+        + lines[feature..]
+            .iter()
+            .position(|l| l.trim() == "]")
+            .ok_or("manifest days feature is not closed")?;
+    lines.insert(end, format!("    \"dep:{}\",", name));
+
+    let last = lines
+        .iter()
+        .rposition(|l| l.contains("path = \"../20"))
+        .ok_or("manifest has no days to follow")?;
+    lines.insert(
+        last + 1,
+        format!(
+            "{} = {{ path = \"../{}\", optional = true }}",
+            name,
+            days::path(year, day)
+        ),
+    );
+
+    Ok(lines.join("\n") + "\n")
+}
+
+fn parse_entry(line: &str) -> Option<(u16, u8)> {
+    let line = line.trim();
+    let args = line
+        .strip_prefix("Day::new(")
+        .or_else(|| line.strip_prefix("solver!("))?;
+    let mut args = args.split(',').map(str::trim);
+
+    Some((args.next()?.parse().ok()?, args.next()?.parse().ok()?))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const REGISTRY: &str = "pub const DAYS: &[Day] = &[
+    Day::new(2023, 1, \"trebuchet\"),
+    Day::new(2024, 1, \"hysteria\"),
+];
+";
+
+    const SOLVERS: &str = "const SOLVERS: &[Solver] = &[
+    solver!(2023, 1, trebuchet),
+    solver!(2024, 1, hysteria),
+];
+";
+
+    const MANIFEST: &str = "[package]
+name = \"aoc\"
+
+[features]
+days = [
+    \"dep:trebuchet\",
+]
+
+[dependencies]
+common = { path = \"../common\" }
+trebuchet = { path = \"../2023/01\", optional = true }
+
+[target.'cfg(target_os = \"linux\")'.dependencies]
+inotify = \"0.11\"
+";
+
+    #[test]
+    fn scaffold_registers_in_order() {
+        let source = register(REGISTRY, 2023, 12, "lens").unwrap();
+        let exp = "pub const DAYS: &[Day] = &[
+    Day::new(2023, 1, \"trebuchet\"),
+    Day::new(2023, 12, \"lens\"),
+    Day::new(2024, 1, \"hysteria\"),
+];
+";
+        assert_eq!(source, exp);
+
+        let source = register(REGISTRY, 2025, 1, "next").unwrap();
+        assert!(source.ends_with("    Day::new(2025, 1, \"next\"),\n];\n"));
+
+        assert!(register("", 2025, 1, "next").is_err());
+    }
+
+    #[test]
+    fn scaffold_adds_solvers_in_order() {
+        let source = add_solver(SOLVERS, 2023, 12, "lens-lib").unwrap();
+        let exp = "const SOLVERS: &[Solver] = &[
+    solver!(2023, 1, trebuchet),
+    solver!(2023, 12, lens_lib),
+    solver!(2024, 1, hysteria),
+];
+";
+        assert_eq!(source, exp);
+
+        assert!(add_solver("", 2025, 1, "next").is_err());
+    }
+
+    #[test]
+    fn scaffold_adds_dependencies() {
+        let manifest = add_dependency(MANIFEST, 2030, 3, "lens").unwrap();
+        let exp = "[package]
+name = \"aoc\"
+
+[features]
+days = [
+    \"dep:trebuchet\",
+    \"dep:lens\",
+]
+
+[dependencies]
+common = { path = \"../common\" }
+trebuchet = { path = \"../2023/01\", optional = true }
+lens = { path = \"../2030/03\", optional = true }
+
+[target.'cfg(target_os = \"linux\")'.dependencies]
+inotify = \"0.11\"
+";
+        assert_eq!(manifest, exp);
+
+        assert!(add_dependency("", 2030, 3, "lens").is_err());
+    }
+
+    #[test]
+    fn scaffold_checks_names() {
+        assert!(check_name("lens").is_ok());
+        assert!(check_name("lens-2").is_ok());
+        assert!(check_name("Lens").is_err());
+        assert!(check_name("2lens").is_err());
+        assert!(check_name("").is_err());
+    }
+
+    #[test]
+    fn scaffold_checks_names_are_unique() {
+        let registry = "    Day::new(2030, 1, \"lens-lib\"),\n";
+
+        assert!(check_unique("lens", REGISTRY, MANIFEST).is_ok());
+        assert!(check_unique("lens_lib", registry, MANIFEST).is_err());
+        assert!(check_unique("hysteria", REGISTRY, MANIFEST).is_err());
+        assert!(check_unique("cube", REGISTRY, MANIFEST).is_err());
+        assert!(check_unique("inotify", REGISTRY, MANIFEST).is_err());
+        assert!(check_unique("aoc", REGISTRY, MANIFEST).is_err());
+        assert!(check_unique("days", REGISTRY, MANIFEST).is_ok());
+    }
+
+    #[test]
+    fn scaffold_renders_crate_name() {
+        assert!(render(CARGO_TOML, "lens-lib").contains("name = \"lens-lib\""));
+        assert!(render(MAIN_RS, "lens-lib").contains("lens_lib::part1(&contents)"));
+        assert!(render(MAIN_RS, "lens-lib").contains("trace::init(\"lens-lib\")"));
+    }
+
+    #[test]
+    fn scaffold_creates_day() {
+        let root = std::env::temp_dir().join(format!("aoc-scaffold-{}", std::process::id()));
+        fs::create_dir_all(root.join("aoc/src")).unwrap();
+        fs::write(root.join("aoc/src/days.rs"), REGISTRY).unwrap();
+        fs::write(root.join("aoc/src/solvers.rs"), SOLVERS).unwrap();
+        fs::write(root.join("aoc/Cargo.toml"), MANIFEST).unwrap();
+
+        new_day(&root, 2030, 3, "lens").unwrap();
+
+        assert!(root.join("2030/03/src/lib.rs").exists());
+        assert!(root.join("2030/03/sample_1.txt").exists());
+        let registry = fs::read_to_string(root.join("aoc/src/days.rs")).unwrap();
+        assert!(registry.contains("Day::new(2030, 3, \"lens\")"));
+        let solvers = fs::read_to_string(root.join("aoc/src/solvers.rs")).unwrap();
+        assert!(solvers.contains("solver!(2030, 3, lens)"));
+        let manifest = fs::read_to_string(root.join("aoc/Cargo.toml")).unwrap();
+        assert!(manifest.contains("lens = { path = \"../2030/03\", optional = true }"));
+
+        // never overwrite a day, whether it's on disk or registered
+        assert!(new_day(&root, 2030, 3, "other").is_err());
+        assert!(new_day(&root, 2023, 1, "other").is_err());
+        assert!(new_day(&root, 2030, 26, "other").is_err());
+
+        // nor reuse a crate name, which would break the runner's build
+        for taken in ["lens", "cube", "common", "inotify"] {
+            assert!(new_day(&root, 2030, 4, taken).is_err(), "{}", taken);
+        }
+        assert!(!root.join("2030/04").exists());
+        let after = fs::read_to_string(root.join("aoc/Cargo.toml")).unwrap();
+        assert_eq!(manifest, after);
+
+        fs::remove_dir_all(root).unwrap();
+    }
+}
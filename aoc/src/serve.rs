@@ -0,0 +1,252 @@
+use std::{error::Error, io, panic, time::Instant};
+
+use common::{json::string, parse::ParseError};
+use tiny_http::{Header, Request, Response, Server};
+
+use crate::{days, solvers};
+
+/// A status and the JSON to send with it.
+#[derive(Debug, PartialEq, Eq)]
+pub struct Reply {
+    pub status: u16,
+    pub body: String,
+}
+
+impl Reply {
+    fn ok(body: String) -> Self {
+        Self { status: 200, body }
+    }
+
+    fn error(status: u16, message: &str) -> Self {
+        Self {
+            status,
+            body: format!(r#"{{"error": {}}}"#, string(message)),
+        }
+    }
+}
+
+/// Answers requests on `addr` until killed. Nothing here needs the network
+/// beyond the socket it listens on.
+pub fn serve(addr: &str) -> Result<(), Box<dyn Error>> {
+    let server = Server::http(addr).map_err(|e| format!("could not listen on {}: {}", addr, e))?;
+    println!("listening on http://{}", server.server_addr());
+
+    for request in server.incoming_requests() {
+        // one client going away shouldn't stop the others
+        if let Err(e) = respond(request) {
+            eprintln!("could not reply: {}", e);
+        }
+    }
+
+    Ok(())
+}
+
+fn respond(mut request: Request) -> io::Result<()> {
+    let mut body = vec![];
+    request.as_reader().read_to_end(&mut body)?;
+
+    let reply = handle(request.method().as_str(), request.url(), &body);
+    let json = Header::from_bytes("Content-Type", "application/json").expect("header is valid");
+
+    request.respond(
+        Response::from_string(reply.body)
+            .with_status_code(reply.status)
+            .with_header(json),
+    )
+}
+
+/// What to reply to a request, apart from the server so it can be tried
+/// without one.
+///
+/// - `GET /days` lists the registered days.
+/// - `POST /solve/{year}/{day}` solves both parts of the input in the body.
+pub fn handle(method: &str, url: &str, body: &[u8]) -> Reply {
+    let path = url.split('?').next().unwrap_or_default();
+    let segments: Vec<_> = path.split('/').filter(|s| !s.is_empty()).collect();
+
+    match (method, segments.as_slice()) {
+        ("GET", ["days"]) => Reply::ok(list()),
+        ("POST", ["solve", year, day]) => solve(year, day, body),
+        (_, ["days"]) => Reply::error(405, "use GET for /days"),
+        (_, ["solve", _, _]) => Reply::error(405, "use POST with the input as the body"),
+        _ => Reply::error(404, &format!("nothing at {}", path)),
+    }
+}
+
+fn list() -> String {
+    let days: Vec<_> = days::DAYS
+        .iter()
+        .map(|d| {
+            format!(
+                r#"{{"year": {}, "day": {}, "name": {}, "solver": {}}}"#,
+                d.year,
+                d.day,
+                string(d.name),
+                solvers::find(d.year, d.day).is_some()
+            )
+        })
+        .collect();
+
+    format!(r#"{{"days": [{}]}}"#, days.join(", "))
+}
+
+fn solve(year: &str, day: &str, body: &[u8]) -> Reply {
+    let (Ok(year), Ok(day)) = (year.parse::<u16>(), day.parse::<u8>()) else {
+        return Reply::error(404, &format!("{}/{} is not a year and day", year, day));
+    };
+    let Some(solver) = solvers::find(year, day) else {
+        return Reply::error(404, &format!("{} day {} has no solver", year, day));
+    };
+    let Ok(input) = std::str::from_utf8(body) else {
+        return Reply::error(400, "the input should be UTF-8");
+    };
+
+    let parts: Vec<_> = solver
+        .parts
+        .iter()
+        .enumerate()
+        .map(|(i, part)| {
+            let started = Instant::now();
+            let outcome = panic::catch_unwind(|| part(input));
+            let micros = started.elapsed().as_micros();
+
+            let result = match outcome {
+                Ok(Ok(answer)) => format!(r#""answer": {}"#, string(&answer.to_string())),
+                Ok(Err(e)) => format!(r#""error": {}"#, error(e.as_ref())),
+                Err(panic) => format!(r#""error": {}"#, error(&*panicked(panic))),
+            };
+
+            format!(r#"{{"part": {}, {}, "micros": {}}}"#, i + 1, result, micros)
+        })
+        .collect();
+
+    Reply::ok(format!(
+        r#"{{"year": {}, "day": {}, "parts": [{}]}}"#,
+        year,
+        day,
+        parts.join(", ")
+    ))
+}
+
+// with where it was when it's an error from parsing
+fn error(e: &(dyn Error + 'static)) -> String {
+    match e.downcast_ref::<ParseError>() {
+        Some(e) => format!(
+            r#"{{"message": {}, "line": {}, "column": {}}}"#,
+            string(&e.message),
+            e.line,
+            e.column
+        ),
+        None => format!(r#"{{"message": {}}}"#, string(&e.to_string())),
+    }
+}
+
+fn panicked(panic: Box<dyn std::any::Any + Send>) -> Box<dyn Error> {
+    let message = panic
+        .downcast_ref::<&str>()
+        .map(|s| s.to_string())
+        .or_else(|| panic.downcast_ref::<String>().cloned())
+        .unwrap_or_default();
+
+    format!("the solver panicked: {}", message).into()
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{
+        io::{Read, Write},
+        net::TcpStream,
+        thread,
+    };
+
+    use super::*;
+
+    const SAMPLE: &str = include_str!("../../2023/04/sample_1.txt");
+
+    #[test]
+    fn serve_lists_days() {
+        let reply = handle("GET", "/days", b"");
+
+        assert_eq!(reply.status, 200);
+        assert!(reply.body.starts_with(
+            r#"{"days": [{"year": 2023, "day": 1, "name": "trebuchet", "solver": true}, "#
+        ));
+    }
+
+    #[test]
+    fn serve_solves() {
+        let reply = handle("POST", "/solve/2023/4", SAMPLE.as_bytes());
+
+        assert_eq!(reply.status, 200);
+        assert!(reply.body.starts_with(
+            r#"{"year": 2023, "day": 4, "parts": [{"part": 1, "answer": "13", "micros": "#
+        ));
+        assert!(reply
+            .body
+            .contains(r#"{"part": 2, "answer": "30", "micros": "#));
+    }
+
+    #[test]
+    fn serve_reports_where_parsing_failed() {
+        let reply = handle("POST", "/solve/2023/4", b"Card 1: 1 | 2\nCard 2 3 | 4");
+
+        assert_eq!(reply.status, 200);
+        assert!(reply
+            .body
+            .contains(r#""error": {"message": "expected ':' in a card", "line": 2, "column": 7}"#));
+    }
+
+    #[test]
+    fn serve_only_places_parse_errors() {
+        let e: Box<dyn Error> = "line 2, column 7: not from a parser".into();
+
+        assert_eq!(
+            error(e.as_ref()),
+            r#"{"message": "line 2, column 7: not from a parser"}"#
+        );
+    }
+
+    #[test]
+    fn serve_refuses_what_it_cannot_do() {
+        assert_eq!(handle("GET", "/solve/2023/4", b"").status, 405);
+        assert_eq!(handle("POST", "/days", b"").status, 405);
+        assert_eq!(handle("POST", "/solve/2022/1", b"").status, 404);
+        assert_eq!(handle("POST", "/solve/twenty/1", b"").status, 404);
+        assert_eq!(handle("POST", "/solve/2023/4", &[0xff]).status, 400);
+        assert_eq!(
+            handle("GET", "/nowhere", b""),
+            Reply::error(404, "nothing at /nowhere")
+        );
+    }
+
+    #[test]
+    fn serve_escapes_strings() {
+        assert_eq!(string("a \"b\"\n\\"), r#""a \"b\"\n\\""#);
+        assert_eq!(string("\u{1b}"), r#""\u001b""#);
+    }
+
+    #[test]
+    fn serve_answers_over_http() {
+        let server = Server::http("127.0.0.1:0").unwrap();
+        let addr = server.server_addr().to_ip().unwrap();
+
+        let handler = thread::spawn(move || respond(server.recv().unwrap()));
+
+        let mut client = TcpStream::connect(addr).unwrap();
+        write!(
+            client,
+            "POST /solve/2023/4 HTTP/1.1\r\nHost: localhost\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{}",
+            SAMPLE.len(),
+            SAMPLE
+        )
+        .unwrap();
+
+        let mut response = String::new();
+        client.read_to_string(&mut response).unwrap();
+        handler.join().unwrap().unwrap();
+
+        assert!(response.starts_with("HTTP/1.1 200"));
+        assert!(response.contains("Content-Type: application/json"));
+        assert!(response.contains(r#""answer": "30""#));
+    }
+}
@@ -0,0 +1,457 @@
+use std::{
+    collections::BTreeMap,
+    error::Error,
+    fs,
+    io::{self, Read},
+    path::{Path, PathBuf},
+    process::{Command, Stdio},
+    thread::{self, JoinHandle},
+    time::{Duration, Instant},
+};
+
+use common::{answer, trace, Answer};
+
+use crate::days::Day;
+
+// how long a run gets before it's taken to have hung, unless timing is the point
+const TIMEOUT: Duration = Duration::from_secs(10);
+
+// how often a running day is checked on
+const POLL: Duration = Duration::from_millis(1);
+
+// a Rust program that panicked exits with this
+const PANICKED: i32 = 101;
+
+/// Reprints text through a day's parser, or gives `None` when it doesn't
+/// parse, so shrinking only ever tries inputs the day could be given.
+pub type Form = fn(&str) -> Option<String>;
+
+/// What makes an input worth keeping while it shrinks.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Predicate {
+    /// the day panics on it, where it panicked on the original input if
+    /// that's known, so the input doesn't shrink into some other panic
+    Panics { at: Option<String> },
+    /// the day is still going after this long
+    Slower(Duration),
+    /// the day's answers change when it's also given these arguments, like a
+    /// `--param` that picks another implementation
+    Differs(Vec<String>),
+}
+
+/// The ways an input is cut into pieces to take some away, coarsest first.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Cut {
+    /// blocks separated by a blank line
+    Sections,
+    Lines,
+    /// columns of a grid, where every line is as long
+    Columns,
+    /// anything between whitespace
+    Tokens,
+    /// characters other than line breaks
+    Chars,
+}
+
+// a grid only loses whole rows and columns, so it stays a grid
+const GRID_CUTS: &[Cut] = &[Cut::Lines, Cut::Columns];
+const TEXT_CUTS: &[Cut] = &[Cut::Sections, Cut::Lines, Cut::Tokens, Cut::Chars];
+
+/// Takes pieces away from `input` for as long as what's left is still
+/// `interesting`, going from whole sections down to single characters, or
+/// rows and columns for a grid, and round again until nothing more can go.
+/// Each piece that's left is needed: taking any one of them away makes the
+/// input uninteresting.
+///
+/// With a `form`, candidates are reprinted by the day's parser first and
+/// those it can't parse are never tried.
+pub fn shrink(
+    input: &str,
+    form: Option<Form>,
+    mut interesting: impl FnMut(&str) -> bool,
+) -> String {
+    let mut text = input.to_string();
+    let cuts = match grid_width(input) {
+        Some(_) => GRID_CUTS,
+        None => TEXT_CUTS,
+    };
+
+    loop {
+        let before = text.len();
+        for cut in cuts.iter().copied() {
+            text = reduce(&text, cut, form, &mut interesting);
+        }
+
+        if text.len() >= before {
+            return text;
+        }
+    }
+}
+
+// delta debugging: try taking away each of `chunks` runs of pieces, halving
+// the runs whenever none of them can go
+fn reduce(
+    text: &str,
+    cut: Cut,
+    form: Option<Form>,
+    interesting: &mut impl FnMut(&str) -> bool,
+) -> String {
+    let mut text = text.to_string();
+    let mut chunks = 2;
+
+    loop {
+        let count = count(&text, cut);
+        if count == 0 {
+            return text;
+        }
+
+        let size = count.div_ceil(chunks.min(count));
+        let smaller = (0..count).step_by(size).find_map(|start| {
+            let candidate = remove(&text, cut, start..(start + size).min(count));
+            let candidate = match form {
+                Some(form) => form(&candidate)?,
+                None => candidate,
+            };
+
+            // only ever smaller, so this always ends
+            (candidate.len() < text.len() && interesting(&candidate)).then_some(candidate)
+        });
+
+        match smaller {
+            Some(smaller) => {
+                text = smaller;
+                chunks = (chunks - 1).max(2);
+            }
+            None if size == 1 => return text,
+            None => chunks = (chunks * 2).min(count),
+        }
+    }
+}
+
+/// How many pieces `text` cuts into.
+fn count(text: &str, cut: Cut) -> usize {
+    match cut {
+        Cut::Columns => grid_width(text).unwrap_or(0),
+        cut => pieces(text, cut).len(),
+    }
+}
+
+/// `text` without the pieces in `range`.
+fn remove(text: &str, cut: Cut, range: std::ops::Range<usize>) -> String {
+    if cut == Cut::Columns {
+        return text
+            .split_inclusive('\n')
+            .map(|line| {
+                line.chars()
+                    .enumerate()
+                    .filter(|(i, c)| *c == '\n' || !range.contains(i))
+                    .map(|(_, c)| c)
+                    .collect::<String>()
+            })
+            .collect();
+    }
+
+    pieces(text, cut)
+        .iter()
+        .enumerate()
+        .map(|(i, (piece, left))| if range.contains(&i) { *left } else { *piece })
+        .collect()
+}
+
+/// Each piece with what's left of it once it's taken away, which keeps line
+/// breaks in place so lines stay lines.
+fn pieces(text: &str, cut: Cut) -> Vec<(&str, &str)> {
+    match cut {
+        Cut::Sections => text.split_inclusive("\n\n").map(|s| (s, "")).collect(),
+        Cut::Lines => text.split_inclusive('\n').map(|l| (l, "")).collect(),
+        Cut::Columns => unreachable!("columns aren't pieces of the text"),
+        Cut::Tokens => {
+            // a token and the whitespace after it
+            let mut pieces = vec![];
+            let mut start = 0;
+
+            while start < text.len() {
+                let rest = &text[start..];
+                let token = rest.find(char::is_whitespace).unwrap_or(rest.len());
+                let space = rest[token..]
+                    .find(|c: char| !c.is_whitespace())
+                    .map_or(rest.len(), |s| token + s);
+
+                let piece = &rest[..space];
+                let left = match piece.find('\n') {
+                    Some(n) => &piece[n..piece.rfind('\n').expect("found one") + 1],
+                    None => "",
+                };
+
+                pieces.push((piece, left));
+                start += space;
+            }
+
+            pieces
+        }
+        Cut::Chars => text
+            .char_indices()
+            .map(|(i, c)| {
+                let s = &text[i..i + c.len_utf8()];
+                (s, if c == '\n' { s } else { "" })
+            })
+            .collect(),
+    }
+}
+
+/// How wide `text` is, if it's a grid of at least two lines that are all as
+/// long as each other.
+fn grid_width(text: &str) -> Option<usize> {
+    let widths: Vec<_> = text.lines().map(|l| l.chars().count()).collect();
+
+    match widths.as_slice() {
+        [first, rest @ ..] if !rest.is_empty() && *first > 1 => {
+            rest.iter().all(|w| w == first).then_some(*first)
+        }
+        _ => None,
+    }
+}
+
+/// A day's binary, built once and run on each candidate.
+pub struct Runner {
+    binary: PathBuf,
+    file: PathBuf,
+}
+
+/// How a run went.
+#[derive(Debug, PartialEq, Eq)]
+enum Outcome {
+    Exited {
+        code: Option<i32>,
+        stdout: String,
+        stderr: String,
+    },
+    TimedOut,
+}
+
+impl Runner {
+    /// Builds the day in release mode.
+    pub fn build(root: &Path, d: &Day) -> Result<Self, Box<dyn Error>> {
+        let dir = root.join(d.path());
+        let status = Command::new("cargo")
+            .args(["build", "--quiet", "--release", "--manifest-path"])
+            .arg(dir.join("Cargo.toml"))
+            .status()?;
+
+        if !status.success() {
+            return Err(format!("{} did not build", d.name).into());
+        }
+
+        let target = std::env::var_os("CARGO_TARGET_DIR")
+            .map(PathBuf::from)
+            .unwrap_or(dir.join("target"));
+
+        Ok(Self {
+            binary: target.join("release").join(d.name),
+            file: std::env::temp_dir().join(format!(
+                "aoc-shrink-{}-{}",
+                d.name,
+                std::process::id()
+            )),
+        })
+    }
+
+    /// Whether `input` still does what `predicate` is looking for.
+    pub fn check(&self, predicate: &Predicate, input: &str) -> Result<bool, Box<dyn Error>> {
+        fs::write(&self.file, input)?;
+
+        Ok(match predicate {
+            Predicate::Panics { at } => match self.panic_site()? {
+                Some(site) => at.as_ref().is_none_or(|at| *at == site),
+                None => false,
+            },
+            Predicate::Slower(limit) => self.run(&[], *limit)? == Outcome::TimedOut,
+            Predicate::Differs(args) => {
+                let (Some(plain), Some(other)) = (self.answers(&[])?, self.answers(args)?) else {
+                    // a failing run isn't an answer to compare
+                    return Ok(false);
+                };
+
+                plain != other
+            }
+        })
+    }
+
+    /// Where the day panics on `input`, if it does.
+    pub fn panics_at(&self, input: &str) -> Result<Option<String>, Box<dyn Error>> {
+        fs::write(&self.file, input)?;
+
+        self.panic_site()
+    }
+
+    fn panic_site(&self) -> Result<Option<String>, Box<dyn Error>> {
+        Ok(match self.run(&[], TIMEOUT)? {
+            Outcome::Exited {
+                code: Some(PANICKED),
+                stderr,
+                ..
+            } => Some(site(&stderr).unwrap_or_default()),
+            _ => None,
+        })
+    }
+
+    fn answers(&self, args: &[String]) -> Result<Option<BTreeMap<u8, Answer>>, Box<dyn Error>> {
+        Ok(match self.run(args, TIMEOUT)? {
+            Outcome::Exited {
+                code: Some(0),
+                stdout,
+                ..
+            } => Some(answer::parse(&stdout)),
+            _ => None,
+        })
+    }
+
+    fn run(&self, args: &[String], limit: Duration) -> Result<Outcome, Box<dyn Error>> {
+        let mut child = Command::new(&self.binary)
+            .arg(&self.file)
+            .args(args)
+            .env("RUST_BACKTRACE", "0")
+            .env(answer::VAR, "1")
+            .env_remove(trace::VAR)
+            .stdout(Stdio::piped())
+            .stderr(Stdio::piped())
+            .spawn()?;
+
+        // read while it runs, or a day that fills a pipe would block on it
+        let stdout = drain(child.stdout.take());
+        let stderr = drain(child.stderr.take());
+
+        let started = Instant::now();
+        loop {
+            if let Some(status) = child.try_wait()? {
+                return Ok(Outcome::Exited {
+                    code: status.code(),
+                    stdout: joined(stdout)?,
+                    stderr: joined(stderr)?,
+                });
+            }
+
+            if started.elapsed() > limit {
+                child.kill()?;
+                child.wait()?;
+                return Ok(Outcome::TimedOut);
+            }
+
+            thread::sleep(POLL);
+        }
+    }
+}
+
+impl Drop for Runner {
+    fn drop(&mut self) {
+        let _ = fs::remove_file(&self.file);
+    }
+}
+
+fn drain(pipe: Option<impl Read + Send + 'static>) -> JoinHandle<io::Result<String>> {
+    thread::spawn(move || {
+        let mut s = String::new();
+        if let Some(mut pipe) = pipe {
+            pipe.read_to_string(&mut s)?;
+        }
+
+        Ok(s)
+    })
+}
+
+fn joined(reader: JoinHandle<io::Result<String>>) -> Result<String, Box<dyn Error>> {
+    Ok(reader.join().map_err(|_| "reading a pipe panicked")??)
+}
+
+/// The file, line and column a panic message says it came from, leaving out
+/// the thread, whose id changes from run to run.
+fn site(stderr: &str) -> Option<String> {
+    let (_, rest) = stderr.split_once("panicked at ")?;
+    let site = rest.lines().next()?;
+
+    Some(site.trim_end_matches(':').to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn tidy(s: &str) -> Option<String> {
+        (!s.contains("bad")).then(|| s.to_string())
+    }
+
+    #[test]
+    fn runner_reads_output_while_running() {
+        // the input is the script, so a lot of output is all it takes
+        let runner = Runner {
+            binary: "sh".into(),
+            file: std::env::temp_dir().join(format!("aoc-shrink-sh-{}", std::process::id())),
+        };
+        fs::write(&runner.file, "head -c 1000000 /dev/zero | tr '\\0' x\n").unwrap();
+
+        let Outcome::Exited { code, stdout, .. } = runner.run(&[], TIMEOUT).unwrap() else {
+            panic!("timed out");
+        };
+
+        assert_eq!(code, Some(0));
+        assert_eq!(stdout.len(), 1000000);
+    }
+
+    #[test]
+    fn shrink_finds_panic_sites() {
+        let stderr = "\nthread 'main' (2455) panicked at src/main.rs:11:31:\nfilename\n";
+
+        assert_eq!(site(stderr).as_deref(), Some("src/main.rs:11:31"));
+        assert_eq!(site("error: oops"), None);
+    }
+
+    #[test]
+    fn shrink_cuts_pieces() {
+        let text = "a b\nc d\n\ne f\n";
+
+        assert_eq!(remove(text, Cut::Sections, 0..1), "e f\n");
+        assert_eq!(remove(text, Cut::Lines, 1..3), "a b\ne f\n");
+        assert_eq!(remove(text, Cut::Tokens, 1..3), "a \nd\n\ne f\n");
+        assert_eq!(remove(text, Cut::Chars, 0..2), "b\nc d\n\ne f\n");
+
+        assert_eq!(count("abc\ndef\n", Cut::Columns), 3);
+        assert_eq!(remove("abc\ndef\n", Cut::Columns, 1..2), "ac\ndf\n");
+        assert_eq!(count("abc\nde\n", Cut::Columns), 0);
+    }
+
+    #[test]
+    fn shrink_keeps_what_is_needed() {
+        let input: String = (0..100).map(|i| format!("{} {}\n", i, i * 2)).collect();
+
+        let mut runs = 0;
+        let shrunk = shrink(&input, None, |s| {
+            runs += 1;
+            s.contains("42") && s.contains("17")
+        });
+
+        assert_eq!(shrunk, "42\n17\n");
+        assert!(runs < 400, "took {} runs", runs);
+    }
+
+    #[test]
+    fn shrink_tries_only_what_parses() {
+        let input = "keep\nbad\nkeep\n";
+
+        let shrunk = shrink(input, Some(tidy), |s| {
+            assert!(!s.contains("bad"), "{:?} should not have been tried", s);
+            s.contains("keep")
+        });
+
+        assert_eq!(shrunk, "keep\n");
+    }
+
+    #[test]
+    fn shrink_finds_the_columns() {
+        let grid = "..#..\n.....\n..#..\n.....\n";
+        let shrunk = shrink(grid, None, |s| {
+            grid_width(s).is_some() && s.matches('#').count() == 2
+        });
+
+        assert_eq!(shrunk, ".#\n.#\n");
+    }
+}
@@ -0,0 +1,79 @@
+use std::error::Error;
+
+use common::Answer;
+
+pub type Part = fn(&str) -> Result<Answer, Box<dyn Error>>;
+
+/// A day's parts, linked into the runner so they can be called in process.
+pub struct Solver {
+    pub year: u16,
+    pub day: u8,
+    pub parts: [Part; 2],
+}
+
+// days differ in their error types, so each part is wrapped to box its error
+macro_rules! solver {
+    ($year:literal, $day:literal, $lib:ident) => {
+        Solver {
+            year: $year,
+            day: $day,
+            parts: [
+                |s| $lib::part1(s).map_err(Into::into),
+                |s| $lib::part2(s).map_err(Into::into),
+            ],
+        }
+    };
+}
+
+// `aoc new` adds each new day here, and to the `days` feature
+const SOLVERS: &[Solver] = &[
+    solver!(2023, 1, trebuchet),
+    solver!(2023, 2, cube),
+    solver!(2023, 3, gear),
+    solver!(2023, 4, scratch),
+    solver!(2023, 5, seed),
+    solver!(2023, 6, wait),
+    solver!(2023, 7, camel),
+    solver!(2023, 8, wasteland),
+    solver!(2023, 9, mirage),
+    solver!(2023, 10, pipes),
+    solver!(2023, 11, cosmic),
+    solver!(2024, 1, hysteria),
+    solver!(2024, 2, reports),
+    solver!(2024, 3, mull),
+    solver!(2024, 4, search),
+    solver!(2024, 5, print),
+    solver!(2024, 6, guard),
+    solver!(2024, 7, bridge),
+    solver!(2024, 8, resonant),
+    solver!(2024, 9, frag),
+    solver!(2024, 10, hoof),
+    solver!(2024, 11, blink),
+    solver!(2024, 12, garden),
+];
+
+pub fn find(year: u16, day: u8) -> Option<&'static Solver> {
+    SOLVERS.iter().find(|s| s.year == year && s.day == day)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::days;
+
+    #[test]
+    fn solvers_cover_every_day() {
+        for d in days::DAYS {
+            assert!(find(d.year, d.day).is_some(), "{} has no solver", d.name);
+        }
+    }
+
+    #[test]
+    fn solvers_solve() {
+        let scratch = find(2023, 4).unwrap();
+        let sample = include_str!("../../2023/04/sample_1.txt");
+
+        assert_eq!(scratch.parts[0](sample).unwrap(), Answer::Int(13));
+        assert_eq!(scratch.parts[1](sample).unwrap(), Answer::Int(30));
+    }
+}
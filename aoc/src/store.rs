@@ -0,0 +1,508 @@
+use std::{
+    collections::BTreeMap,
+    error::Error,
+    fs,
+    path::{Path, PathBuf},
+};
+
+use crate::{
+    days,
+    fetch::{Fetcher, Resource},
+    samples::Sample,
+};
+
+/// What a file in a day's directory is, judging by its name.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum FileKind {
+    Input,
+    /// ordered by the part it was saved for, then its number
+    Sample {
+        part: u8,
+        index: u32,
+    },
+}
+
+/// Recognises the names inputs have been saved under: `input`, `input.txt`,
+/// `sample.txt`, `sample2.txt`, `sample_2.txt`, `part_2_sample_0.txt`.
+pub fn classify(name: &str) -> Option<FileKind> {
+    let stem = name.strip_suffix(".txt").unwrap_or(name);
+
+    if stem == "input" {
+        return Some(FileKind::Input);
+    }
+
+    let (part, rest) = match stem.strip_prefix("part_") {
+        Some(rest) => {
+            let (part, rest) = rest.split_once('_')?;
+            (part.parse().ok()?, rest)
+        }
+        None => (1, stem),
+    };
+
+    let index = rest.strip_prefix("sample")?;
+    let index = index.strip_prefix('_').unwrap_or(index);
+    let index = if index.is_empty() {
+        1
+    } else {
+        index.parse().ok()?
+    };
+
+    Some(FileKind::Sample { part, index })
+}
+
+/// Inputs and samples under one naming scheme: `<year>/<day>/input.txt` and
+/// `<year>/<day>/sample_N.txt` numbered from 1, each sample's answers in
+/// `sample_N.expected`.
+pub struct InputStore {
+    root: PathBuf,
+}
+
+impl InputStore {
+    pub fn new(root: &Path) -> Self {
+        Self {
+            root: root.to_path_buf(),
+        }
+    }
+
+    pub fn dir(&self, year: u16, day: u8) -> PathBuf {
+        self.root.join(days::path(year, day))
+    }
+
+    /// The day's input, under its old name if it hasn't been renamed yet.
+    pub fn input(&self, year: u16, day: u8) -> Option<PathBuf> {
+        let dir = self.dir(year, day);
+
+        ["input.txt", "input"]
+            .iter()
+            .map(|f| dir.join(f))
+            .find(|p| p.exists())
+    }
+
+    /// Samples in scheme order, whatever they're called.
+    pub fn samples(&self, year: u16, day: u8) -> Result<Vec<PathBuf>, Box<dyn Error>> {
+        let mut samples: Vec<_> = self
+            .files(year, day)?
+            .into_iter()
+            .filter_map(|(kind, path)| match kind {
+                FileKind::Sample { .. } => Some((kind, path)),
+                FileKind::Input => None,
+            })
+            .collect();
+
+        samples.sort();
+        Ok(samples.into_iter().map(|(_, p)| p).collect())
+    }
+
+    /// Renames that would bring the day under the naming scheme, as
+    /// `(from, to)`. Each sample's `.expected` answers move along with it.
+    /// Files already in place are left out.
+    pub fn plan(&self, year: u16, day: u8) -> Result<Vec<(PathBuf, PathBuf)>, Box<dyn Error>> {
+        let dir = self.dir(year, day);
+        let mut renames = vec![];
+
+        if let Some(input) = self.input(year, day) {
+            renames.push((input, dir.join("input.txt")));
+        }
+
+        for (i, sample) in self.samples(year, day)?.into_iter().enumerate() {
+            let to = dir.join(format!("sample_{}.txt", i + 1));
+            let expected = sample.with_extension("expected");
+
+            // `sample` and `sample.txt` share answers, which go with the first
+            if expected.exists() && !renames.iter().any(|(from, _)| *from == expected) {
+                renames.push((expected, to.with_extension("expected")));
+            }
+
+            renames.push((sample, to));
+        }
+
+        renames.retain(|(from, to)| from != to);
+        Ok(renames)
+    }
+
+    /// Carries out `plan`. Goes through temporary names, so one file can take
+    /// the name another is leaving. Fails before renaming anything if a file
+    /// would be overwritten.
+    pub fn normalise(&self, year: u16, day: u8) -> Result<Vec<(PathBuf, PathBuf)>, Box<dyn Error>> {
+        let renames = self.plan(year, day)?;
+        let dir = self.dir(year, day);
+
+        let mut staged = vec![];
+        for (i, (from, to)) in renames.iter().enumerate() {
+            if to.exists() && !renames.iter().any(|(f, _)| f == to) {
+                return Err(format!("{} is in the way", to.display()).into());
+            }
+
+            let name = to.file_name().and_then(|n| n.to_str()).unwrap_or_default();
+            let tmp = dir.join(format!(".{}.{}.renaming", i, name));
+            if tmp.exists() {
+                return Err(
+                    format!("{} is in the way of {}", tmp.display(), from.display()).into(),
+                );
+            }
+
+            staged.push(tmp);
+        }
+
+        for ((from, _), tmp) in renames.iter().zip(staged.iter()) {
+            fs::rename(from, tmp)?;
+        }
+
+        for ((_, to), tmp) in renames.iter().zip(staged.iter()) {
+            fs::rename(tmp, to)?;
+        }
+
+        Ok(renames)
+    }
+
+    /// Groups of samples with the same contents, ignoring trailing
+    /// whitespace, across every day in the store.
+    pub fn duplicates(&self) -> Result<Vec<Vec<PathBuf>>, Box<dyn Error>> {
+        let mut by_contents: BTreeMap<String, Vec<PathBuf>> = BTreeMap::new();
+
+        for (year, day) in self.days()? {
+            for sample in self.samples(year, day)? {
+                let contents = normalised(&fs::read_to_string(&sample)?);
+                by_contents.entry(contents).or_default().push(sample);
+            }
+        }
+
+        Ok(by_contents
+            .into_values()
+            .filter(|paths| paths.len() > 1)
+            .collect())
+    }
+
+    /// Downloads whatever the day doesn't have yet. Returns what was fetched.
+    pub fn fetch(
+        &self,
+        year: u16,
+        day: u8,
+        fetcher: &dyn Fetcher,
+    ) -> Result<Vec<Resource>, Box<dyn Error>> {
+        let dir = self.dir(year, day);
+        let mut fetched = vec![];
+
+        for resource in [Resource::Input, Resource::Puzzle] {
+            let have = match resource {
+                Resource::Input => self.input(year, day).is_some(),
+                Resource::Puzzle => dir.join(resource.file_name()).exists(),
+            };
+
+            if have {
+                continue;
+            }
+
+            let contents = fetcher.fetch(year, day, resource)?;
+            fs::create_dir_all(&dir)?;
+            fs::write(dir.join(resource.file_name()), contents)?;
+            fetched.push(resource);
+        }
+
+        Ok(fetched)
+    }
+
+    /// Saves samples the day doesn't have yet as the next `sample_N.txt`,
+    /// with their answers alongside. Returns the new files.
+    pub fn add_samples(
+        &self,
+        year: u16,
+        day: u8,
+        samples: &[Sample],
+    ) -> Result<Vec<PathBuf>, Box<dyn Error>> {
+        let dir = self.dir(year, day);
+        let existing = self.samples(year, day)?;
+
+        let mut known = vec![];
+        for path in existing.iter() {
+            known.push(normalised(&fs::read_to_string(path)?));
+        }
+
+        let mut next = existing.len() + 1;
+        let mut added = vec![];
+
+        for sample in samples {
+            let contents = normalised(&sample.text);
+            if known.contains(&contents) {
+                continue;
+            }
+
+            let path = dir.join(format!("sample_{}.txt", next));
+            if path.exists() {
+                return Err(
+                    format!("{} is in the way, normalise the day first", path.display()).into(),
+                );
+            }
+
+            fs::write(&path, &sample.text)?;
+            if !sample.answers.is_empty() {
+                fs::write(path.with_extension("expected"), sample.expected())?;
+            }
+
+            known.push(contents);
+            added.push(path);
+            next += 1;
+        }
+
+        Ok(added)
+    }
+
+    fn files(&self, year: u16, day: u8) -> Result<Vec<(FileKind, PathBuf)>, Box<dyn Error>> {
+        let mut files = vec![];
+
+        for entry in fs::read_dir(self.dir(year, day))? {
+            let path = entry?.path();
+            let name = path
+                .file_name()
+                .and_then(|n| n.to_str())
+                .unwrap_or_default();
+
+            if let Some(kind) = classify(name) {
+                files.push((kind, path));
+            }
+        }
+
+        Ok(files)
+    }
+
+    // every `<year>/<day>` directory, registered or not
+    fn days(&self) -> Result<Vec<(u16, u8)>, Box<dyn Error>> {
+        let mut found = vec![];
+
+        for year in fs::read_dir(&self.root)? {
+            let year = year?.path();
+            let Some(y) = year.file_name().and_then(|n| n.to_str()?.parse().ok()) else {
+                continue;
+            };
+
+            for day in fs::read_dir(&year)? {
+                let day = day?.path();
+                if let Some(d) = day.file_name().and_then(|n| n.to_str()?.parse().ok()) {
+                    found.push((y, d));
+                }
+            }
+        }
+
+        found.sort();
+        Ok(found)
+    }
+}
+
+fn normalised(contents: &str) -> String {
+    let lines: Vec<_> = contents.lines().map(str::trim_end).collect();
+
+    lines.join("\n").trim_end().to_string()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::fetch::Mirror;
+
+    struct TempDir(PathBuf);
+
+    impl TempDir {
+        fn new(name: &str) -> Self {
+            let dir = std::env::temp_dir().join(format!("aoc-{}-{}", name, std::process::id()));
+            let _ = fs::remove_dir_all(&dir);
+            fs::create_dir_all(&dir).unwrap();
+            Self(dir)
+        }
+
+        fn write(&self, path: &str, contents: &str) {
+            let path = self.0.join(path);
+            fs::create_dir_all(path.parent().unwrap()).unwrap();
+            fs::write(path, contents).unwrap();
+        }
+    }
+
+    impl Drop for TempDir {
+        fn drop(&mut self) {
+            let _ = fs::remove_dir_all(&self.0);
+        }
+    }
+
+    fn names(paths: &[PathBuf]) -> Vec<&str> {
+        paths
+            .iter()
+            .map(|p| p.file_name().unwrap().to_str().unwrap())
+            .collect()
+    }
+
+    #[test]
+    fn store_classifies_names() {
+        let exps = vec![
+            ("input", Some(FileKind::Input)),
+            ("input.txt", Some(FileKind::Input)),
+            ("sample.txt", Some(FileKind::Sample { part: 1, index: 1 })),
+            ("sample2.txt", Some(FileKind::Sample { part: 1, index: 2 })),
+            ("sample_2.txt", Some(FileKind::Sample { part: 1, index: 2 })),
+            (
+                "part_2_sample_0.txt",
+                Some(FileKind::Sample { part: 2, index: 0 }),
+            ),
+            ("pattern1.txt", None),
+            ("sample_1.expected", None),
+        ];
+
+        for (name, exp) in exps {
+            assert_eq!(classify(name), exp, "{}", name);
+        }
+    }
+
+    #[test]
+    fn store_normalises_names() {
+        let tmp = TempDir::new("normalise");
+        tmp.write("2023/10/input", "in");
+        tmp.write("2023/10/sample.txt", "a");
+        tmp.write("2023/10/sample_2.txt", "b");
+        tmp.write("2023/10/part_2_sample_0.txt", "c");
+        tmp.write("2023/10/notes.md", "");
+        let store = InputStore::new(&tmp.0);
+
+        assert_eq!(store.plan(2023, 10).unwrap().len(), 3);
+        store.normalise(2023, 10).unwrap();
+
+        assert!(store.plan(2023, 10).unwrap().is_empty());
+        let samples = store.samples(2023, 10).unwrap();
+        assert_eq!(
+            names(&samples),
+            vec!["sample_1.txt", "sample_2.txt", "sample_3.txt"]
+        );
+        assert_eq!(fs::read_to_string(&samples[2]).unwrap(), "c");
+        assert_eq!(store.input(2023, 10), Some(tmp.0.join("2023/10/input.txt")));
+        assert!(tmp.0.join("2023/10/notes.md").exists());
+    }
+
+    #[test]
+    fn store_normalises_clashing_names() {
+        let tmp = TempDir::new("clashing");
+        tmp.write("2023/10/sample", "a");
+        tmp.write("2023/10/sample.txt", "b");
+        tmp.write("2023/10/sample_2", "c");
+        tmp.write("2023/10/sample_2.txt", "d");
+        let store = InputStore::new(&tmp.0);
+
+        store.normalise(2023, 10).unwrap();
+
+        let samples = store.samples(2023, 10).unwrap();
+        let contents: Vec<_> = samples
+            .iter()
+            .map(|s| fs::read_to_string(s).unwrap())
+            .collect();
+        assert_eq!(contents, vec!["a", "b", "c", "d"]);
+        assert!(!fs::read_dir(tmp.0.join("2023/10")).unwrap().any(|e| e
+            .unwrap()
+            .file_name()
+            .to_string_lossy()
+            .ends_with(".renaming")));
+    }
+
+    #[test]
+    fn store_moves_answers_with_samples() {
+        let tmp = TempDir::new("answers");
+        tmp.write("2023/10/sample.txt", "a");
+        tmp.write("2023/10/sample.expected", "part 1: 1\n");
+        tmp.write("2023/10/sample_1.txt", "b");
+        tmp.write("2023/10/sample_1.expected", "part 1: 2\n");
+        tmp.write("2023/10/part_2_sample_0.txt", "c");
+        tmp.write("2023/10/part_2_sample_0.expected", "part 2: 3\n");
+        let store = InputStore::new(&tmp.0);
+
+        store.normalise(2023, 10).unwrap();
+
+        for (n, contents, answers) in [(1, "a", "1"), (2, "b", "2"), (3, "c", "3")] {
+            let sample = tmp.0.join(format!("2023/10/sample_{}.txt", n));
+            let expected = fs::read_to_string(sample.with_extension("expected")).unwrap();
+
+            assert_eq!(fs::read_to_string(&sample).unwrap(), contents);
+            assert!(
+                expected.ends_with(&format!(": {}\n", answers)),
+                "{}",
+                expected
+            );
+        }
+        assert!(!tmp.0.join("2023/10/sample.expected").exists());
+    }
+
+    #[test]
+    fn store_normalise_keeps_stray_answers() {
+        let tmp = TempDir::new("stray");
+        tmp.write("2023/10/sample.txt", "a");
+        tmp.write("2023/10/sample_1.expected", "part 1: 1\n");
+        tmp.write("2023/10/sample_0.txt", "b");
+        tmp.write("2023/10/sample_0.expected", "part 1: 0\n");
+        let store = InputStore::new(&tmp.0);
+
+        assert!(store.normalise(2023, 10).is_err());
+        assert!(tmp.0.join("2023/10/sample.txt").exists());
+        assert!(tmp.0.join("2023/10/sample_0.txt").exists());
+        assert_eq!(
+            fs::read_to_string(tmp.0.join("2023/10/sample_1.expected")).unwrap(),
+            "part 1: 1\n"
+        );
+    }
+
+    #[test]
+    fn store_finds_duplicates() {
+        let tmp = TempDir::new("duplicates");
+        tmp.write("2023/08/sample_1.txt", "RL\n\nAAA\n");
+        tmp.write("2023/08/sample_2.txt", "LLR\n");
+        tmp.write("2024/01/sample.txt", "RL  \n\nAAA");
+        let store = InputStore::new(&tmp.0);
+
+        let duplicates = store.duplicates().unwrap();
+
+        assert_eq!(duplicates.len(), 1);
+        assert_eq!(names(&duplicates[0]), vec!["sample_1.txt", "sample.txt"]);
+    }
+
+    #[test]
+    fn store_fetches_only_missing() {
+        let tmp = TempDir::new("fetch");
+        tmp.write("mirror/2023/01/input.txt", "1abc2");
+        tmp.write("mirror/2023/01/puzzle.html", "<article></article>");
+        tmp.write("repo/2023/01/puzzle.html", "saved");
+        let store = InputStore::new(&tmp.0.join("repo"));
+        let mirror = Mirror {
+            dir: tmp.0.join("mirror"),
+        };
+
+        assert_eq!(
+            store.fetch(2023, 1, &mirror).unwrap(),
+            vec![Resource::Input]
+        );
+        assert!(store.fetch(2023, 1, &mirror).unwrap().is_empty());
+        assert_eq!(
+            fs::read_to_string(tmp.0.join("repo/2023/01/puzzle.html")).unwrap(),
+            "saved"
+        );
+        assert!(store.fetch(2023, 2, &mirror).is_err());
+    }
+
+    #[test]
+    fn store_adds_new_samples() {
+        let tmp = TempDir::new("samples");
+        tmp.write("2023/01/sample_1.txt", "1abc2\n");
+        let store = InputStore::new(&tmp.0);
+        let samples = vec![
+            Sample {
+                text: "1abc2\n".into(),
+                answers: vec![],
+            },
+            Sample {
+                text: "two1nine\n".into(),
+                answers: vec![(2, "29".into())],
+            },
+        ];
+
+        let added = store.add_samples(2023, 1, &samples).unwrap();
+
+        assert_eq!(names(&added), vec!["sample_2.txt"]);
+        assert_eq!(
+            fs::read_to_string(tmp.0.join("2023/01/sample_2.expected")).unwrap(),
+            "part 2: 29\n"
+        );
+        assert!(store.add_samples(2023, 1, &samples).unwrap().is_empty());
+    }
+}
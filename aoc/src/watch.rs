@@ -0,0 +1,344 @@
+use std::{
+    collections::{BTreeMap, HashMap},
+    error::Error,
+    fs,
+    io::{self, Write},
+    path::{Path, PathBuf},
+    process::Command,
+    thread,
+    time::{Duration, Instant},
+};
+
+use common::{answer, Answer};
+use inotify::{EventMask, Inotify, WatchDescriptor, WatchMask};
+
+use crate::{
+    days::Day,
+    store::{self, InputStore},
+};
+
+// long enough for an editor to finish saving before rebuilding
+const SETTLE: Duration = Duration::from_millis(150);
+
+// compiler errors shown when a build fails, the rest are only counted
+const SHOWN_ERRORS: usize = 5;
+
+/// Rebuilds and reruns a day, samples first then the input, whenever its
+/// sources or inputs change, until interrupted.
+pub fn watch(root: &Path, d: &Day) -> Result<(), Box<dyn Error>> {
+    let store = InputStore::new(root);
+    let mut watcher = Watcher::new(&store.dir(d.year, d.day))?;
+    let mut last = HashMap::new();
+
+    loop {
+        let panel = check(&store, d, &mut last)?;
+        print!("\x1b[H\x1b[2J{}", panel);
+        io::stdout().flush()?;
+
+        watcher.wait()?;
+    }
+}
+
+/// The day's directory and everything under `src`, including directories
+/// made there after watching started.
+struct Watcher {
+    inotify: Inotify,
+    src: PathBuf,
+    dirs: HashMap<WatchDescriptor, PathBuf>,
+}
+
+impl Watcher {
+    const MASK: WatchMask = WatchMask::CLOSE_WRITE
+        .union(WatchMask::CREATE)
+        .union(WatchMask::DELETE)
+        .union(WatchMask::MOVED_TO);
+
+    fn new(dir: &Path) -> Result<Self, Box<dyn Error>> {
+        let inotify = Inotify::init()?;
+        let wd = inotify.watches().add(dir, Self::MASK)?;
+
+        let mut watcher = Self {
+            inotify,
+            src: dir.join("src"),
+            dirs: HashMap::from([(wd, dir.to_path_buf())]),
+        };
+        watcher.add(&watcher.src.clone())?;
+
+        Ok(watcher)
+    }
+
+    // `top` and every directory under it
+    fn add(&mut self, top: &Path) -> Result<(), Box<dyn Error>> {
+        let mut unread = vec![top.to_path_buf()];
+
+        while let Some(next) = unread.pop() {
+            for entry in fs::read_dir(&next)? {
+                let path = entry?.path();
+                if path.is_dir() {
+                    unread.push(path);
+                }
+            }
+
+            let wd = self.inotify.watches().add(&next, Self::MASK)?;
+            self.dirs.insert(wd, next);
+        }
+
+        Ok(())
+    }
+
+    /// Blocks until something worth a rerun changes, then lets the rest of a
+    /// save land, since one is often several events.
+    fn wait(&mut self) -> Result<(), Box<dyn Error>> {
+        while !self.read(true)?.1 {}
+
+        thread::sleep(SETTLE);
+        while self.read(false)?.0 > 0 {}
+
+        Ok(())
+    }
+
+    // how many events there were, and whether any is worth a rerun
+    fn read(&mut self, blocking: bool) -> Result<(usize, bool), Box<dyn Error>> {
+        let mut buffer = [0; 4096];
+        let events = match blocking {
+            true => self.inotify.read_events_blocking(&mut buffer),
+            false => self.inotify.read_events(&mut buffer),
+        };
+        let events = match events {
+            Ok(events) => events,
+            Err(e) if e.kind() == io::ErrorKind::WouldBlock => return Ok((0, false)),
+            Err(e) => return Err(e.into()),
+        };
+
+        let mut count = 0;
+        let mut rerun = false;
+        let mut made = vec![];
+
+        for event in events {
+            count += 1;
+            let Some(name) = event.name.and_then(|n| n.to_str()) else {
+                continue;
+            };
+
+            if !event.mask.contains(EventMask::ISDIR) {
+                rerun |= relevant(name);
+                continue;
+            }
+
+            let made_in = self.dirs.get(&event.wd);
+            if event
+                .mask
+                .intersects(EventMask::CREATE | EventMask::MOVED_TO)
+                && made_in.is_some_and(|dir| dir.starts_with(&self.src))
+            {
+                made.extend(made_in.map(|dir| dir.join(name)));
+            }
+        }
+
+        // whatever came with a new directory was never seen being written
+        for dir in made {
+            self.add(&dir)?;
+            rerun = true;
+        }
+
+        Ok((count, rerun))
+    }
+}
+
+/// Whether a changed file is worth a rerun, leaving out editor droppings and
+/// what builds write.
+fn relevant(name: &str) -> bool {
+    if name.starts_with('.') || name.ends_with('~') || name == "Cargo.lock" {
+        return false;
+    }
+
+    let extension = Path::new(name).extension().and_then(|e| e.to_str());
+
+    matches!(extension, Some("rs" | "toml" | "expected")) || store::classify(name).is_some()
+}
+
+/// Builds and runs the day once, giving the panel to show.
+fn check(
+    store: &InputStore,
+    d: &Day,
+    last: &mut HashMap<PathBuf, BTreeMap<u8, Answer>>,
+) -> Result<String, Box<dyn Error>> {
+    let manifest = store.dir(d.year, d.day).join("Cargo.toml");
+    let mut panel = format!("{} day {} ({})\n", d.year, d.day, d.name);
+
+    let started = Instant::now();
+    let build = Command::new("cargo")
+        .args(["build", "--release", "--message-format", "short"])
+        .arg("--manifest-path")
+        .arg(&manifest)
+        .output()?;
+
+    if !build.status.success() {
+        panel.push_str(&build_failure(&String::from_utf8_lossy(&build.stderr)));
+        return Ok(panel);
+    }
+    panel.push_str(&format!(
+        "built in {:.1}s\n",
+        started.elapsed().as_secs_f64()
+    ));
+
+    let mut files = store.samples(d.year, d.day)?;
+    files.extend(store.input(d.year, d.day));
+
+    for file in files {
+        let name = file
+            .file_name()
+            .unwrap_or_default()
+            .to_string_lossy()
+            .to_string();
+
+        let started = Instant::now();
+        let run = Command::new("cargo")
+            .args(["run", "--quiet", "--release", "--manifest-path"])
+            .arg(&manifest)
+            .arg("--")
+            .arg(&file)
+            .env(answer::VAR, "1")
+            .output()?;
+        let took = started.elapsed();
+
+        if !run.status.success() {
+            let stderr = String::from_utf8_lossy(&run.stderr);
+            let reason = stderr.lines().last().unwrap_or("no output");
+
+            panel.push_str(&format!("  {:<16} failed: {}\n", name, reason));
+            last.remove(&file);
+            continue;
+        }
+
+        let got = answer::parse(&String::from_utf8_lossy(&run.stdout));
+        let expected = fs::read_to_string(file.with_extension("expected"))
+            .map(|e| answer::parse(&e))
+            .unwrap_or_default();
+
+        let line = status(&got, &expected, last.get(&file));
+        panel.push_str(&format!(
+            "  {:<16} {}  ({:.2}s)\n",
+            name,
+            line,
+            took.as_secs_f64()
+        ));
+
+        last.insert(file, got);
+    }
+
+    Ok(panel)
+}
+
+/// Each answer, marked against the expected answer for its part and with
+/// what it was on the last run if that's changed.
+fn status(
+    got: &BTreeMap<u8, Answer>,
+    expected: &BTreeMap<u8, Answer>,
+    last: Option<&BTreeMap<u8, Answer>>,
+) -> String {
+    if got.is_empty() {
+        return "no answers".to_string();
+    }
+
+    let marked: Vec<_> = got
+        .iter()
+        .map(|(part, answer)| {
+            let mut s = answer.to_string();
+
+            match expected.get(part) {
+                Some(e) if e == answer => s.push_str(" ✓"),
+                Some(e) => s.push_str(&format!(" ✗ want {}", e)),
+                None => (),
+            }
+
+            match last.and_then(|l| l.get(part)) {
+                Some(was) if was != answer => s.push_str(&format!(" (was {})", was)),
+                _ => (),
+            }
+
+            s
+        })
+        .collect();
+
+    marked.join(" | ")
+}
+
+/// A line counting errors and warnings, then the first few errors.
+fn build_failure(stderr: &str) -> String {
+    let errors: Vec<_> = stderr
+        .lines()
+        .filter(|l| l.contains(": error") || l.starts_with("error"))
+        .filter(|l| !l.starts_with("error: could not compile"))
+        .collect();
+    let warnings = stderr.lines().filter(|l| l.contains(": warning")).count();
+
+    let mut s = format!(
+        "build failed: {} errors, {} warnings\n",
+        errors.len(),
+        warnings
+    );
+    for e in errors.iter().take(SHOWN_ERRORS) {
+        s.push_str(&format!("  {}\n", e));
+    }
+
+    s
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn answers(a: &[(u8, i64)]) -> BTreeMap<u8, Answer> {
+        a.iter().map(|(part, n)| (*part, Answer::Int(*n))).collect()
+    }
+
+    #[test]
+    fn watch_picks_relevant_changes() {
+        assert!(relevant("lib.rs"));
+        assert!(relevant("Cargo.toml"));
+        assert!(relevant("input.txt"));
+        assert!(relevant("sample_2.txt"));
+        assert!(relevant("sample_2.expected"));
+
+        assert!(!relevant("Cargo.lock"));
+        assert!(!relevant(".lib.rs.swp"));
+        assert!(!relevant("lib.rs~"));
+        assert!(!relevant("4913"));
+        assert!(!relevant("notes.md"));
+    }
+
+    #[test]
+    fn watch_marks_answers() {
+        let got = answers(&[(1, 13), (2, 30)]);
+        let expected = answers(&[(1, 13), (2, 31)]);
+
+        assert_eq!(status(&got, &expected, None), "13 ✓ | 30 ✗ want 31");
+        assert_eq!(
+            status(&got, &BTreeMap::new(), Some(&answers(&[(1, 13), (2, 29)]))),
+            "13 | 30 (was 29)"
+        );
+        assert_eq!(
+            status(&answers(&[(2, 31)]), &expected, None),
+            "31 ✓",
+            "a day that only answers part 2"
+        );
+        assert_eq!(status(&BTreeMap::new(), &expected, None), "no answers");
+    }
+
+    #[test]
+    fn watch_summarises_build_failures() {
+        let stderr = "   Compiling day v0.1.0
+src/lib.rs:3:5: warning: unused variable: `x`
+src/lib.rs:9:1: error[E0425]: cannot find value `y` in this scope
+error: could not compile `day` (lib) due to 1 previous error
+";
+
+        assert_eq!(
+            build_failure(stderr),
+            "build failed: 1 errors, 1 warnings
+  src/lib.rs:9:1: error[E0425]: cannot find value `y` in this scope
+"
+        );
+    }
+}
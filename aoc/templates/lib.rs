@@ -0,0 +1,44 @@
+use std::str::FromStr;
+
+use common::Answer;
+
+#[tracing::instrument(skip_all)]
+pub fn part1(contents: &str) -> Result<Answer, String> {
+    let puzzle: Puzzle = contents.parse()?;
+
+    Ok(puzzle.lines.len().into())
+}
+
+#[tracing::instrument(skip_all)]
+pub fn part2(_contents: &str) -> Result<Answer, String> {
+    Err("part 2 is not solved yet".into())
+}
+
+#[derive(Debug, PartialEq, Eq)]
+pub struct Puzzle {
+    pub lines: Vec<String>,
+}
+
+impl FromStr for Puzzle {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let lines = s.lines().map(String::from).collect();
+
+        Ok(Self { lines })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const SAMPLE: &str = include_str!("../sample_1.txt");
+
+    #[test]
+    fn puzzle_parses_sample() {
+        let puzzle: Puzzle = SAMPLE.parse().unwrap();
+
+        assert_eq!(puzzle.lines.len(), SAMPLE.lines().count());
+    }
+}
@@ -0,0 +1,20 @@
+use std::{env, fs};
+
+fn main() -> Result<(), String> {
+    let _day = common::trace::init("{{name}}");
+
+    let args: Vec<_> = env::args().collect();
+    let filename = args.get(1).expect("filename");
+    let contents = fs::read_to_string(filename).expect("contents");
+
+    if common::answer::for_tools() {
+        common::answer::print(1, &{{lib}}::part1(&contents)?);
+        common::answer::print(2, &{{lib}}::part2(&contents)?);
+        return Ok(());
+    }
+
+    println!("solution 1: {}", {{lib}}::part1(&contents)?);
+    println!("solution 2: {}", {{lib}}::part2(&contents)?);
+
+    Ok(())
+}
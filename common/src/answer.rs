@@ -0,0 +1,154 @@
+use std::{collections::BTreeMap, convert::Infallible, env, fmt::Display, str::FromStr};
+
+/// Set by tools that read a day's answers, like `aoc watch` and `aoc shrink`,
+/// so the day prints them in a form they can rely on.
+pub const VAR: &str = "AOC_ANSWERS";
+
+/// What a part of a puzzle comes to. Numbers are kept in the smallest variant
+/// they fit, so the same value always compares equal however it was made.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum Answer {
+    Int(i64),
+    /// too big for an `Int`
+    Big(i128),
+    Text(String),
+}
+
+impl Answer {
+    /// The answer as a number, if it is one.
+    pub fn as_i128(&self) -> Option<i128> {
+        match self {
+            Answer::Int(n) => Some(*n as i128),
+            Answer::Big(n) => Some(*n),
+            Answer::Text(_) => None,
+        }
+    }
+}
+
+impl Display for Answer {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Answer::Int(n) => write!(f, "{}", n),
+            Answer::Big(n) => write!(f, "{}", n),
+            Answer::Text(s) => write!(f, "{}", s),
+        }
+    }
+}
+
+/// Numbers where it reads as one, like the answers written down in
+/// `sample_N.expected` files.
+impl FromStr for Answer {
+    type Err = Infallible;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let s = s.trim();
+
+        Ok(match s.parse::<i128>() {
+            Ok(n) => n.into(),
+            Err(_) => Answer::Text(s.to_owned()),
+        })
+    }
+}
+
+impl From<i128> for Answer {
+    fn from(n: i128) -> Self {
+        match i64::try_from(n) {
+            Ok(n) => Answer::Int(n),
+            Err(_) => Answer::Big(n),
+        }
+    }
+}
+
+impl From<u128> for Answer {
+    fn from(n: u128) -> Self {
+        match i128::try_from(n) {
+            Ok(n) => n.into(),
+            Err(_) => Answer::Text(n.to_string()),
+        }
+    }
+}
+
+macro_rules! from_int {
+    ($($t:ty),*) => {
+        $(
+            impl From<$t> for Answer {
+                fn from(n: $t) -> Self {
+                    (n as i128).into()
+                }
+            }
+        )*
+    };
+}
+
+from_int!(i8, i16, i32, i64, isize, u8, u16, u32, u64, usize);
+
+impl From<String> for Answer {
+    fn from(s: String) -> Self {
+        Answer::Text(s)
+    }
+}
+
+impl From<&str> for Answer {
+    fn from(s: &str) -> Self {
+        Answer::Text(s.to_owned())
+    }
+}
+
+/// Whether a tool has asked for the answers with `AOC_ANSWERS`. A day's
+/// binary then prints only its answers, each with `print`.
+pub fn for_tools() -> bool {
+    env::var_os(VAR).is_some_and(|v| !v.is_empty() && v != "0")
+}
+
+/// Prints one part's answer as a `part N: answer` line, the way
+/// `sample_N.expected` files write them down.
+pub fn print(part: u8, answer: &Answer) {
+    println!("part {}: {}", part, answer);
+}
+
+/// The answers in `part N: answer` lines, by part. Other lines are skipped.
+pub fn parse(text: &str) -> BTreeMap<u8, Answer> {
+    text.lines()
+        .filter_map(|l| {
+            let (part, answer) = l.strip_prefix("part ")?.split_once(": ")?;
+            Some((part.parse().ok()?, answer.parse().ok()?))
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn answer_keeps_numbers_small() {
+        assert_eq!(Answer::from(5u64), Answer::Int(5));
+        assert_eq!(Answer::from(-5i128), Answer::Int(-5));
+        assert_eq!(Answer::from(u64::MAX), Answer::Big(u64::MAX as i128));
+        assert_eq!(Answer::from(u128::MAX), Answer::Text(u128::MAX.to_string()));
+        assert_eq!(Answer::from(u64::MAX).as_i128(), Some(u64::MAX as i128));
+    }
+
+    #[test]
+    fn answer_parses_what_it_displays() {
+        for answer in [
+            Answer::Int(-12),
+            Answer::Big(i128::MIN),
+            Answer::Text("ABC".into()),
+        ] {
+            assert_eq!(answer.to_string().parse::<Answer>(), Ok(answer));
+        }
+
+        assert_eq!(" 42\n".parse::<Answer>(), Ok(Answer::Int(42)));
+    }
+
+    #[test]
+    fn answer_lines_parse_by_part() {
+        let answers = parse("part 1: 13\npart 1 occurrences: 4\ntotal: 9\npart 2: ABC\n");
+
+        assert_eq!(
+            answers,
+            BTreeMap::from([(1, Answer::Int(13)), (2, Answer::Text("ABC".into()))])
+        );
+    }
+}
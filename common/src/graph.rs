@@ -0,0 +1,555 @@
+//! Searches over anything that can say what a node's neighbours are.
+
+use std::{
+    cmp::Reverse,
+    collections::{BinaryHeap, HashMap, HashSet, VecDeque},
+    error::Error,
+    fmt::{Debug, Display},
+    hash::Hash,
+    marker::PhantomData,
+};
+
+/// A graph is whatever can list the neighbours of a node. Edges cost 1 unless
+/// `edges` says otherwise.
+pub trait Graph {
+    type Node: Clone + Eq + Hash;
+
+    fn neighbours(&self, node: &Self::Node) -> impl IntoIterator<Item = Self::Node>;
+
+    /// Neighbours with what it costs to get to each.
+    fn edges(&self, node: &Self::Node) -> impl IntoIterator<Item = (Self::Node, u64)> {
+        self.neighbours(node).into_iter().map(|n| (n, 1))
+    }
+}
+
+/// A graph from a function giving the neighbours of a node.
+pub struct FromFn<N, F> {
+    neighbours: F,
+    node: PhantomData<fn() -> N>,
+}
+
+pub fn from_fn<N, I, F>(neighbours: F) -> FromFn<N, F>
+where
+    F: Fn(&N) -> I,
+    I: IntoIterator<Item = N>,
+{
+    FromFn {
+        neighbours,
+        node: PhantomData,
+    }
+}
+
+impl<N, I, F> Graph for FromFn<N, F>
+where
+    N: Clone + Eq + Hash,
+    F: Fn(&N) -> I,
+    I: IntoIterator<Item = N>,
+{
+    type Node = N;
+
+    fn neighbours(&self, node: &N) -> impl IntoIterator<Item = N> {
+        (self.neighbours)(node)
+    }
+}
+
+/// Found going round a cycle where there shouldn't be one.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Cycle<N>(pub N);
+
+impl<N: Debug> Display for Cycle<N> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "found a cycle through {:?}", self.0)
+    }
+}
+
+impl<N: Debug> Error for Cycle<N> {}
+
+/// Why paths from a node couldn't be counted.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum CountError<N> {
+    /// the paths go round a cycle through this node, so never stop
+    Cycle(N),
+    /// more paths from this node than fit in a u64
+    Overflow(N),
+}
+
+impl<N: Debug> Display for CountError<N> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            CountError::Cycle(n) => write!(f, "found a cycle through {:?}", n),
+            CountError::Overflow(n) => write!(f, "too many paths from {:?} to count", n),
+        }
+    }
+}
+
+impl<N: Debug> Error for CountError<N> {}
+
+/// Breadth first from every start at once, giving each node reached with how
+/// many steps it is from the nearest start.
+pub fn bfs<G: Graph>(graph: &G, starts: impl IntoIterator<Item = G::Node>) -> Bfs<'_, G> {
+    let mut seen = HashSet::new();
+    let queue = starts
+        .into_iter()
+        .filter(|s| seen.insert(s.clone()))
+        .map(|s| (s, 0))
+        .collect();
+
+    Bfs { graph, queue, seen }
+}
+
+pub struct Bfs<'a, G: Graph> {
+    graph: &'a G,
+    queue: VecDeque<(G::Node, usize)>,
+    seen: HashSet<G::Node>,
+}
+
+impl<G: Graph> Iterator for Bfs<'_, G> {
+    type Item = (G::Node, usize);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let (node, steps) = self.queue.pop_front()?;
+
+        for n in self.graph.neighbours(&node) {
+            if self.seen.insert(n.clone()) {
+                self.queue.push_back((n, steps + 1));
+            }
+        }
+
+        Some((node, steps))
+    }
+}
+
+/// Depth first from `start`, giving nodes in the order they're first reached.
+pub fn dfs<G: Graph>(graph: &G, start: G::Node) -> Dfs<'_, G> {
+    Dfs {
+        graph,
+        stack: vec![start],
+        seen: HashSet::new(),
+    }
+}
+
+pub struct Dfs<'a, G: Graph> {
+    graph: &'a G,
+    stack: Vec<G::Node>,
+    seen: HashSet<G::Node>,
+}
+
+impl<G: Graph> Iterator for Dfs<'_, G> {
+    type Item = G::Node;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        while let Some(node) = self.stack.pop() {
+            if !self.seen.insert(node.clone()) {
+                continue;
+            }
+
+            // reversed so the first neighbour is the next one visited
+            let next: Vec<_> = self.graph.neighbours(&node).into_iter().collect();
+            self.stack
+                .extend(next.into_iter().rev().filter(|n| !self.seen.contains(n)));
+
+            return Some(node);
+        }
+
+        None
+    }
+}
+
+/// The cheapest way from one node to another, both ends included.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Path<N> {
+    pub nodes: Vec<N>,
+    pub cost: u64,
+}
+
+/// The cheapest path from `start` to any node that `is_goal`.
+pub fn dijkstra<G: Graph>(
+    graph: &G,
+    start: G::Node,
+    is_goal: impl FnMut(&G::Node) -> bool,
+) -> Option<Path<G::Node>> {
+    astar(graph, start, is_goal, |_| 0)
+}
+
+/// Like `dijkstra`, trying nodes `heuristic` thinks are closer first. The
+/// path is only the cheapest if `heuristic` never overestimates.
+pub fn astar<G: Graph>(
+    graph: &G,
+    start: G::Node,
+    mut is_goal: impl FnMut(&G::Node) -> bool,
+    heuristic: impl Fn(&G::Node) -> u64,
+) -> Option<Path<G::Node>> {
+    // nodes get ids in the order they're found, which the rest is kept by
+    let mut ids = HashMap::from([(start.clone(), 0)]);
+    let mut nodes = vec![start];
+    let mut costs = vec![0];
+    let mut came_from: Vec<Option<usize>> = vec![None];
+
+    let mut queue = BinaryHeap::from([Reverse((heuristic(&nodes[0]), 0, 0))]);
+
+    while let Some(Reverse((_, cost, id))) = queue.pop() {
+        if cost > costs[id] {
+            continue;
+        }
+
+        if is_goal(&nodes[id]) {
+            let mut path = vec![nodes[id].clone()];
+            let mut at = id;
+            while let Some(previous) = came_from[at] {
+                path.push(nodes[previous].clone());
+                at = previous;
+            }
+
+            path.reverse();
+            return Some(Path { nodes: path, cost });
+        }
+
+        let edges: Vec<_> = graph.edges(&nodes[id]).into_iter().collect();
+        for (next, step) in edges {
+            let next_cost = cost.saturating_add(step);
+            let next_id = *ids.entry(next.clone()).or_insert_with(|| {
+                nodes.push(next);
+                costs.push(u64::MAX);
+                came_from.push(None);
+                nodes.len() - 1
+            });
+
+            if next_cost < costs[next_id] {
+                costs[next_id] = next_cost;
+                came_from[next_id] = Some(id);
+
+                let estimate = next_cost.saturating_add(heuristic(&nodes[next_id]));
+                queue.push(Reverse((estimate, next_cost, next_id)));
+            }
+        }
+    }
+
+    None
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Visit {
+    Open,
+    Done,
+}
+
+/// `nodes` and everything they lead to, with every node before all of its
+/// neighbours.
+pub fn topological_sort<G: Graph>(
+    graph: &G,
+    nodes: impl IntoIterator<Item = G::Node>,
+) -> Result<Vec<G::Node>, Cycle<G::Node>> {
+    let mut visits = HashMap::new();
+    let mut order = vec![];
+
+    for root in nodes {
+        if visits.contains_key(&root) {
+            continue;
+        }
+
+        visits.insert(root.clone(), Visit::Open);
+        let mut stack = vec![(root.clone(), neighbours(graph, &root))];
+
+        while let Some((_, next)) = stack.last_mut() {
+            let Some(next) = next.next() else {
+                let (node, _) = stack.pop().unwrap();
+                visits.insert(node.clone(), Visit::Done);
+                order.push(node);
+                continue;
+            };
+
+            match visits.get(&next) {
+                Some(Visit::Open) => return Err(Cycle(next)),
+                Some(Visit::Done) => {}
+                None => {
+                    visits.insert(next.clone(), Visit::Open);
+                    let after = neighbours(graph, &next);
+                    stack.push((next, after));
+                }
+            }
+        }
+    }
+
+    order.reverse();
+    Ok(order)
+}
+
+/// The strongly connected components of `nodes` and everything they lead to,
+/// with a component before any that lead to it.
+pub fn strongly_connected_components<G: Graph>(
+    graph: &G,
+    nodes: impl IntoIterator<Item = G::Node>,
+) -> Vec<Vec<G::Node>> {
+    let mut tarjan = Tarjan {
+        graph,
+        ids: HashMap::new(),
+        found: vec![],
+        low: vec![],
+        on_stack: vec![],
+        stack: vec![],
+        calls: vec![],
+    };
+    let mut components = vec![];
+
+    for root in nodes {
+        if tarjan.ids.contains_key(&root) {
+            continue;
+        }
+
+        tarjan.visit(root);
+
+        while let Some((id, next)) = tarjan.calls.last_mut() {
+            let id = *id;
+
+            if let Some(next) = next.next() {
+                match tarjan.ids.get(&next) {
+                    Some(&next_id) if tarjan.on_stack[next_id] => {
+                        tarjan.low[id] = tarjan.low[id].min(next_id)
+                    }
+                    Some(_) => {}
+                    None => tarjan.visit(next),
+                }
+
+                continue;
+            }
+
+            tarjan.calls.pop();
+            if let Some((parent, _)) = tarjan.calls.last() {
+                tarjan.low[*parent] = tarjan.low[*parent].min(tarjan.low[id]);
+            }
+
+            if tarjan.low[id] == id {
+                components.push(tarjan.component(id));
+            }
+        }
+    }
+
+    components
+}
+
+// a node's id is the order it was found in
+struct Tarjan<'a, G: Graph> {
+    graph: &'a G,
+    ids: HashMap<G::Node, usize>,
+    found: Vec<G::Node>,
+    low: Vec<usize>,
+    on_stack: Vec<bool>,
+    stack: Vec<usize>,
+    // nodes being visited, with the neighbours left to look at
+    calls: Vec<(usize, std::vec::IntoIter<G::Node>)>,
+}
+
+impl<G: Graph> Tarjan<'_, G> {
+    fn visit(&mut self, node: G::Node) {
+        let id = self.found.len();
+
+        self.ids.insert(node.clone(), id);
+        self.calls.push((id, neighbours(self.graph, &node)));
+        self.found.push(node);
+        self.low.push(id);
+        self.on_stack.push(true);
+        self.stack.push(id);
+    }
+
+    // everything on the stack down to `root`
+    fn component(&mut self, root: usize) -> Vec<G::Node> {
+        let mut component = vec![];
+
+        while let Some(member) = self.stack.pop() {
+            self.on_stack[member] = false;
+            component.push(self.found[member].clone());
+
+            if member == root {
+                break;
+            }
+        }
+
+        component
+    }
+}
+
+/// How many paths lead from each node to one that `is_end`, for `starts` and
+/// every node reached from them. Paths stop at the first end they reach, and
+/// would never stop on a cycle, so one is an error, as is a count too big for
+/// a u64.
+pub fn count_paths<G: Graph>(
+    graph: &G,
+    starts: impl IntoIterator<Item = G::Node>,
+    mut is_end: impl FnMut(&G::Node) -> bool,
+) -> Result<HashMap<G::Node, u64>, CountError<G::Node>> {
+    let mut counts = HashMap::new();
+    let mut open = HashSet::new();
+
+    for start in starts {
+        if counts.contains_key(&start) {
+            continue;
+        }
+
+        if is_end(&start) {
+            counts.insert(start, 1);
+            continue;
+        }
+
+        open.insert(start.clone());
+        let mut stack = vec![(start.clone(), neighbours(graph, &start), 0u64)];
+
+        while let Some((node, next, total)) = stack.last_mut() {
+            let Some(next) = next.next() else {
+                let (node, _, total) = stack.pop().unwrap();
+                open.remove(&node);
+                counts.insert(node, total);
+
+                if let Some((parent, _, parent_total)) = stack.last_mut() {
+                    *parent_total = parent_total
+                        .checked_add(total)
+                        .ok_or_else(|| CountError::Overflow(parent.clone()))?;
+                }
+
+                continue;
+            };
+
+            if let Some(count) = counts.get(&next) {
+                *total = total
+                    .checked_add(*count)
+                    .ok_or_else(|| CountError::Overflow(node.clone()))?;
+            } else if open.contains(&next) {
+                return Err(CountError::Cycle(next));
+            } else if is_end(&next) {
+                counts.insert(next, 1);
+                *total = total
+                    .checked_add(1)
+                    .ok_or_else(|| CountError::Overflow(node.clone()))?;
+            } else {
+                open.insert(next.clone());
+                let after = neighbours(graph, &next);
+                stack.push((next, after, 0));
+            }
+        }
+    }
+
+    Ok(counts)
+}
+
+// collected, so nothing borrows the graph while searches push more nodes
+fn neighbours<G: Graph>(graph: &G, node: &G::Node) -> std::vec::IntoIter<G::Node> {
+    graph
+        .neighbours(node)
+        .into_iter()
+        .collect::<Vec<_>>()
+        .into_iter()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // 0 -> 1 -> 2 -> 3, 0 -> 2, and 4 on its own
+    fn line() -> FromFn<u32, impl Fn(&u32) -> Vec<u32>> {
+        from_fn(|n: &u32| match n {
+            0 => vec![1, 2],
+            1 => vec![2],
+            2 => vec![3],
+            _ => vec![],
+        })
+    }
+
+    struct Weighted;
+
+    impl Graph for Weighted {
+        type Node = char;
+
+        fn neighbours(&self, node: &char) -> impl IntoIterator<Item = char> {
+            self.edges(node).into_iter().map(|(n, _)| n)
+        }
+
+        fn edges(&self, node: &char) -> impl IntoIterator<Item = (char, u64)> {
+            match node {
+                'a' => vec![('b', 7), ('c', 2)],
+                'b' => vec![('d', 1)],
+                'c' => vec![('b', 3), ('d', 9)],
+                _ => vec![],
+            }
+        }
+    }
+
+    #[test]
+    fn searches_visit_each_node_once() {
+        let order: Vec<_> = bfs(&line(), [0]).collect();
+        assert_eq!(order, vec![(0, 0), (1, 1), (2, 1), (3, 2)]);
+
+        let order: Vec<_> = dfs(&line(), 0).collect();
+        assert_eq!(order, vec![0, 1, 2, 3]);
+
+        let from_both: Vec<_> = bfs(&line(), [3, 0, 3]).collect();
+        assert_eq!(from_both, vec![(3, 0), (0, 0), (1, 1), (2, 1)]);
+    }
+
+    #[test]
+    fn cheapest_paths() {
+        let path = dijkstra(&Weighted, 'a', |n| *n == 'd').unwrap();
+        assert_eq!(path.nodes, vec!['a', 'c', 'b', 'd']);
+        assert_eq!(path.cost, 6);
+
+        assert_eq!(dijkstra(&Weighted, 'd', |n| *n == 'a'), None);
+        assert_eq!(dijkstra(&Weighted, 'a', |n| *n == 'a').unwrap().cost, 0);
+
+        // unit costs, guided towards the goal
+        let path = astar(&line(), 0, |n| *n == 3, |n| u64::from(3 - n)).unwrap();
+        assert_eq!(path.nodes, vec![0, 2, 3]);
+    }
+
+    #[test]
+    fn topological_order_or_cycle() {
+        let order = topological_sort(&line(), [4, 0]).unwrap();
+        assert_eq!(order, vec![0, 1, 2, 3, 4]);
+
+        let looped = from_fn(|n: &u32| vec![(n + 1) % 3]);
+        assert!(topological_sort(&looped, [0]).is_err());
+    }
+
+    #[test]
+    fn components_are_found() {
+        // 0 <-> 1 -> 2 <-> 3 -> 4
+        let graph = from_fn(|n: &u32| match n {
+            0 => vec![1],
+            1 => vec![0, 2],
+            2 => vec![3],
+            3 => vec![2, 4],
+            _ => vec![],
+        });
+
+        let mut components = strongly_connected_components(&graph, [0]);
+        components.iter_mut().for_each(|c| c.sort());
+        assert_eq!(components, vec![vec![4], vec![2, 3], vec![0, 1]]);
+    }
+
+    #[test]
+    fn paths_are_counted() {
+        let counts = count_paths(&line(), [0, 4], |n| *n == 3).unwrap();
+        assert_eq!(counts[&0], 2);
+        assert_eq!(counts[&1], 1);
+        assert_eq!(counts[&4], 0);
+
+        // every path stops at the first end
+        let counts = count_paths(&line(), [0], |n| *n == 2).unwrap();
+        assert_eq!(counts[&0], 2);
+        assert!(!counts.contains_key(&3));
+
+        let looped = from_fn(|n: &u32| vec![(n + 1) % 3]);
+        assert_eq!(
+            count_paths(&looped, [0], |n| *n == 5),
+            Err(CountError::Cycle(0))
+        );
+
+        // each layer doubles the paths, 2^64 of them from the bottom
+        let layers = from_fn(|n: &u32| match n {
+            0..=63 => vec![n + 1, n + 65],
+            64 => vec![],
+            _ => vec![n - 64],
+        });
+        assert_eq!(
+            count_paths(&layers, [0], |n| *n == 64),
+            Err(CountError::Overflow(0))
+        );
+    }
+}
@@ -0,0 +1,38 @@
+//! Writing JSON strings by hand, for the few places that print JSON without
+//! pulling in a serializer.
+
+/// `s` escaped to go between the quotes of a JSON string.
+pub fn escape(s: &str) -> String {
+    let mut escaped = String::with_capacity(s.len());
+
+    for c in s.chars() {
+        match c {
+            '"' => escaped.push_str("\\\""),
+            '\\' => escaped.push_str("\\\\"),
+            '\n' => escaped.push_str("\\n"),
+            '\r' => escaped.push_str("\\r"),
+            '\t' => escaped.push_str("\\t"),
+            c if c.is_control() => escaped.push_str(&format!("\\u{:04x}", c as u32)),
+            c => escaped.push(c),
+        }
+    }
+
+    escaped
+}
+
+/// `s` as a JSON string, quotes and all.
+pub fn string(s: &str) -> String {
+    format!("\"{}\"", escape(s))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn json_escapes_strings() {
+        assert_eq!(escape("a \"b\"\n\\"), r#"a \"b\"\n\\"#);
+        assert_eq!(escape("\t\r\u{1b}\u{7f}é"), r#"\t\r\u001b\u007fé"#);
+        assert_eq!(string("x"), r#""x""#);
+    }
+}
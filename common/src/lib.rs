@@ -0,0 +1,10 @@
+pub use answer::Answer;
+
+pub mod answer;
+pub mod graph;
+pub mod json;
+pub mod math;
+pub mod params;
+pub mod parse;
+pub mod trace;
+pub mod visual;
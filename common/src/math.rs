@@ -0,0 +1,252 @@
+//! Number theory that keeps coming up in puzzles. Anything that can overflow
+//! says so by returning an `Option`, and `big` has versions that can't.
+
+pub mod big;
+
+macro_rules! gcd_lcm {
+    ($t:ty, $gcd:ident, $lcm:ident, $gcd_all:ident, $lcm_all:ident) => {
+        /// Greatest common divisor, where `gcd(0, n)` is `n`.
+        pub fn $gcd(mut a: $t, mut b: $t) -> $t {
+            while b != 0 {
+                (a, b) = (b, a % b);
+            }
+
+            a
+        }
+
+        /// Least common multiple, or nothing if it doesn't fit.
+        pub fn $lcm(a: $t, b: $t) -> Option<$t> {
+            if a == 0 || b == 0 {
+                return Some(0);
+            }
+
+            (a / $gcd(a, b)).checked_mul(b)
+        }
+
+        /// The gcd of every number, which is 0 when there are none.
+        pub fn $gcd_all(ns: &[$t]) -> $t {
+            ns.iter().fold(0, |acc, n| $gcd(acc, *n))
+        }
+
+        /// The lcm of every number, which is 1 when there are none.
+        pub fn $lcm_all(ns: &[$t]) -> Option<$t> {
+            ns.iter().try_fold(1, |acc, n| $lcm(acc, *n))
+        }
+    };
+}
+
+gcd_lcm!(u64, gcd, lcm, gcd_all, lcm_all);
+gcd_lcm!(u128, gcd_u128, lcm_u128, gcd_all_u128, lcm_all_u128);
+
+/// `(g, x, y)` where `a * x + b * y = g` and `g` is the gcd of `a` and `b`.
+/// Keep inputs well inside `i128`, as the coefficients can get as big.
+pub fn extended_gcd(a: i128, b: i128) -> (i128, i128, i128) {
+    let (mut old_r, mut r) = (a, b);
+    let (mut old_x, mut x) = (1, 0);
+    let (mut old_y, mut y) = (0, 1);
+
+    while r != 0 {
+        let q = old_r / r;
+        (old_r, r) = (r, old_r - q * r);
+        (old_x, x) = (x, old_x - q * x);
+        (old_y, y) = (y, old_y - q * y);
+    }
+
+    match old_r < 0 {
+        true => (-old_r, -old_x, -old_y),
+        false => (old_r, old_x, old_y),
+    }
+}
+
+/// The `x` in `0..m` with `a * x = 1 (mod m)`, if there is one.
+pub fn mod_inverse(a: i128, m: i128) -> Option<i128> {
+    if m <= 0 {
+        return None;
+    }
+
+    let (g, x, _) = extended_gcd(a.rem_euclid(m), m);
+
+    match g == 1 {
+        true => Some(x.rem_euclid(m)),
+        false => None,
+    }
+}
+
+/// `a * b % m` without overflowing, however big `m` is. Panics when `m` is 0.
+pub fn mul_mod(a: u128, b: u128, m: u128) -> u128 {
+    if let Some(product) = a.checked_mul(b) {
+        return product % m;
+    }
+
+    let (mut a, mut b) = (a % m, b % m);
+    let mut product = 0;
+
+    while b > 0 {
+        if b & 1 == 1 {
+            product = add_mod(product, a, m);
+        }
+
+        a = add_mod(a, a, m);
+        b >>= 1;
+    }
+
+    product
+}
+
+// `(a + b) % m` for `a` and `b` already below `m`
+fn add_mod(a: u128, b: u128, m: u128) -> u128 {
+    match a >= m - b {
+        true => a - (m - b),
+        false => a + b,
+    }
+}
+
+/// `base ^ exp % m`. Panics when `m` is 0.
+pub fn pow_mod(base: u128, mut exp: u128, m: u128) -> u128 {
+    let mut base = base % m;
+    let mut result = 1 % m;
+
+    while exp > 0 {
+        if exp & 1 == 1 {
+            result = mul_mod(result, base, m);
+        }
+
+        base = mul_mod(base, base, m);
+        exp >>= 1;
+    }
+
+    result
+}
+
+/// Solves `x = r (mod m)` for every `(r, m)` at once, giving `(x, lcm)` with
+/// `x` the smallest non-negative answer. The moduli don't have to be coprime,
+/// so there may be no answer. Moduli must be positive, and there is no answer
+/// either when their lcm doesn't fit.
+pub fn crt(congruences: &[(i128, i128)]) -> Option<(i128, i128)> {
+    congruences.iter().try_fold((0, 1), |(r1, m1), (r2, m2)| {
+        if *m2 <= 0 {
+            return None;
+        }
+
+        let r2 = r2.rem_euclid(*m2);
+        let (g, p, _) = extended_gcd(m1, *m2);
+
+        let diff = r2 - r1;
+        if diff % g != 0 {
+            return None;
+        }
+
+        let step = m2 / g;
+        let lcm = m1.checked_mul(step)?;
+
+        // how many `m1`s past `r1` the answer is
+        let k = mul_mod(
+            (diff / g).rem_euclid(step) as u128,
+            p.rem_euclid(step) as u128,
+            step as u128,
+        ) as i128;
+
+        Some((r1 + m1 * k, lcm))
+    })
+}
+
+/// The largest `r` with `r * r <= n`.
+pub fn isqrt(n: u128) -> u128 {
+    n.isqrt()
+}
+
+/// How many digits `n` has in base 10, counting 0 as one.
+pub fn digit_count(n: u64) -> u32 {
+    n.checked_ilog10().map_or(1, |d| d + 1)
+}
+
+/// The first and second halves of the digits of `n`, if there are an even
+/// number of them. `1000` becomes `(10, 0)`.
+pub fn split_digits(n: u64) -> Option<(u64, u64)> {
+    let count = digit_count(n);
+    if !count.is_multiple_of(2) {
+        return None;
+    }
+
+    let half = 10u64.pow(count / 2);
+    Some((n / half, n % half))
+}
+
+/// The digits of `a` followed by the digits of `b`, if that fits.
+pub fn concat(a: u64, b: u64) -> Option<u64> {
+    a.checked_mul(10u64.checked_pow(digit_count(b))?)?
+        .checked_add(b)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn gcd_and_lcm() {
+        assert_eq!(gcd(12, 18), 6);
+        assert_eq!(gcd(0, 7), 7);
+        assert_eq!(gcd(7, 0), 7);
+        assert_eq!(lcm(4, 6), Some(12));
+        assert_eq!(lcm(0, 6), Some(0));
+        assert_eq!(lcm(u64::MAX, u64::MAX - 1), None);
+
+        assert_eq!(gcd_all(&[]), 0);
+        assert_eq!(gcd_all(&[12, 18, 27]), 3);
+        assert_eq!(lcm_all(&[]), Some(1));
+        assert_eq!(lcm_all(&[2, 3, 4]), Some(12));
+        assert!(lcm_all_u128(&[u64::MAX as u128, u64::MAX as u128 - 1]).is_some());
+    }
+
+    #[test]
+    fn inverses() {
+        assert_eq!(extended_gcd(240, 46), (2, -9, 47));
+        assert_eq!(extended_gcd(-4, 6).0, 2);
+        assert_eq!(mod_inverse(3, 11), Some(4));
+        assert_eq!(mod_inverse(-3, 11), Some(7));
+        assert_eq!(mod_inverse(2, 4), None);
+    }
+
+    #[test]
+    fn modular_arithmetic_doesnt_overflow() {
+        let m = u128::MAX - 158;
+
+        assert_eq!(mul_mod(m - 1, m - 1, m), 1);
+        assert_eq!(pow_mod(2, 10, 1000), 24);
+        assert_eq!(pow_mod(m - 1, 3, m), m - 1);
+        assert_eq!(pow_mod(5, 0, 1), 0);
+    }
+
+    #[test]
+    fn chinese_remainders() {
+        assert_eq!(crt(&[(2, 3), (3, 5), (2, 7)]), Some((23, 105)));
+        assert_eq!(crt(&[]), Some((0, 1)));
+
+        // not coprime, with and without an answer
+        assert_eq!(crt(&[(2, 6), (8, 10)]), Some((8, 30)));
+        assert_eq!(crt(&[(1, 6), (2, 10)]), None);
+
+        assert_eq!(crt(&[(-1, 4), (0, 3)]), Some((3, 12)));
+        assert_eq!(crt(&[(1, 0)]), None);
+        assert_eq!(crt(&[(0, i128::MAX), (1, i128::MAX - 1)]), None);
+    }
+
+    #[test]
+    fn digits() {
+        assert_eq!(isqrt(99), 9);
+        assert_eq!(isqrt(100), 10);
+
+        assert_eq!(digit_count(0), 1);
+        assert_eq!(digit_count(9), 1);
+        assert_eq!(digit_count(10), 2);
+        assert_eq!(digit_count(u64::MAX), 20);
+
+        assert_eq!(split_digits(1000), Some((10, 0)));
+        assert_eq!(split_digits(253000), Some((253, 0)));
+        assert_eq!(split_digits(123), None);
+
+        assert_eq!(concat(12, 345), Some(12345));
+        assert_eq!(concat(12, 0), Some(120));
+        assert_eq!(concat(u64::MAX / 10, 9), None);
+    }
+}
@@ -0,0 +1,129 @@
+//! The same, for when the numbers outgrow `u128`.
+
+pub use num_bigint::{BigInt, BigUint};
+
+/// Greatest common divisor, never negative.
+pub fn gcd(a: &BigInt, b: &BigInt) -> BigInt {
+    let (mut a, mut b) = (a.magnitude().clone(), b.magnitude().clone());
+
+    while b != BigUint::ZERO {
+        (a, b) = (b.clone(), a % b);
+    }
+
+    a.into()
+}
+
+/// Least common multiple, never negative.
+pub fn lcm(a: &BigInt, b: &BigInt) -> BigInt {
+    let g = gcd(a, b);
+    if g == BigInt::ZERO {
+        return g;
+    }
+
+    (a / g * b).magnitude().clone().into()
+}
+
+/// The lcm of every number, which is 1 when there are none.
+pub fn lcm_all(ns: &[BigInt]) -> BigInt {
+    ns.iter().fold(BigInt::from(1), |acc, n| lcm(&acc, n))
+}
+
+/// `(g, x, y)` where `a * x + b * y = g` and `g` is the gcd of `a` and `b`.
+pub fn extended_gcd(a: &BigInt, b: &BigInt) -> (BigInt, BigInt, BigInt) {
+    let (mut old_r, mut r) = (a.clone(), b.clone());
+    let (mut old_x, mut x) = (BigInt::from(1), BigInt::ZERO);
+    let (mut old_y, mut y) = (BigInt::ZERO, BigInt::from(1));
+
+    while r != BigInt::ZERO {
+        let q = &old_r / &r;
+        (old_r, r) = (r.clone(), old_r - &q * r);
+        (old_x, x) = (x.clone(), old_x - &q * x);
+        (old_y, y) = (y.clone(), old_y - &q * y);
+    }
+
+    match old_r < BigInt::ZERO {
+        true => (-old_r, -old_x, -old_y),
+        false => (old_r, old_x, old_y),
+    }
+}
+
+/// Like [`super::crt`], without a limit on the lcm.
+pub fn crt(congruences: &[(BigInt, BigInt)]) -> Option<(BigInt, BigInt)> {
+    congruences
+        .iter()
+        .try_fold((BigInt::ZERO, BigInt::from(1)), |(r1, m1), (r2, m2)| {
+            if *m2 <= BigInt::ZERO {
+                return None;
+            }
+
+            let (g, p, _) = extended_gcd(&m1, m2);
+
+            let diff = rem_euclid(&(r2 - &r1), m2);
+            if &diff % &g != BigInt::ZERO {
+                return None;
+            }
+
+            let step = m2 / &g;
+            let k = rem_euclid(&(diff / &g * p), &step);
+
+            Some((&r1 + &m1 * k, m1 * step))
+        })
+}
+
+fn rem_euclid(a: &BigInt, m: &BigInt) -> BigInt {
+    let r = a % m;
+
+    match r < BigInt::ZERO {
+        true => r + m,
+        false => r,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn big(n: i128) -> BigInt {
+        n.into()
+    }
+
+    #[test]
+    fn matches_the_small_versions() {
+        assert_eq!(gcd(&big(-12), &big(18)), big(6));
+        assert_eq!(lcm(&big(4), &big(-6)), big(12));
+        assert_eq!(lcm(&big(0), &big(0)), big(0));
+        assert_eq!(lcm_all(&[]), big(1));
+
+        let (g, x, y) = extended_gcd(&big(240), &big(46));
+        assert_eq!((g, x, y), (big(2), big(-9), big(47)));
+
+        let congruences = [(big(2), big(6)), (big(8), big(10))];
+        assert_eq!(crt(&congruences), Some((big(8), big(30))));
+        assert_eq!(crt(&[(big(1), big(6)), (big(2), big(10))]), None);
+        assert_eq!(
+            crt(&[(big(-1), big(4)), (big(0), big(3))]),
+            Some((big(3), big(12)))
+        );
+    }
+
+    #[test]
+    fn goes_past_u128() {
+        let primes: Vec<_> = [
+            1_000_000_007i128,
+            1_000_000_009,
+            998_244_353,
+            2_147_483_647,
+            4_294_967_291,
+        ]
+        .into_iter()
+        .map(big)
+        .collect();
+
+        let product: BigInt = primes.iter().product();
+        assert!(product > big(i128::MAX));
+        assert_eq!(lcm_all(&primes), product);
+
+        let congruences: Vec<_> = primes.iter().map(|p| (p - 1, p.clone())).collect();
+        assert_eq!(crt(&congruences), Some((&product - 1, product)));
+    }
+}
@@ -0,0 +1,235 @@
+//! The constants a solution is built around, like how many times to blink, as
+//! typed parameters with defaults. They can be changed without recompiling,
+//! from `params.toml` in the day's directory and then with `--param name=value`.
+//!
+//! ```
+//! common::params! {
+//!     #[derive(Debug)]
+//!     pub struct Blinks {
+//!         /// blinks for part 1
+//!         part1_limit: usize = 25,
+//!     }
+//! }
+//! ```
+
+use std::{fs, path::Path};
+
+use toml::Value;
+
+/// Where a day keeps its parameters, next to its `Cargo.toml`.
+pub const FILE: &str = "params.toml";
+
+/// One parameter, for saying what a day takes.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Param {
+    pub name: &'static str,
+    pub default: String,
+    pub doc: &'static str,
+}
+
+/// A day's parameters. Declared with `params!`, which parses values with
+/// each field's `FromStr`.
+pub trait Params: Default {
+    fn schema() -> Vec<Param>;
+
+    /// Changes one parameter, parsing `value` as its type.
+    fn set(&mut self, name: &str, value: &str) -> Result<(), String>;
+}
+
+/// A struct of parameters, each with a type and a default. Doc comments on
+/// the fields say what they're for when the parameters are listed.
+#[macro_export]
+macro_rules! params {
+    (
+        $(#[$meta:meta])*
+        $vis:vis struct $name:ident {
+            $(
+                $(#[doc = $doc:literal])*
+                $field:ident: $ty:ty = $default:expr
+            ),* $(,)?
+        }
+    ) => {
+        $(#[$meta])*
+        $vis struct $name {
+            $( $(#[doc = $doc])* pub $field: $ty, )*
+        }
+
+        impl Default for $name {
+            fn default() -> Self {
+                Self { $( $field: $default, )* }
+            }
+        }
+
+        impl $crate::params::Params for $name {
+            fn schema() -> Vec<$crate::params::Param> {
+                let defaults = Self::default();
+
+                vec![$(
+                    $crate::params::Param {
+                        name: stringify!($field),
+                        default: defaults.$field.to_string(),
+                        doc: concat!($($doc),*),
+                    },
+                )*]
+            }
+
+            fn set(&mut self, name: &str, value: &str) -> Result<(), String> {
+                match name {
+                    $(
+                        stringify!($field) => {
+                            self.$field = value.parse::<$ty>().map_err(|e| {
+                                format!("{} should be a {}: {}", name, stringify!($ty), e)
+                            })?;
+                        }
+                    )*
+                    _ => return Err(format!("there's no parameter called {}", name)),
+                }
+
+                Ok(())
+            }
+        }
+    };
+}
+
+/// The defaults, changed by `dir/params.toml` if there is one and then by each
+/// `--param name=value` in `args`. Gives back the rest of `args`.
+pub fn load<P: Params>(dir: &Path, args: &[String]) -> Result<(P, Vec<String>), String> {
+    let mut params = P::default();
+
+    let file = dir.join(FILE);
+    if file.exists() {
+        let text = fs::read_to_string(&file).map_err(|e| format!("{}: {}", file.display(), e))?;
+        from_toml(&mut params, &text).map_err(|e| format!("{}: {}", file.display(), e))?;
+    }
+
+    let mut rest = vec![];
+    let mut args = args.iter();
+    while let Some(arg) = args.next() {
+        if arg != "--param" {
+            rest.push(arg.clone());
+            continue;
+        }
+
+        let (name, value) = args
+            .next()
+            .and_then(|a| a.split_once('='))
+            .ok_or_else(|| format!("--param needs a name=value, one of:\n{}", describe::<P>()))?;
+        params.set(name.trim(), value.trim())?;
+    }
+
+    Ok((params, rest))
+}
+
+/// Sets each key in a TOML table. Lists become their items separated by
+/// spaces, so `stones = [0, 1, 10]` reads like `--param stones="0 1 10"`.
+pub fn from_toml<P: Params>(params: &mut P, text: &str) -> Result<(), String> {
+    let table: toml::Table = text
+        .parse()
+        .map_err(|e: toml::de::Error| e.message().to_string())?;
+
+    for (name, value) in table.iter() {
+        params.set(name, &plain(value))?;
+    }
+
+    Ok(())
+}
+
+fn plain(value: &Value) -> String {
+    match value {
+        Value::String(s) => s.clone(),
+        Value::Array(items) => items.iter().map(plain).collect::<Vec<_>>().join(" "),
+        v => v.to_string(),
+    }
+}
+
+/// Every parameter with its default, as a `params.toml` to start from.
+pub fn describe<P: Params>() -> String {
+    P::schema()
+        .iter()
+        .map(|p| {
+            // numbers and flags as themselves, anything else quoted
+            let default = match (p.default.parse::<i64>(), p.default.parse::<bool>()) {
+                (Ok(n), _) => Value::Integer(n),
+                (_, Ok(b)) => Value::Boolean(b),
+                _ => Value::String(p.default.clone()),
+            };
+
+            match p.doc.trim() {
+                "" => format!("{} = {}\n", p.name, default),
+                doc => format!("# {}\n{} = {}\n", doc, p.name, default),
+            }
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    params! {
+        #[derive(Debug, PartialEq)]
+        struct Test {
+            /// how far to go
+            limit: usize = 25,
+            name: String = "AAA".to_string(),
+            diagonal: bool = false,
+        }
+    }
+
+    fn args(s: &str) -> Vec<String> {
+        s.split(' ').map(String::from).collect()
+    }
+
+    #[test]
+    fn params_have_defaults_and_a_schema() {
+        assert_eq!(Test::default().limit, 25);
+
+        let schema = Test::schema();
+        assert_eq!(schema[0].name, "limit");
+        assert_eq!(schema[0].default, "25");
+        assert_eq!(schema[0].doc.trim(), "how far to go");
+
+        assert_eq!(
+            describe::<Test>(),
+            "# how far to go\nlimit = 25\nname = \"AAA\"\ndiagonal = false\n"
+        );
+    }
+
+    #[test]
+    fn params_load_from_args() {
+        let dir = Path::new("/nowhere");
+        let (params, rest) =
+            load::<Test>(dir, &args("day input.txt --param limit=1000 extra")).unwrap();
+
+        assert_eq!(params.limit, 1000);
+        assert_eq!(rest, args("day input.txt extra"));
+
+        let error = load::<Test>(dir, &args("day --param limit=lots")).unwrap_err();
+        assert!(error.starts_with("limit should be a usize"), "{}", error);
+
+        assert!(load::<Test>(dir, &args("day --param size=1")).is_err());
+        assert!(load::<Test>(dir, &args("day --param")).is_err());
+    }
+
+    #[test]
+    fn params_load_from_toml() {
+        let mut params = Test::default();
+        from_toml(&mut params, "limit = 7\nname = \"ZZZ\"\ndiagonal = true").unwrap();
+
+        assert_eq!(
+            params,
+            Test {
+                limit: 7,
+                name: "ZZZ".to_string(),
+                diagonal: true,
+            }
+        );
+
+        // what `describe` writes reads back in
+        from_toml(&mut params, &describe::<Test>()).unwrap();
+        assert_eq!(params, Test::default());
+
+        assert!(from_toml(&mut params, "limit = [").is_err());
+        assert!(from_toml(&mut params, "limit = -1").is_err());
+    }
+}
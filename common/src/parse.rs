@@ -0,0 +1,299 @@
+//! nom combinators for the grammars puzzle inputs keep using, and errors that
+//! say where in the input parsing went wrong.
+//!
+//! Lists here are strict: once a separator is found the next item has to
+//! parse, so a bad line is an error instead of the end of the list, even
+//! inside an `opt` or `alt`.
+
+use std::{error::Error, fmt::Display, str::FromStr};
+
+use nom::{
+    bytes::complete::tag,
+    character::complete::{char, digit1, line_ending, multispace0, satisfy, space0, space1},
+    combinator::{all_consuming, map_res, not, opt, peek, recognize},
+    error::{context, ErrorKind, VerboseError, VerboseErrorKind},
+    sequence::{pair, preceded, separated_pair, terminated, tuple},
+    IResult, Parser,
+};
+
+pub type Res<'a, O> = IResult<&'a str, O, VerboseError<&'a str>>;
+
+/// A number, with a `-` in front if it's negative.
+pub fn number<T: FromStr>(input: &str) -> Res<'_, T> {
+    context(
+        "a number",
+        map_res(recognize(pair(opt(char('-')), digit1)), str::parse),
+    )(input)
+}
+
+/// Numbers on one line separated by spaces, like `1 2  3`, up to the end of
+/// the line or some punctuation. Anything else after a space is a bad number.
+pub fn numbers<T: FromStr>(input: &str) -> Res<'_, Vec<T>> {
+    let word = satisfy(|c| c.is_alphanumeric() || c == '-');
+
+    separated(number, terminated(space1, peek(word)))(input)
+}
+
+/// `label: 1 2 3`, giving the numbers.
+pub fn labelled<'a, T: FromStr>(label: &'static str) -> impl FnMut(&'a str) -> Res<'a, Vec<T>> {
+    context(
+        label,
+        preceded(tuple((tag(label), char(':'), space0)), numbers),
+    )
+}
+
+/// `a` and `b` either side of `separator`, like `47|53` or `3,4`.
+pub fn pair_of<'a, A, B>(
+    a: impl Parser<&'a str, A, VerboseError<&'a str>>,
+    separator: char,
+    b: impl Parser<&'a str, B, VerboseError<&'a str>>,
+) -> impl FnMut(&'a str) -> Res<'a, (A, B)> {
+    separated_pair(a, char(separator), b)
+}
+
+/// `item`s with `separator` between them, like `75,47,61`.
+pub fn list_of<'a, O>(
+    item: impl Parser<&'a str, O, VerboseError<&'a str>>,
+    separator: char,
+) -> impl FnMut(&'a str) -> Res<'a, Vec<O>> {
+    separated(item, char(separator))
+}
+
+/// `item` on every line up to a blank line or the end.
+pub fn lines<'a, O>(
+    item: impl Parser<&'a str, O, VerboseError<&'a str>>,
+) -> impl FnMut(&'a str) -> Res<'a, Vec<O>> {
+    separated(item, tuple((space0, line_ending, not(blank_rest))))
+}
+
+/// A line with nothing but spaces on it, after the end of another.
+pub fn blank_line(input: &str) -> Res<'_, &str> {
+    // missing the blank line is one mistake, wherever in it that shows
+    recognize(pair(blank_rest, blank_rest))(input).map_err(|e| {
+        e.map(|_| VerboseError {
+            errors: vec![(input, VerboseErrorKind::Context("a blank line"))],
+        })
+    })
+}
+
+// the rest of a line with nothing but spaces on it
+fn blank_rest(input: &str) -> Res<'_, &str> {
+    recognize(pair(space0, line_ending))(input)
+}
+
+/// `section` over and over, with blank lines between.
+pub fn sections<'a, O>(
+    section: impl Parser<&'a str, O, VerboseError<&'a str>>,
+) -> impl FnMut(&'a str) -> Res<'a, Vec<O>> {
+    separated(section, blank_line)
+}
+
+// one or more `item`s, where anything but whitespace after a separator has
+// to be another item
+fn separated<'a, O, S>(
+    mut item: impl Parser<&'a str, O, VerboseError<&'a str>>,
+    mut separator: impl Parser<&'a str, S, VerboseError<&'a str>>,
+) -> impl FnMut(&'a str) -> Res<'a, Vec<O>> {
+    move |input| {
+        let (mut rest, first) = item.parse(input)?;
+        let mut items = vec![first];
+
+        loop {
+            match separator.parse(rest) {
+                Ok((next, _)) if !next.trim().is_empty() => {
+                    // too late to backtrack, so nothing around it can try
+                    // something else and lose where the error was
+                    let (after, o) = item.parse(next).map_err(|e| match e {
+                        nom::Err::Error(e) => nom::Err::Failure(e),
+                        e => e,
+                    })?;
+                    items.push(o);
+                    rest = after;
+                }
+                _ => return Ok((rest, items)),
+            }
+        }
+    }
+}
+
+/// Runs `parser` over the whole of `input`, which may end in whitespace.
+pub fn parse<'a, O>(
+    input: &'a str,
+    parser: impl Parser<&'a str, O, VerboseError<&'a str>>,
+) -> Result<O, ParseError> {
+    parse_in(input, input, parser)
+}
+
+/// Like `parse` for a `part` of `whole`, giving positions in `whole`.
+pub fn parse_in<'a, O>(
+    whole: &str,
+    part: &'a str,
+    parser: impl Parser<&'a str, O, VerboseError<&'a str>>,
+) -> Result<O, ParseError> {
+    match all_consuming(terminated(parser, multispace0))(part) {
+        Ok((_, o)) => Ok(o),
+        Err(nom::Err::Error(e) | nom::Err::Failure(e)) => Err(ParseError::new(whole, part, e)),
+        Err(nom::Err::Incomplete(_)) => Err(ParseError::at(whole, part, "more input")),
+    }
+}
+
+/// Where parsing failed, counting lines and columns from 1.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ParseError {
+    pub line: usize,
+    pub column: usize,
+    pub message: String,
+}
+
+impl ParseError {
+    fn new(whole: &str, part: &str, e: VerboseError<&str>) -> Self {
+        let Some((at, kind)) = e.errors.first() else {
+            return Self::at(whole, part, "something else");
+        };
+
+        let mut contexts = e
+            .errors
+            .iter()
+            .filter_map(|(input, kind)| match kind {
+                VerboseErrorKind::Context(c) => Some((*input, *c)),
+                _ => None,
+            })
+            .peekable();
+
+        // a context that starts where the error is names what was expected,
+        // where one that starts earlier is only what it was part of
+        let named = contexts.next_if(|(input, _)| input.as_ptr() == at.as_ptr());
+
+        // a missing character says more than whatever it was part of
+        let expected = match (kind, named) {
+            (VerboseErrorKind::Char(c), _) => format!("'{}'", c.escape_default()),
+            (VerboseErrorKind::Nom(ErrorKind::Eof), _) => "nothing more".to_string(),
+            (_, Some((_, c))) => c.to_string(),
+            (VerboseErrorKind::Nom(k), None) => k.description().to_lowercase(),
+            (VerboseErrorKind::Context(c), None) => c.to_string(),
+        };
+
+        let mut error = Self::at(whole, at, &expected);
+        if let Some((_, outer)) = contexts.last() {
+            error.message = format!("{} in {}", error.message, outer);
+        }
+
+        error
+    }
+
+    // `rest` is what was left of `whole` when parsing failed
+    fn at(whole: &str, rest: &str, expected: &str) -> Self {
+        let offset = (rest.as_ptr() as usize)
+            .checked_sub(whole.as_ptr() as usize)
+            .filter(|o| *o <= whole.len())
+            .unwrap_or(whole.len() - rest.len().min(whole.len()));
+
+        let before = &whole[..offset];
+        let line_start = before.rfind('\n').map_or(0, |i| i + 1);
+
+        Self {
+            line: before.matches('\n').count() + 1,
+            column: before[line_start..].chars().count() + 1,
+            message: format!("expected {}", expected),
+        }
+    }
+}
+
+impl Display for ParseError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "line {}, column {}: {}",
+            self.line, self.column, self.message
+        )
+    }
+}
+
+impl Error for ParseError {}
+
+impl From<ParseError> for String {
+    fn from(e: ParseError) -> Self {
+        e.to_string()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn numbers_parse() {
+        assert_eq!(parse("1 -2  30\n", numbers::<i32>), Ok(vec![1, -2, 30]));
+        assert_eq!(
+            parse("seeds: 79 14", labelled::<u64>("seeds")),
+            Ok(vec![79, 14])
+        );
+        assert_eq!(
+            parse("47|53", pair_of(number::<u8>, '|', number::<u8>)),
+            Ok((47, 53))
+        );
+        assert_eq!(
+            parse(
+                "1 2 | 3",
+                separated_pair(numbers::<u8>, tag(" | "), numbers)
+            ),
+            Ok((vec![1, 2], vec![3]))
+        );
+    }
+
+    #[test]
+    fn sections_of_lines_parse() {
+        let input = "1,2\n3,4\n\n5\n6\n";
+        let pairs = lines(pair_of(number::<u8>, ',', number::<u8>));
+        let singles = lines(number::<u8>);
+
+        assert_eq!(
+            parse(input, separated_pair(pairs, blank_line, singles)),
+            Ok((vec![(1, 2), (3, 4)], vec![5, 6]))
+        );
+
+        assert_eq!(
+            parse("1 2\n\n3\n \n4", sections(lines(numbers::<u8>))),
+            Ok(vec![vec![vec![1, 2]], vec![vec![3]], vec![vec![4]]])
+        );
+
+        let error = parse(
+            "1\n2\n3",
+            separated_pair(number::<u8>, blank_line, number::<u8>),
+        )
+        .unwrap_err();
+        assert_eq!(error.to_string(), "line 1, column 2: expected a blank line");
+
+        let error = parse("1,2\n3,x", lines(list_of(number::<u8>, ','))).unwrap_err();
+        assert_eq!(error.to_string(), "line 2, column 3: expected a number");
+
+        // a bad item isn't skipped by trying something else
+        let error = parse("1 2 x", opt(numbers::<u8>)).unwrap_err();
+        assert_eq!((error.line, error.column), (1, 5));
+
+        // trailing spaces don't end a line early
+        assert_eq!(
+            parse("1 \n2 \n\n3", sections(lines(numbers::<u8>))),
+            Ok(vec![vec![vec![1], vec![2]], vec![vec![3]]])
+        );
+    }
+
+    #[test]
+    fn errors_say_where() {
+        let error = parse("1 2\n3 x 4\n", lines(numbers::<u8>)).unwrap_err();
+        assert_eq!((error.line, error.column), (2, 3));
+        assert_eq!(error.to_string(), "line 2, column 3: expected a number");
+
+        let error = parse("seeds: 1\nseeds 2", lines(labelled::<u8>("seeds"))).unwrap_err();
+        assert_eq!((error.line, error.column), (2, 6));
+        assert_eq!(error.message, "expected ':' in seeds");
+
+        // a number too big for its type is still a number, just not one that fits
+        let error = parse("300", number::<u8>).unwrap_err();
+        assert_eq!((error.line, error.column), (1, 1));
+
+        let whole = "title\n12 x";
+        let error = parse_in(whole, &whole[6..], numbers::<u8>).unwrap_err();
+        assert_eq!((error.line, error.column), (2, 4));
+    }
+}
@@ -0,0 +1,76 @@
+//! Collecting the `tracing` spans and events days leave in their hot paths.
+//!
+//! Nothing is collected unless `AOC_TRACE` is set, which `aoc run -v` does:
+//! to `json` for an object per line, or to anything else for plain text.
+//! Either way it goes to stderr, so the answers on stdout stay readable.
+
+use std::{env, io};
+
+use tracing::{span::EnteredSpan, Level};
+use tracing_subscriber::fmt::format::FmtSpan;
+
+/// The variable that turns tracing on.
+pub const VAR: &str = "AOC_TRACE";
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Format {
+    Text,
+    Json,
+}
+
+impl Format {
+    /// The format `AOC_TRACE` asks for, if it's set to anything.
+    pub fn from_var(value: Option<&str>) -> Option<Self> {
+        match value?.trim() {
+            "" | "0" => None,
+            "json" => Some(Format::Json),
+            _ => Some(Format::Text),
+        }
+    }
+
+    /// What to set `AOC_TRACE` to for this format.
+    pub fn var(self) -> &'static str {
+        match self {
+            Format::Text => "text",
+            Format::Json => "json",
+        }
+    }
+}
+
+/// Starts collecting if `AOC_TRACE` asks for it, and opens a span for the
+/// day that lasts as long as what's returned. Spans report how long they
+/// took when they close.
+pub fn init(day: &'static str) -> EnteredSpan {
+    if let Some(format) = Format::from_var(env::var(VAR).ok().as_deref()) {
+        let subscriber = tracing_subscriber::fmt()
+            .with_max_level(Level::TRACE)
+            .with_span_events(FmtSpan::CLOSE)
+            .with_writer(io::stderr);
+
+        // something else collecting already is fine too
+        let _ = match format {
+            Format::Text => subscriber.try_init(),
+            Format::Json => subscriber.json().try_init(),
+        };
+    }
+
+    tracing::info_span!("day", day).entered()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn formats_come_from_the_variable() {
+        assert_eq!(Format::from_var(None), None);
+        assert_eq!(Format::from_var(Some("")), None);
+        assert_eq!(Format::from_var(Some("0")), None);
+        assert_eq!(Format::from_var(Some("1")), Some(Format::Text));
+        assert_eq!(Format::from_var(Some("json")), Some(Format::Json));
+        assert_eq!(
+            Format::from_var(Some(Format::Json.var())),
+            Some(Format::Json)
+        );
+    }
+}
@@ -0,0 +1,518 @@
+//! Frames of a simulation, a grid with some cells highlighted and a status
+//! line, and places to send them: an animation in the terminal, a cast file
+//! for asciinema, or a PNG per frame.
+//!
+//! Simulations take an `Option<&mut dyn Sink>` and push a frame per step, so
+//! they cost nothing when nobody is watching.
+
+use std::{
+    fmt::Display,
+    fs::{self, File},
+    io::{self, BufWriter, Write},
+    path::PathBuf,
+    thread,
+    time::Duration,
+};
+
+use crate::json;
+
+mod png;
+
+/// Colours for highlighted cells.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Colour {
+    Red,
+    Green,
+    Yellow,
+    Blue,
+    Magenta,
+    Cyan,
+}
+
+impl Colour {
+    // the ANSI background colour
+    fn ansi(self) -> u8 {
+        match self {
+            Colour::Red => 41,
+            Colour::Green => 42,
+            Colour::Yellow => 43,
+            Colour::Blue => 44,
+            Colour::Magenta => 45,
+            Colour::Cyan => 46,
+        }
+    }
+
+    fn rgb(self) -> [u8; 3] {
+        match self {
+            Colour::Red => [220, 50, 47],
+            Colour::Green => [80, 200, 80],
+            Colour::Yellow => [230, 200, 40],
+            Colour::Blue => [60, 110, 230],
+            Colour::Magenta => [200, 70, 200],
+            Colour::Cyan => [40, 200, 210],
+        }
+    }
+}
+
+/// A grid of characters, some highlighted, with a status line under it.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Frame {
+    pub width: usize,
+    pub height: usize,
+    cells: Vec<char>,
+    colours: Vec<Option<Colour>>,
+    pub status: String,
+}
+
+impl Frame {
+    /// A frame of `text`, with short lines padded out with spaces.
+    pub fn from_text(text: &str) -> Self {
+        let width = text.lines().map(|l| l.chars().count()).max().unwrap_or(0);
+        let height = text.lines().count();
+
+        let cells = text
+            .lines()
+            .flat_map(|l| l.chars().chain(std::iter::repeat(' ')).take(width))
+            .collect();
+
+        Self {
+            width,
+            height,
+            cells,
+            colours: vec![None; width * height],
+            status: String::new(),
+        }
+    }
+
+    fn index(&self, x: usize, y: usize) -> Option<usize> {
+        (x < self.width && y < self.height).then_some(y * self.width + x)
+    }
+
+    pub fn get(&self, x: usize, y: usize) -> Option<char> {
+        self.index(x, y).map(|i| self.cells[i])
+    }
+
+    pub fn colour(&self, x: usize, y: usize) -> Option<Colour> {
+        self.index(x, y).and_then(|i| self.colours[i])
+    }
+
+    /// Changes a cell, if it's in the frame.
+    pub fn set(&mut self, x: usize, y: usize, c: char) {
+        if let Some(i) = self.index(x, y) {
+            self.cells[i] = c;
+        }
+    }
+
+    /// Colours a cell, if it's in the frame.
+    pub fn highlight(&mut self, x: usize, y: usize, colour: Colour) {
+        if let Some(i) = self.index(x, y) {
+            self.colours[i] = Some(colour);
+        }
+    }
+
+    pub fn unhighlight(&mut self, x: usize, y: usize) {
+        if let Some(i) = self.index(x, y) {
+            self.colours[i] = None;
+        }
+    }
+
+    /// The frame with highlights as ANSI background colours, ending in the
+    /// status line.
+    pub fn to_ansi(&self) -> String {
+        let mut s = String::new();
+
+        for y in 0..self.height {
+            let mut current = None;
+
+            for x in 0..self.width {
+                let colour = self.colours[y * self.width + x];
+                if colour != current {
+                    match colour {
+                        Some(c) => s.push_str(&format!("\x1b[30;{}m", c.ansi())),
+                        None => s.push_str("\x1b[0m"),
+                    }
+                    current = colour;
+                }
+
+                s.push(self.cells[y * self.width + x]);
+            }
+
+            if current.is_some() {
+                s.push_str("\x1b[0m");
+            }
+            s.push('\n');
+        }
+
+        s.push_str(&self.status);
+        s.push('\n');
+        s
+    }
+}
+
+impl Display for Frame {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        for row in self.cells.chunks(self.width.max(1)) {
+            writeln!(f, "{}", row.iter().collect::<String>())?;
+        }
+
+        write!(f, "{}", self.status)
+    }
+}
+
+/// Somewhere to send frames. Writing can fail part way through a simulation,
+/// so the first error is kept and given back by `finish`.
+pub trait Sink {
+    fn frame(&mut self, frame: &Frame);
+
+    /// Called once the simulation is over.
+    fn finish(&mut self) -> io::Result<()> {
+        Ok(())
+    }
+}
+
+impl<S: Sink + ?Sized> Sink for Box<S> {
+    fn frame(&mut self, frame: &Frame) {
+        (**self).frame(frame)
+    }
+
+    fn finish(&mut self) -> io::Result<()> {
+        (**self).finish()
+    }
+}
+
+/// Keeps every frame, mostly for tests.
+impl Sink for Vec<Frame> {
+    fn frame(&mut self, frame: &Frame) {
+        self.push(frame.clone());
+    }
+}
+
+// keeps the first error only, as later ones are usually the same again
+fn keep(error: &mut Option<io::Error>, result: io::Result<()>) {
+    if let (None, Err(e)) = (&error, result) {
+        *error = Some(e);
+    }
+}
+
+/// Passes on every `n`th frame, and the last, to speed up long simulations.
+pub struct Every<S> {
+    sink: S,
+    n: usize,
+    seen: usize,
+    // the last frame, if it was skipped
+    skipped: Option<Frame>,
+}
+
+impl<S: Sink> Every<S> {
+    /// `n` of 0 is taken as 1.
+    pub fn new(sink: S, n: usize) -> Self {
+        Self {
+            sink,
+            n: n.max(1),
+            seen: 0,
+            skipped: None,
+        }
+    }
+}
+
+impl<S: Sink> Sink for Every<S> {
+    fn frame(&mut self, frame: &Frame) {
+        self.seen += 1;
+
+        if (self.seen - 1).is_multiple_of(self.n) {
+            self.sink.frame(frame);
+            self.skipped = None;
+        } else {
+            match &mut self.skipped {
+                Some(skipped) => skipped.clone_from(frame),
+                None => self.skipped = Some(frame.clone()),
+            }
+        }
+    }
+
+    fn finish(&mut self) -> io::Result<()> {
+        if let Some(frame) = self.skipped.take() {
+            self.sink.frame(&frame);
+        }
+
+        self.sink.finish()
+    }
+}
+
+/// Redraws the terminal for every frame, waiting between them.
+pub struct Terminal<W: Write> {
+    out: W,
+    delay: Duration,
+    error: Option<io::Error>,
+}
+
+impl<W: Write> Terminal<W> {
+    /// Shows `fps` frames a second, or as many as it can when it's 0.
+    pub fn new(out: W, fps: u32) -> Self {
+        let delay = match fps {
+            0 => Duration::ZERO,
+            fps => Duration::from_secs(1) / fps,
+        };
+
+        Self {
+            out,
+            delay,
+            error: None,
+        }
+    }
+}
+
+impl<W: Write> Sink for Terminal<W> {
+    fn frame(&mut self, frame: &Frame) {
+        if self.error.is_some() {
+            return;
+        }
+
+        let drawn =
+            write!(self.out, "\x1b[H\x1b[2J{}", frame.to_ansi()).and_then(|_| self.out.flush());
+        keep(&mut self.error, drawn);
+
+        thread::sleep(self.delay);
+    }
+
+    fn finish(&mut self) -> io::Result<()> {
+        self.error.take().map_or(Ok(()), Err)
+    }
+}
+
+/// An asciicast v2 recording, which `asciinema play` can replay.
+pub struct Cast<W: Write> {
+    out: W,
+    // seconds between frames
+    interval: f64,
+    time: f64,
+    started: bool,
+    error: Option<io::Error>,
+}
+
+impl<W: Write> Cast<W> {
+    /// Plays `fps` frames a second, with 0 taken as 1.
+    pub fn new(out: W, fps: u32) -> Self {
+        Self {
+            out,
+            interval: 1.0 / fps.max(1) as f64,
+            time: 0.0,
+            started: false,
+            error: None,
+        }
+    }
+
+    fn write(&mut self, frame: &Frame) -> io::Result<()> {
+        if !self.started {
+            let width = frame.width.max(frame.status.chars().count());
+            let height = frame.height + 1;
+
+            writeln!(
+                self.out,
+                r#"{{"version": 2, "width": {}, "height": {}}}"#,
+                width, height
+            )?;
+            self.started = true;
+        }
+
+        let screen = format!("\x1b[H\x1b[2J{}", frame.to_ansi()).replace('\n', "\r\n");
+        writeln!(
+            self.out,
+            r#"[{:.3}, "o", "{}"]"#,
+            self.time,
+            json::escape(&screen)
+        )?;
+        self.time += self.interval;
+
+        Ok(())
+    }
+}
+
+impl<W: Write> Sink for Cast<W> {
+    fn frame(&mut self, frame: &Frame) {
+        if self.error.is_none() {
+            let written = self.write(frame);
+            keep(&mut self.error, written);
+        }
+    }
+
+    fn finish(&mut self) -> io::Result<()> {
+        if let Some(e) = self.error.take() {
+            return Err(e);
+        }
+
+        self.out.flush()
+    }
+}
+
+/// A PNG per frame in a directory, numbered in order, with each cell a
+/// square of `scale` pixels. There's no font, so cells are coloured by their
+/// highlight, or as blank for `.` and space and filled for anything else.
+pub struct Png {
+    dir: PathBuf,
+    scale: usize,
+    count: usize,
+    error: Option<io::Error>,
+}
+
+impl Png {
+    /// Creates `dir` if it isn't there yet. `scale` of 0 is taken as 1.
+    pub fn new(dir: impl Into<PathBuf>, scale: usize) -> io::Result<Self> {
+        let dir = dir.into();
+        fs::create_dir_all(&dir)?;
+
+        Ok(Self {
+            dir,
+            scale: scale.max(1),
+            count: 0,
+            error: None,
+        })
+    }
+
+    fn write(&mut self, frame: &Frame) -> io::Result<()> {
+        let (width, height) = (frame.width * self.scale, frame.height * self.scale);
+        let mut pixels = Vec::with_capacity(width * height * 3);
+
+        for y in 0..height {
+            for x in 0..width {
+                let (cx, cy) = (x / self.scale, y / self.scale);
+
+                let rgb = match (frame.colour(cx, cy), frame.get(cx, cy)) {
+                    (Some(colour), _) => colour.rgb(),
+                    (None, Some('.' | ' ')) => [24, 24, 24],
+                    (None, _) => [170, 170, 170],
+                };
+                pixels.extend(rgb);
+            }
+        }
+
+        let path = self.dir.join(format!("{:05}.png", self.count));
+        self.count += 1;
+
+        let mut out = BufWriter::new(File::create(path)?);
+        png::write(&mut out, width as u32, height as u32, &pixels)?;
+        out.flush()
+    }
+}
+
+impl Sink for Png {
+    fn frame(&mut self, frame: &Frame) {
+        if self.error.is_none() {
+            let written = self.write(frame);
+            keep(&mut self.error, written);
+        }
+    }
+
+    fn finish(&mut self) -> io::Result<()> {
+        self.error.take().map_or(Ok(()), Err)
+    }
+}
+
+/// Usage for the options `from_args` reads, for days to add to their own.
+pub const USAGE: &str = "[--animate | --cast FILE | --png DIR] [--fps N] [--every N]";
+
+/// Where frames should go from command line options: `--animate` for the
+/// terminal, `--cast FILE` or `--png DIR`, with `--fps N` (default 30) and
+/// `--every N` to only show every `N`th frame. Nothing when there are none.
+pub fn from_args(args: &[String]) -> Result<Option<Box<dyn Sink>>, String> {
+    let value = |name: &str| -> Result<Option<&String>, String> {
+        match args.iter().position(|a| a == name) {
+            Some(i) => args
+                .get(i + 1)
+                .map(Some)
+                .ok_or(format!("{} needs a value", name)),
+            None => Ok(None),
+        }
+    };
+    let number = |name: &str, default: usize| -> Result<usize, String> {
+        value(name)?.map_or(Ok(default), |v| {
+            v.parse()
+                .map_err(|_| format!("{} should be a number, not {}", name, v))
+        })
+    };
+
+    let fps = number("--fps", 30)? as u32;
+    let every = number("--every", 1)?;
+
+    let sink: Box<dyn Sink> = if let Some(path) = value("--cast")? {
+        let file = File::create(path).map_err(|e| format!("{}: {}", path, e))?;
+        Box::new(Cast::new(BufWriter::new(file), fps))
+    } else if let Some(dir) = value("--png")? {
+        Box::new(Png::new(dir, 4).map_err(|e| format!("{}: {}", dir, e))?)
+    } else if args.iter().any(|a| a == "--animate") {
+        Box::new(Terminal::new(io::stdout(), fps))
+    } else {
+        return Ok(None);
+    };
+
+    Ok(Some(Box::new(Every::new(sink, every))))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn frame() -> Frame {
+        let mut frame = Frame::from_text("#..\n.#");
+        frame.highlight(1, 0, Colour::Red);
+        frame.highlight(2, 0, Colour::Red);
+        frame.status = "step 1".to_string();
+        frame
+    }
+
+    #[test]
+    fn frames_pad_and_highlight() {
+        let frame = frame();
+
+        assert_eq!((frame.width, frame.height), (3, 2));
+        assert_eq!(frame.get(2, 1), Some(' '));
+        assert_eq!(frame.get(3, 0), None);
+        assert_eq!(frame.colour(1, 0), Some(Colour::Red));
+        assert_eq!(frame.to_string(), "#..\n.# \nstep 1");
+
+        // one colour change for the run, and a reset at the end of the row
+        assert_eq!(frame.to_ansi(), "#\x1b[30;41m..\x1b[0m\n.# \nstep 1\n");
+    }
+
+    #[test]
+    fn every_keeps_the_last_frame() {
+        let mut every = Every::new(vec![], 3);
+
+        for i in 0..5 {
+            let mut f = frame();
+            f.status = i.to_string();
+            every.frame(&f);
+        }
+        every.finish().unwrap();
+
+        let statuses: Vec<_> = every.sink.iter().map(|f| f.status.as_str()).collect();
+        assert_eq!(statuses, vec!["0", "3", "4"]);
+    }
+
+    #[test]
+    fn casts_are_json_lines() {
+        let mut out = vec![];
+        let mut cast = Cast::new(&mut out, 2);
+        cast.frame(&frame());
+        cast.frame(&frame());
+        cast.finish().unwrap();
+
+        let text = String::from_utf8(out).unwrap();
+        let lines: Vec<_> = text.lines().collect();
+
+        assert_eq!(lines[0], r#"{"version": 2, "width": 6, "height": 3}"#);
+        assert!(lines[1].starts_with(r#"[0.000, "o", "\u001b[H\u001b[2J"#));
+        assert!(lines[1].ends_with(r#"step 1\r\n"]"#));
+        assert!(lines[2].starts_with("[0.500, "));
+    }
+
+    #[test]
+    fn options_pick_a_sink() {
+        let args = |s: &str| s.split(' ').map(String::from).collect::<Vec<_>>();
+
+        assert!(from_args(&args("day input.txt")).unwrap().is_none());
+        assert!(from_args(&args("day --animate --fps 0")).unwrap().is_some());
+        assert!(from_args(&args("day --animate --every")).is_err());
+        assert!(from_args(&args("day --animate --fps x")).is_err());
+    }
+}
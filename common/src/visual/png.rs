@@ -0,0 +1,136 @@
+//! Just enough of PNG to write RGB images, without compressing them.
+
+use std::io::{self, Write};
+
+const SIGNATURE: [u8; 8] = [0x89, b'P', b'N', b'G', b'\r', b'\n', 0x1a, b'\n'];
+
+// the most a stored deflate block can hold
+const BLOCK: usize = 65535;
+
+/// Writes `pixels`, three bytes a pixel row by row, as a `width` by `height`
+/// PNG.
+pub fn write(out: &mut impl Write, width: u32, height: u32, pixels: &[u8]) -> io::Result<()> {
+    let row = width as usize * 3;
+    if pixels.len() != row * height as usize {
+        return Err(io::Error::new(
+            io::ErrorKind::InvalidInput,
+            "pixels should be 3 bytes for every pixel",
+        ));
+    }
+
+    out.write_all(&SIGNATURE)?;
+
+    let mut header = vec![];
+    header.extend(width.to_be_bytes());
+    header.extend(height.to_be_bytes());
+    // 8 bits per channel, RGB, then the only compression, filtering and
+    // interlacing there are
+    header.extend([8, 2, 0, 0, 0]);
+    chunk(out, b"IHDR", &header)?;
+
+    // every row starts with a filter type, and 0 is none
+    let mut raw = Vec::with_capacity(pixels.len() + height as usize);
+    for r in pixels.chunks(row.max(1)).take(height as usize) {
+        raw.push(0);
+        raw.extend(r);
+    }
+    if row == 0 {
+        raw = vec![0; height as usize];
+    }
+
+    chunk(out, b"IDAT", &zlib_stored(&raw))?;
+    chunk(out, b"IEND", &[])
+}
+
+fn chunk(out: &mut impl Write, kind: &[u8; 4], data: &[u8]) -> io::Result<()> {
+    out.write_all(&(data.len() as u32).to_be_bytes())?;
+    out.write_all(kind)?;
+    out.write_all(data)?;
+
+    let crc = crc32(kind.iter().chain(data));
+    out.write_all(&crc.to_be_bytes())
+}
+
+// a zlib stream of uncompressed deflate blocks
+fn zlib_stored(data: &[u8]) -> Vec<u8> {
+    let mut out = vec![0x78, 0x01];
+    let blocks: Vec<_> = data.chunks(BLOCK).collect();
+
+    if blocks.is_empty() {
+        out.extend([1, 0, 0, 0xff, 0xff]);
+    }
+
+    for (i, block) in blocks.iter().enumerate() {
+        let last = i + 1 == blocks.len();
+        let len = block.len() as u16;
+
+        out.push(last as u8);
+        out.extend(len.to_le_bytes());
+        out.extend((!len).to_le_bytes());
+        out.extend(*block);
+    }
+
+    out.extend(adler32(data).to_be_bytes());
+    out
+}
+
+fn crc32<'a>(bytes: impl IntoIterator<Item = &'a u8>) -> u32 {
+    let mut crc = !0u32;
+
+    for b in bytes {
+        crc ^= *b as u32;
+        for _ in 0..8 {
+            let mask = (crc & 1).wrapping_neg();
+            crc = (crc >> 1) ^ (0xedb8_8320 & mask);
+        }
+    }
+
+    !crc
+}
+
+fn adler32(bytes: &[u8]) -> u32 {
+    let (mut a, mut b) = (1u32, 0u32);
+
+    for byte in bytes {
+        a = (a + *byte as u32) % 65521;
+        b = (b + a) % 65521;
+    }
+
+    (b << 16) | a
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn checksums_match_known_values() {
+        assert_eq!(crc32(b"123456789"), 0xcbf4_3926);
+        assert_eq!(crc32(b"IEND"), 0xae42_6082);
+        assert_eq!(adler32(b"Wikipedia"), 0x11e6_0398);
+    }
+
+    #[test]
+    fn writes_a_png() {
+        let mut out = vec![];
+        write(&mut out, 2, 1, &[255, 0, 0, 0, 0, 255]).unwrap();
+
+        assert_eq!(out[..8], SIGNATURE);
+        assert_eq!(&out[12..16], b"IHDR");
+        assert_eq!(&out[16..24], &[0, 0, 0, 2, 0, 0, 0, 1]);
+        assert_eq!(&out[out.len() - 8..out.len() - 4], b"IEND");
+
+        assert!(write(&mut vec![], 2, 2, &[0; 6]).is_err());
+    }
+
+    #[test]
+    fn big_images_split_into_blocks() {
+        let data = vec![7; BLOCK + 10];
+        let z = zlib_stored(&data);
+
+        // two block headers, the zlib header and the checksum
+        assert_eq!(z.len(), data.len() + 2 * 5 + 2 + 4);
+        assert_eq!(z[2], 0);
+        assert_eq!(z[2 + 5 + BLOCK], 1);
+    }
+}
@@ -0,0 +1,40 @@
+use std::{error::Error, fs, path::Path};
+
+use fuzz::TARGETS;
+
+/// Writes every target's seed corpus from the samples committed next to its
+/// day, e.g. `corpus/cube_game/sample_1_3` for the third line of
+/// `2023/02/sample_1.txt`. Run it from the fuzz directory.
+fn main() -> Result<(), Box<dyn Error>> {
+    let root = Path::new(env!("CARGO_MANIFEST_DIR")).join("..");
+
+    for target in TARGETS {
+        let dir = Path::new("corpus").join(target.name);
+        fs::create_dir_all(&dir)?;
+
+        let mut seeds = 0;
+        for entry in fs::read_dir(root.join(target.day))? {
+            let path = entry?.path();
+            let name = path
+                .file_name()
+                .and_then(|n| n.to_str())
+                .unwrap_or_default();
+            let Some(stem) = name
+                .strip_suffix(".txt")
+                .filter(|s| s.starts_with("sample_"))
+            else {
+                continue;
+            };
+
+            let text = fs::read_to_string(&path)?;
+            for (i, input) in (target.split)(&text).into_iter().enumerate() {
+                fs::write(dir.join(format!("{}_{}", stem, i + 1)), input)?;
+                seeds += 1;
+            }
+        }
+
+        println!("{}: {} seeds", target.name, seeds);
+    }
+
+    Ok(())
+}
@@ -0,0 +1,25 @@
+#![no_main]
+
+use camel::{Hand, Rules};
+use libfuzzer_sys::fuzz_target;
+
+// hands aren't `FromStr` since they need the rules to parse
+fuzz_target!(|data: &[u8]| {
+    let Ok(s) = std::str::from_utf8(data) else {
+        return;
+    };
+
+    for rules in [Rules::camel(), Rules::camel_jokers(), Rules::poker()] {
+        let Ok(hand) = Hand::new(s, &rules) else {
+            continue;
+        };
+
+        let text = hand.to_string();
+        let again = Hand::new(&text, &rules)
+            .unwrap_or_else(|e| panic!("{:?} printed as {:?}, which fails: {}", hand, text, e));
+
+        assert_eq!(hand.cards, again.cards, "printed as {:?}", text);
+        assert_eq!(hand.bid, again.bid, "printed as {:?}", text);
+        assert_eq!(hand, again, "printed as {:?}", text);
+    }
+});
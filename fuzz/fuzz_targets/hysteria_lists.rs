@@ -0,0 +1,34 @@
+#![no_main]
+
+use libfuzzer_sys::fuzz_target;
+
+// lists never fail to parse, bad lines become warnings, so check the columns
+// print back as rows that parse cleanly to the same columns
+fuzz_target!(|data: &[u8]| {
+    let Ok(s) = std::str::from_utf8(data) else {
+        return;
+    };
+
+    let lists = hysteria::parse(s);
+    let rows = lists.columns.first().map_or(0, |c| c.len());
+    assert!(lists.columns.iter().all(|c| c.len() == rows), "{:?}", lists);
+
+    let text: String = (0..rows)
+        .map(|i| {
+            let row: Vec<_> = lists.columns.iter().map(|c| c[i].to_string()).collect();
+            row.join("   ") + "\n"
+        })
+        .collect();
+    let again = hysteria::parse(&text);
+
+    assert!(
+        again.warnings.is_empty(),
+        "printed as {:?}: {:?}",
+        text,
+        again
+    );
+    assert_eq!(lists.columns, again.columns, "printed as {:?}", text);
+
+    // the metrics take whatever numbers parsed, so must not overflow on them
+    let _ = lists.pairwise();
+});
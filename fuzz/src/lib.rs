@@ -0,0 +1,89 @@
+use std::{
+    fmt::{Debug, Display},
+    str::FromStr,
+};
+
+/// A fuzz target and where its seed corpus comes from.
+pub struct Target {
+    pub name: &'static str,
+    /// the day's directory, relative to the repository root
+    pub day: &'static str,
+    /// cuts a sample file into inputs for the target
+    pub split: fn(&str) -> Vec<&str>,
+}
+
+pub const TARGETS: &[Target] = &[
+    target("trebuchet_vocabulary", "2023/01", whole),
+    target("cube_game", "2023/02", lines),
+    target("cube_set", "2023/02", sets),
+    target("gear_grid", "2023/03", whole),
+    target("scratch_card", "2023/04", lines),
+    target("seed_map", "2023/05", sections),
+    target("camel_hand", "2023/07", lines),
+    target("wasteland_map", "2023/08", whole),
+    target("mirage_history", "2023/09", lines),
+    target("pipes_grid", "2023/10", whole),
+    target("hysteria_lists", "2024/01", whole),
+    target("reports_report", "2024/02", lines),
+    target("search_grid", "2024/04", whole),
+    target("search_pattern", "2024/04", whole),
+    target("print_rule", "2024/05", lines),
+    target("print_rule_set", "2024/05", sections),
+    target("bridge_equation", "2024/07", lines),
+    target("resonant_grid", "2024/08", whole),
+    target("hoof_map", "2024/10", whole),
+];
+
+const fn target(name: &'static str, day: &'static str, split: fn(&str) -> Vec<&str>) -> Target {
+    Target { name, day, split }
+}
+
+pub fn whole(s: &str) -> Vec<&str> {
+    vec![s]
+}
+
+pub fn lines(s: &str) -> Vec<&str> {
+    s.lines().collect()
+}
+
+/// Blocks separated by a blank line.
+pub fn sections(s: &str) -> Vec<&str> {
+    s.split("\n\n").collect()
+}
+
+/// The sets of cubes in each game, `3 blue, 4 red` out of
+/// `Game 1: 3 blue, 4 red; 1 red`.
+pub fn sets(s: &str) -> Vec<&str> {
+    s.lines()
+        .filter_map(|l| l.split_once(": "))
+        .flat_map(|(_, sets)| sets.split("; "))
+        .collect()
+}
+
+/// Parses the input, if it's text, and makes sure it didn't panic doing so.
+pub fn parses<T: FromStr>(data: &[u8]) {
+    if let Ok(s) = std::str::from_utf8(data) {
+        let _ = s.parse::<T>();
+    }
+}
+
+/// Whatever parses has to print back to text that parses to the same value.
+pub fn round_trip<T>(data: &[u8])
+where
+    T: FromStr + Display + PartialEq + Debug,
+    T::Err: Debug,
+{
+    let Ok(s) = std::str::from_utf8(data) else {
+        return;
+    };
+    let Ok(value) = s.parse::<T>() else {
+        return;
+    };
+
+    let text = value.to_string();
+    let again = text
+        .parse::<T>()
+        .unwrap_or_else(|e| panic!("{:?} printed as {:?}, which fails: {:?}", value, text, e));
+
+    assert_eq!(value, again, "printed as {:?}", text);
+}